@@ -88,7 +88,7 @@ fn config_for_file(b: &mut Bencher) {
             &Options {
                 config: Some(Config {
                     jsc: JscConfig {
-                        target: JscTarget::Es2016,
+                        target: Some(JscTarget::Es2016),
                         syntax: Some(Syntax::Typescript(TsConfig {
                             ..Default::default()
                         })),
@@ -161,7 +161,7 @@ macro_rules! compat {
                 &Options {
                     config: Some(Config {
                         jsc: JscConfig {
-                            target: $target,
+                            target: Some($target),
                             syntax: Some(Syntax::Typescript(TsConfig {
                                 ..Default::default()
                             })),
@@ -203,7 +203,7 @@ macro_rules! tr_only {
                         &Options {
                             config: Some(Config {
                                 jsc: JscConfig {
-                                    target: $target,
+                                    target: Some($target),
                                     syntax: Some(Syntax::Typescript(TsConfig {
                                         ..Default::default()
                                     })),
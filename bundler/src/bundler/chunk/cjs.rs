@@ -4,15 +4,19 @@ use crate::{
         chunk::{merge::Ctx, plan::Dependancy},
         load::TransformedModule,
     },
-    Bundler, Load, Resolve,
+    Bundler, Load, ModuleId, Resolve,
 };
 use anyhow::Error;
-use std::{borrow::Cow, sync::atomic::Ordering};
-use swc_atoms::js_word;
+use std::{borrow::Cow, collections::HashSet, sync::atomic::Ordering};
+use swc_atoms::{js_word, JsWord};
 use swc_common::{SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::{ModuleItem, *};
-use swc_ecma_utils::{prepend, quote_ident, undefined, ExprFactory};
-use swc_ecma_visit::{noop_visit_mut_type, FoldWith, VisitMut, VisitMutWith};
+use swc_ecma_utils::{
+    find_ids, is_valid_ident, prepend, quote_ident, undefined, ExprExt, ExprFactory, Id,
+};
+use swc_ecma_visit::{
+    noop_visit_mut_type, noop_visit_type, FoldWith, Node, Visit, VisitMut, VisitMutWith, VisitWith,
+};
 
 impl<L, R> Bundler<'_, L, R>
 where
@@ -54,24 +58,38 @@ where
             return Ok(());
         }
 
-        log::debug!("Merging as a common js module: {}", info.fm.name);
-        // If src is none, all requires are transpiled
-        let mut v = RequireReplacer {
-            is_entry,
-            ctxt: dep_info.export_ctxt(),
-            load_var: Ident::new("load".into(), DUMMY_SP.with_ctxt(dep_info.export_ctxt())),
-            replaced: false,
-        };
-        entry.body.visit_mut_with(&mut v);
-
-        if v.replaced {
-            if let Some(idx) = targets.iter().position(|v| v.id == dep_info.id) {
-                targets.remove(idx);
-            }
-
-            let load_var = v.load_var;
+        // Modules which only assign to `exports` are hoisted, and unused exports
+        // are removed.
+        //
+        // Hoisted modules are not cached like `__spack_require__` does, so a
+        // module merged into multiple modules is wrapped instead.
+        let hoisted = is_merged_once(ctx, dep_info.id)
+            && match analyze_exports(&dep) {
+                Some(exports) => match analyze_usage(entry, dep_info.export_ctxt(), is_entry) {
+                    Some(used) => {
+                        log::debug!("Hoisting a common js module: {}", dep_info.fm.name);
+                        hoist_module(entry, exports, dep_info.export_ctxt(), &used);
+                        true
+                    }
+                    None => false,
+                },
+                None => false,
+            };
+
+        let replaced = hoisted || {
+            log::debug!("Merging as a common js module: {}", info.fm.name);
+            // If src is none, all requires are transpiled
+            let mut v = RequireReplacer {
+                is_entry,
+                ctxt: dep_info.export_ctxt(),
+                load_var: Ident::new("load".into(), DUMMY_SP.with_ctxt(dep_info.export_ctxt())),
+                replaced: false,
+            };
+            entry.body.visit_mut_with(&mut v);
+
+            if v.replaced {
+                let load_var = v.load_var;
 
-            {
                 info.helpers.require.store(true, Ordering::SeqCst);
 
                 let mut dep = dep.into_owned().fold_with(&mut Unexporter);
@@ -93,6 +111,14 @@ where
                 log::warn!("Injecting load");
             }
 
+            v.replaced
+        };
+
+        if replaced {
+            if let Some(idx) = targets.iter().position(|v| v.id == dep_info.id) {
+                targets.remove(idx);
+            }
+
             if let Some(normal_plan) = ctx.plan.normal.get(&dep_info.id) {
                 for dep in normal_plan.chunks.iter() {
                     if !targets.contains(&dep) {
@@ -117,6 +143,23 @@ where
     }
 }
 
+/// Returns true if the module with `id` is merged into only one module.
+fn is_merged_once(ctx: &Ctx, id: ModuleId) -> bool {
+    let merged_into = ctx
+        .plan
+        .normal
+        .values()
+        .filter(|plan| plan.chunks.iter().any(|dep| dep.id == id))
+        .count();
+
+    merged_into == 1
+        && !ctx
+            .plan
+            .circular
+            .values()
+            .any(|plan| plan.chunks.contains(&id))
+}
+
 fn wrap_module(
     helper_ctxt: SyntaxContext,
     local_ctxt: SyntaxContext,
@@ -390,3 +433,557 @@ impl VisitMut for DefaultHandler {
         }
     }
 }
+
+/// Returns `Some(name)` for `exports.name` and `module.exports.name`.
+fn exported_name(e: &Expr) -> Option<JsWord> {
+    match e {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(obj),
+            prop,
+            computed,
+            ..
+        }) => {
+            let is_exports = match &**obj {
+                Expr::Ident(i) => i.sym == *"exports",
+                obj => is_module_exports(obj),
+            };
+            if !is_exports {
+                return None;
+            }
+
+            match &**prop {
+                Expr::Ident(i) if !*computed => Some(i.sym.clone()),
+                Expr::Lit(Lit::Str(s)) if *computed => Some(s.value.clone()),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns true for `module.exports`.
+fn is_module_exports(e: &Expr) -> bool {
+    match e {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(obj),
+            prop,
+            computed: false,
+            ..
+        }) => match (&**obj, &**prop) {
+            (Expr::Ident(obj), Expr::Ident(prop)) => obj.sym == *"module" && prop.sym == *"exports",
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns true for `module.exports.name`.
+fn is_module_exports_member(e: &Expr) -> bool {
+    match e {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(obj),
+            ..
+        }) => is_module_exports(obj),
+        _ => false,
+    }
+}
+
+/// Returns `(left, right)` for `left = right;`.
+fn as_assign_stmt(s: &Stmt) -> Option<(&Expr, &Expr)> {
+    match s {
+        Stmt::Expr(ExprStmt { expr, .. }) => match &**expr {
+            Expr::Assign(AssignExpr {
+                op: op!("="),
+                left,
+                right,
+                ..
+            }) => match left {
+                PatOrExpr::Expr(left) => Some((&**left, &**right)),
+                PatOrExpr::Pat(left) => match &**left {
+                    Pat::Expr(left) => Some((&**left, &**right)),
+                    _ => None,
+                },
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// A statement of a common js module which can be hoisted.
+enum CjsItem {
+    /// `exports.foo = bar;` or `module.exports = { foo: bar }`
+    Export(Vec<(JsWord, Expr)>),
+    Stmt(Box<Stmt>),
+}
+
+/// Splits a common js module into exports and other statements.
+///
+/// Returns [None] if the exports of the module cannot be analyzed statically.
+/// This is the case if `module`, `exports` or top-level `this` is used in
+/// other ways.
+fn analyze_exports(module: &Module) -> Option<Vec<CjsItem>> {
+    let mut items = vec![];
+    let mut names = HashSet::new();
+    // `exports` does not refer to `module.exports` after it is reassigned.
+    let mut reassigned = false;
+
+    for item in &module.body {
+        let stmt = match item {
+            ModuleItem::ModuleDecl(..) => return None,
+            ModuleItem::Stmt(s) => s,
+        };
+
+        let exports = match as_assign_stmt(stmt) {
+            Some((left, right)) => match exported_name(left) {
+                Some(..) if reassigned && !is_module_exports_member(left) => return None,
+                Some(name) => Some(vec![(name, right.clone())]),
+                // `module.exports = {}` overwrites previous exports.
+                None if is_module_exports(left) && names.is_empty() => {
+                    reassigned = true;
+                    Some(object_exports(right)?)
+                }
+                None => None,
+            },
+            None => None,
+        };
+
+        match exports {
+            Some(exports) => {
+                for (name, value) in &exports {
+                    if !names.insert(name.clone()) || uses_module_object(value) {
+                        return None;
+                    }
+                }
+                items.push(CjsItem::Export(exports));
+            }
+            None => {
+                if uses_module_object(stmt) {
+                    return None;
+                }
+                items.push(CjsItem::Stmt(Box::new(stmt.clone())));
+            }
+        }
+    }
+
+    Some(items)
+}
+
+/// `{ foo, bar: baz }` => `[(foo, foo), (bar, baz)]`
+fn object_exports(e: &Expr) -> Option<Vec<(JsWord, Expr)>> {
+    let obj = match e {
+        Expr::Object(obj) => obj,
+        _ => return None,
+    };
+
+    let mut exports = vec![];
+    for prop in &obj.props {
+        match prop {
+            PropOrSpread::Prop(prop) => match &**prop {
+                Prop::Shorthand(i) => exports.push((i.sym.clone(), Expr::Ident(i.clone()))),
+                Prop::KeyValue(KeyValueProp { key, value }) => {
+                    let name = match key {
+                        PropName::Ident(i) => i.sym.clone(),
+                        PropName::Str(s) => s.value.clone(),
+                        _ => return None,
+                    };
+                    exports.push((name, (**value).clone()));
+                }
+                _ => return None,
+            },
+            PropOrSpread::Spread(..) => return None,
+        }
+    }
+
+    Some(exports)
+}
+
+/// Returns true if `module`, `exports` or top-level `this` is used.
+fn uses_module_object<N>(node: &N) -> bool
+where
+    N: VisitWith<ModuleObjectFinder>,
+{
+    let mut v = ModuleObjectFinder {
+        in_fn: false,
+        found: false,
+    };
+    node.visit_with(&Invalid { span: DUMMY_SP } as _, &mut v);
+    v.found
+}
+
+struct ModuleObjectFinder {
+    in_fn: bool,
+    found: bool,
+}
+
+impl Visit for ModuleObjectFinder {
+    noop_visit_type!();
+
+    fn visit_ident(&mut self, i: &Ident, _: &dyn Node) {
+        if i.sym == *"module" || i.sym == *"exports" {
+            self.found = true;
+        }
+    }
+
+    fn visit_member_expr(&mut self, e: &MemberExpr, _: &dyn Node) {
+        e.obj.visit_with(e as _, self);
+
+        if e.computed {
+            e.prop.visit_with(e as _, self);
+        }
+    }
+
+    fn visit_prop_name(&mut self, n: &PropName, _: &dyn Node) {
+        if let PropName::Computed(n) = n {
+            n.visit_with(n as _, self);
+        }
+    }
+
+    fn visit_function(&mut self, f: &Function, _: &dyn Node) {
+        let old = self.in_fn;
+        self.in_fn = true;
+        f.visit_children_with(self);
+        self.in_fn = old;
+    }
+
+    fn visit_class(&mut self, c: &Class, _: &dyn Node) {
+        let old = self.in_fn;
+        self.in_fn = true;
+        c.visit_children_with(self);
+        self.in_fn = old;
+    }
+
+    fn visit_this_expr(&mut self, _: &ThisExpr, _: &dyn Node) {
+        if !self.in_fn {
+            self.found = true;
+        }
+    }
+}
+
+/// Returns true for `require('foo')` if `foo` is the module with `ctxt`.
+fn is_require_of(e: &Expr, ctxt: SyntaxContext) -> bool {
+    match e {
+        Expr::Call(CallExpr {
+            callee: ExprOrSuper::Expr(callee),
+            args,
+            ..
+        }) if args.len() == 1 => match (&**callee, &*args[0].expr) {
+            (Expr::Ident(i), Expr::Lit(Lit::Str(..))) => {
+                i.sym == js_word!("require") && i.span.ctxt == ctxt
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns names accessed from the module with `ctxt`.
+///
+/// Returns [None] if the module is used in ways other than
+///
+///  - `require('foo');`
+///  - `const { a, b: c } = require('foo');`
+///  - `import { a, b as c } from 'foo';`
+///
+/// at the top level.
+fn analyze_usage(entry: &Module, ctxt: SyntaxContext, is_entry: bool) -> Option<HashSet<JsWord>> {
+    let mut v = RequireCounter {
+        ctxt,
+        requires: 0,
+        imports: 0,
+    };
+    entry.visit_with(&Invalid { span: DUMMY_SP } as _, &mut v);
+    if v.requires + v.imports == 0 || (!is_entry && v.imports != 0) {
+        return None;
+    }
+
+    let mut names = HashSet::new();
+    let mut requires = 0;
+    let mut imports = 0;
+
+    for item in &entry.body {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) if import.span.ctxt == ctxt => {
+                imports += 1;
+                for s in &import.specifiers {
+                    match s {
+                        ImportSpecifier::Named(s) => {
+                            names.insert(s.imported.as_ref().unwrap_or(&s.local).sym.clone());
+                        }
+                        ImportSpecifier::Default(..) => {
+                            names.insert(js_word!("default"));
+                        }
+                        ImportSpecifier::Namespace(..) => return None,
+                    }
+                }
+            }
+
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) if is_require_of(expr, ctxt) => {
+                requires += 1;
+            }
+
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => {
+                for decl in &var.decls {
+                    match &decl.init {
+                        Some(init) if is_require_of(init, ctxt) => {}
+                        _ => continue,
+                    }
+                    requires += 1;
+
+                    let obj = match &decl.name {
+                        Pat::Object(obj) => obj,
+                        _ => return None,
+                    };
+                    for prop in &obj.props {
+                        match prop {
+                            ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => {
+                                match &**value {
+                                    Pat::Ident(..) => {}
+                                    _ => return None,
+                                }
+                                match key {
+                                    PropName::Ident(i) => names.insert(i.sym.clone()),
+                                    PropName::Str(s) => names.insert(s.value.clone()),
+                                    _ => return None,
+                                };
+                            }
+                            ObjectPatProp::Assign(AssignPatProp {
+                                key, value: None, ..
+                            }) => {
+                                names.insert(key.sym.clone());
+                            }
+                            _ => return None,
+                        }
+                    }
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    if requires != v.requires || imports != v.imports {
+        return None;
+    }
+
+    // Used exports are hoisted as variables.
+    if names
+        .iter()
+        .any(|name| !is_valid_ident(name) || name.is_reserved_for_es3())
+    {
+        return None;
+    }
+
+    Some(names)
+}
+
+struct RequireCounter {
+    ctxt: SyntaxContext,
+    requires: usize,
+    imports: usize,
+}
+
+impl Visit for RequireCounter {
+    noop_visit_type!();
+
+    fn visit_import_decl(&mut self, i: &ImportDecl, _: &dyn Node) {
+        if i.span.ctxt == self.ctxt {
+            self.imports += 1;
+        }
+    }
+
+    fn visit_expr(&mut self, e: &Expr, _: &dyn Node) {
+        e.visit_children_with(self);
+
+        if is_require_of(e, self.ctxt) {
+            self.requires += 1;
+        }
+    }
+}
+
+/// Injects statements of a common js module to `entry` before the first usage,
+/// and replaces `require` and `import` of the module with references to the
+/// hoisted exports.
+///
+/// Exports not in `used` are removed if they don't have side effects.
+fn hoist_module(
+    entry: &mut Module,
+    items: Vec<CjsItem>,
+    ctxt: SyntaxContext,
+    used: &HashSet<JsWord>,
+) {
+    let mut exported = HashSet::new();
+    let mut stmts = vec![];
+
+    for item in items {
+        match item {
+            CjsItem::Export(exports) => {
+                for (name, value) in exports {
+                    if used.contains(&name) {
+                        exported.insert(name.clone());
+                        stmts.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+                            span: DUMMY_SP,
+                            kind: VarDeclKind::Var,
+                            declare: false,
+                            decls: vec![VarDeclarator {
+                                span: DUMMY_SP,
+                                name: Pat::Ident(Ident::new(name, DUMMY_SP.with_ctxt(ctxt))),
+                                init: Some(Box::new(value)),
+                                definite: false,
+                            }],
+                        }))));
+                    } else if value.may_have_side_effects() {
+                        stmts.push(ModuleItem::Stmt(value.into_stmt()));
+                    }
+                }
+            }
+            CjsItem::Stmt(stmt) => stmts.push(ModuleItem::Stmt(*stmt)),
+        }
+    }
+
+    // `undefined` for names not exported by the module.
+    let export_ref = |name: JsWord| -> Box<Expr> {
+        if exported.contains(&name) {
+            Box::new(Expr::Ident(Ident::new(name, DUMMY_SP.with_ctxt(ctxt))))
+        } else {
+            undefined(DUMMY_SP)
+        }
+    };
+
+    // Imported bindings may be already declared while merging the module.
+    let declared: HashSet<Id> = stmts
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => Some(find_ids::<_, Id>(var)),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+
+    // Hoisted statements are injected before the first usage.
+    let mut stmts = Some(stmts);
+    let body = entry.body.drain(..).collect::<Vec<_>>();
+    for item in body {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) if import.span.ctxt == ctxt => {
+                entry.body.extend(stmts.take().into_iter().flatten());
+
+                let decls = import
+                    .specifiers
+                    .into_iter()
+                    .filter(|s| match s {
+                        ImportSpecifier::Named(ImportNamedSpecifier { local, .. })
+                        | ImportSpecifier::Default(ImportDefaultSpecifier { local, .. }) => {
+                            !declared.contains(&(local.sym.clone(), local.span.ctxt))
+                        }
+                        ImportSpecifier::Namespace(..) => true,
+                    })
+                    .map(|s| {
+                        let (local, name) = match s {
+                            ImportSpecifier::Named(s) => {
+                                let name = s.imported.as_ref().unwrap_or(&s.local).sym.clone();
+                                (s.local, name)
+                            }
+                            ImportSpecifier::Default(s) => (s.local, js_word!("default")),
+                            ImportSpecifier::Namespace(..) => unreachable!(),
+                        };
+
+                        VarDeclarator {
+                            span: DUMMY_SP,
+                            name: Pat::Ident(local),
+                            init: Some(export_ref(name)),
+                            definite: false,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                if !decls.is_empty() {
+                    entry
+                        .body
+                        .push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+                            span: import.span.with_ctxt(SyntaxContext::empty()),
+                            kind: VarDeclKind::Var,
+                            declare: false,
+                            decls,
+                        }))));
+                }
+            }
+
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { ref expr, .. }))
+                if is_require_of(expr, ctxt) =>
+            {
+                entry.body.extend(stmts.take().into_iter().flatten());
+            }
+
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(mut var))) => {
+                let mut decls = Vec::with_capacity(var.decls.len());
+                for decl in var.decls {
+                    match &decl.init {
+                        Some(init) if is_require_of(init, ctxt) => {}
+                        _ => {
+                            decls.push(decl);
+                            continue;
+                        }
+                    }
+                    entry.body.extend(stmts.take().into_iter().flatten());
+
+                    let obj = match decl.name {
+                        Pat::Object(obj) => obj,
+                        _ => unreachable!(),
+                    };
+                    for prop in obj.props {
+                        let (name, local) = match prop {
+                            ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => {
+                                let name = match key {
+                                    PropName::Ident(i) => i.sym,
+                                    PropName::Str(s) => s.value,
+                                    _ => unreachable!(),
+                                };
+                                (name, *value)
+                            }
+                            ObjectPatProp::Assign(AssignPatProp { key, .. }) => {
+                                (key.sym.clone(), Pat::Ident(key))
+                            }
+                            ObjectPatProp::Rest(..) => unreachable!(),
+                        };
+
+                        decls.push(VarDeclarator {
+                            span: decl.span,
+                            name: local,
+                            init: Some(export_ref(name)),
+                            definite: false,
+                        });
+                    }
+                }
+
+                if !decls.is_empty() {
+                    var.decls = decls;
+                    entry
+                        .body
+                        .push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))));
+                }
+            }
+
+            _ => entry.body.push(item),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundler::tests::suite;
+
+    #[test]
+    fn exports_after_module_exports() {
+        suite().run(|t| {
+            let module = t.parse("module.exports = { a }; exports.b = 1;");
+            assert!(analyze_exports(&module).is_none());
+
+            let module = t.parse("module.exports = { a }; module.exports.b = 1;");
+            assert!(analyze_exports(&module).is_some());
+
+            Ok(())
+        });
+    }
+}
@@ -4,7 +4,7 @@ use std::mem::take;
 use swc_atoms::js_word;
 use swc_common::DUMMY_SP;
 use swc_ecma_ast::*;
-use swc_ecma_utils::{find_ids, private_ident, ExprFactory};
+use swc_ecma_utils::{find_ids, find_top_level_await, private_ident, ExprFactory};
 use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
 
 impl<L, R> Bundler<'_, L, R>
@@ -40,6 +40,9 @@ where
             None => bail!("{:?} should not be wrapped with a function", id),
         };
 
+        // A module with top level await should be evaluated before its dependants.
+        let is_async = find_top_level_await(&module).is_some();
+
         let mut module_items = vec![];
 
         let stmts = {
@@ -74,20 +77,27 @@ where
                     stmts,
                 }),
                 is_generator: false,
-                is_async: false,
+                is_async,
                 type_params: Default::default(),
                 return_type: Default::default(),
             },
             ident: None,
         });
 
-        let module_expr = Expr::Call(CallExpr {
+        let mut module_expr = Expr::Call(CallExpr {
             span: DUMMY_SP,
             callee: module_fn.as_callee(),
             type_args: Default::default(),
             args: Default::default(),
         });
 
+        if is_async {
+            module_expr = Expr::Await(AwaitExpr {
+                span: DUMMY_SP,
+                arg: Box::new(module_expr),
+            });
+        }
+
         let var_decl = VarDecl {
             span,
             declare: false,
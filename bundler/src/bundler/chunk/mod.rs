@@ -74,6 +74,13 @@ where
                     Bundle {
                         kind,
                         id: entry,
+                        file_name: self
+                            .scope
+                            .get_module(entry)
+                            .expect("module should exist at this point")
+                            .fm
+                            .name
+                            .clone(),
                         module,
                     }
                 })
@@ -7,7 +7,7 @@ use petgraph::{
 use std::collections::HashMap;
 use swc_common::DUMMY_SP;
 use swc_ecma_ast::*;
-use swc_ecma_utils::find_ids;
+use swc_ecma_utils::{find_ids, find_top_level_await};
 use swc_ecma_visit::{noop_visit_type, Node, Visit, VisitWith};
 
 type StmtDepGraph = DiGraphMap<usize, Required>;
@@ -106,6 +106,16 @@ pub(super) fn sort(new: &mut Vec<ModuleItem>) {
         }
     }
 
+    // Statements after a top level await should not be evaluated before the
+    // awaited promise settles.
+    for (idx, item) in new.iter().enumerate() {
+        if find_top_level_await(item).is_some() {
+            for later in idx + 1..new.len() {
+                graph.add_edge(idx, later, Required::Always);
+            }
+        }
+    }
+
     // Now graph contains enough information to sort statements.
     let len = new.len();
     let mut orders: Vec<usize> = vec![];
@@ -13,8 +13,8 @@ use swc_ecma_transforms::{
     helpers::{inject_helpers, HELPERS},
     hygiene,
 };
-use swc_ecma_utils::{find_ids, private_ident, ExprFactory};
-use swc_ecma_visit::{noop_fold_type, noop_visit_type, Fold, FoldWith, Node, Visit, VisitWith};
+use swc_ecma_utils::{find_ids, find_top_level_await, private_ident, ExprFactory};
+use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
 
 impl<L, R> Bundler<'_, L, R>
 where
@@ -144,10 +144,7 @@ where
             return module;
         }
 
-        let mut top_level_await_finder = TopLevelAwaitFinder::default();
-        module.visit_with(&Invalid { span: DUMMY_SP }, &mut top_level_await_finder);
-
-        let is_async = top_level_await_finder.found;
+        let is_async = find_top_level_await(&module).is_some();
 
         // Properties of returned object
         let mut props = vec![];
@@ -346,21 +343,6 @@ where
     }
 }
 
-#[derive(Default)]
-struct TopLevelAwaitFinder {
-    found: bool,
-}
-
-impl Visit for TopLevelAwaitFinder {
-    noop_visit_type!();
-
-    fn visit_stmts(&mut self, _: &[Stmt], _: &dyn Node) {}
-
-    fn visit_await_expr(&mut self, _: &AwaitExpr, _: &dyn Node) {
-        self.found = true;
-    }
-}
-
 /// Import renamer. This pass changes import path.
 struct Renamer<'a, R>
 where
@@ -62,6 +62,8 @@ pub enum BundleKind {
 pub struct Bundle {
     pub kind: BundleKind,
     pub id: ModuleId,
+    /// Path of the entry module.
+    pub file_name: FileName,
     /// Merged module.
     pub module: Module,
 }
@@ -0,0 +1,2 @@
+export const a = await Promise.resolve(1);
+console.log("a");
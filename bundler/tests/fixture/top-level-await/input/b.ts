@@ -0,0 +1,4 @@
+import { c } from "./c";
+
+export const b = c + 1;
+console.log("b");
@@ -0,0 +1,3 @@
+const value = await Promise.resolve(2);
+console.log("c");
+export const c = value;
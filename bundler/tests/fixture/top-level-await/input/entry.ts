@@ -0,0 +1,4 @@
+import * as a from "./a";
+import { b } from "./b";
+
+console.log(a, b);
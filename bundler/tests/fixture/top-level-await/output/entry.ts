@@ -0,0 +1,18 @@
+const mod = await async function() {
+    const a = await Promise.resolve(1);
+    console.log("a");
+    return {
+        a
+    };
+}();
+const a = mod;
+const value = await Promise.resolve(2);
+console.log("c");
+const c = value;
+const c1 = c;
+const c2 = c1;
+const b = c2 + 1;
+console.log("b");
+const b1 = b;
+const b2 = b1;
+console.log(a, b2);
@@ -9,6 +9,8 @@
 // except according to those terms.
 #[cfg(feature = "tty-emitter")]
 pub use self::emitter::EmitterWriter;
+#[cfg(feature = "json-emitter")]
+pub use self::json::JsonEmitter;
 use self::Level::*;
 pub use self::{
     diagnostic::{Diagnostic, DiagnosticId, DiagnosticStyledString, SubDiagnostic},
@@ -22,6 +24,7 @@ use crate::{
     sync::{Lock, LockCell},
     syntax_pos::{BytePos, FileLinesResult, FileName, Loc, MultiSpan, Span, NO_EXPANSION},
 };
+use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
     cell::RefCell,
@@ -35,11 +38,13 @@ use termcolor::{Color, ColorSpec};
 mod diagnostic;
 mod diagnostic_builder;
 pub mod emitter;
+#[cfg(feature = "json-emitter")]
+pub mod json;
 mod lock;
 mod snippet;
 mod styled_buffer;
 
-#[derive(Copy, Clone, Debug, PartialEq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Applicability {
     MachineApplicable,
     HasPlaceholders,
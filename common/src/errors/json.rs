@@ -0,0 +1,193 @@
+//! A JSON emitter for errors.
+//!
+//! This works by converting diagnostics to an internal, serializable format
+//! and then writing one JSON object per line, similar to rustc's
+//! `--error-format=json`.
+
+use super::{
+    Applicability, CodeSuggestion, Diagnostic, DiagnosticBuilder, DiagnosticId, Emitter,
+    SourceMapperDyn, SubDiagnostic,
+};
+use crate::{
+    sync::Lrc,
+    syntax_pos::{MultiSpan, Span, SpanLabel},
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// Writes each diagnostic as a json object followed by a newline.
+pub struct JsonEmitter {
+    dst: Box<dyn Write + Send>,
+    cm: Option<Lrc<SourceMapperDyn>>,
+}
+
+impl JsonEmitter {
+    pub fn new(dst: Box<dyn Write + Send>, cm: Option<Lrc<SourceMapperDyn>>) -> Self {
+        JsonEmitter { dst, cm }
+    }
+}
+
+impl Emitter for JsonEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        let data = JsonDiagnostic::from_diagnostic(db, self.cm.as_deref());
+
+        let result = serde_json::to_writer(&mut self.dst, &data)
+            .map_err(std::io::Error::from)
+            .and_then(|_| writeln!(&mut self.dst))
+            .and_then(|_| self.dst.flush());
+        // Failing to print a diagnostic should not abort the compilation.
+        if let Err(e) = result {
+            error!("failed to print diagnostics: {:?}", e);
+        }
+    }
+
+    fn should_show_explain(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonDiagnostic {
+    /// The primary error message.
+    pub message: String,
+    pub code: Option<String>,
+    /// "error", "warning", "note", "help".
+    pub level: String,
+    pub spans: Vec<JsonSpan>,
+    /// Associated diagnostic messages.
+    ///
+    /// Suggestions are stored as children with the level "help".
+    pub children: Vec<JsonDiagnostic>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonSpan {
+    pub file_name: String,
+    /// 0-based, relative to the start of the file.
+    pub byte_start: u32,
+    pub byte_end: u32,
+    /// 1-based.
+    pub line_start: usize,
+    pub line_end: usize,
+    /// 1-based, in characters.
+    pub column_start: usize,
+    pub column_end: usize,
+    /// Is this a "primary" span -- meaning the point, or one of the points,
+    /// where the error occurred?
+    pub is_primary: bool,
+    /// Label that should be placed at this location (if any)
+    pub label: Option<String>,
+    /// If we are suggesting a replacement, this will contain text
+    /// that should be sliced in atop this span.
+    pub suggested_replacement: Option<String>,
+    /// If the suggestion is approximate
+    pub suggestion_applicability: Option<Applicability>,
+}
+
+impl JsonDiagnostic {
+    pub fn from_diagnostic(diag: &Diagnostic, cm: Option<&SourceMapperDyn>) -> Self {
+        let sugg = diag.suggestions.iter().map(|sugg| JsonDiagnostic {
+            message: sugg.msg.clone(),
+            code: None,
+            level: "help".into(),
+            spans: JsonSpan::from_suggestion(sugg, cm),
+            children: vec![],
+        });
+
+        JsonDiagnostic {
+            message: diag.message(),
+            code: diag.code.as_ref().map(|code| match code {
+                DiagnosticId::Error(s) | DiagnosticId::Lint(s) => s.clone(),
+            }),
+            level: diag.level.to_str().into(),
+            spans: JsonSpan::from_multispan(&diag.span, cm),
+            children: diag
+                .children
+                .iter()
+                .map(|c| JsonDiagnostic::from_sub_diagnostic(c, cm))
+                .chain(sugg)
+                .collect(),
+        }
+    }
+
+    fn from_sub_diagnostic(diag: &SubDiagnostic, cm: Option<&SourceMapperDyn>) -> Self {
+        JsonDiagnostic {
+            message: diag.message(),
+            code: None,
+            level: diag.level.to_str().into(),
+            spans: diag
+                .render_span
+                .as_ref()
+                .map(|sp| JsonSpan::from_multispan(sp, cm))
+                .unwrap_or_else(|| JsonSpan::from_multispan(&diag.span, cm)),
+            children: vec![],
+        }
+    }
+}
+
+impl JsonSpan {
+    fn from_span_label(
+        span: SpanLabel,
+        suggestion: Option<(&String, Applicability)>,
+        cm: Option<&SourceMapperDyn>,
+    ) -> Option<Self> {
+        Self::from_span_full(span.span, span.is_primary, span.label, suggestion, cm)
+    }
+
+    fn from_span_full(
+        span: Span,
+        is_primary: bool,
+        label: Option<String>,
+        suggestion: Option<(&String, Applicability)>,
+        cm: Option<&SourceMapperDyn>,
+    ) -> Option<Self> {
+        let cm = cm?;
+        if span.is_dummy() {
+            return None;
+        }
+
+        let start = cm.lookup_char_pos(span.lo());
+        let end = cm.lookup_char_pos(span.hi());
+
+        Some(JsonSpan {
+            file_name: start.file.name.to_string(),
+            byte_start: (span.lo() - start.file.start_pos).0,
+            byte_end: (span.hi() - start.file.start_pos).0,
+            line_start: start.line,
+            line_end: end.line,
+            column_start: start.col.0 + 1,
+            column_end: end.col.0 + 1,
+            is_primary,
+            label,
+            suggested_replacement: suggestion.map(|x| x.0.clone()),
+            suggestion_applicability: suggestion.map(|x| x.1),
+        })
+    }
+
+    fn from_multispan(msp: &MultiSpan, cm: Option<&SourceMapperDyn>) -> Vec<Self> {
+        msp.span_labels()
+            .into_iter()
+            .filter_map(|span_str| Self::from_span_label(span_str, None, cm))
+            .collect()
+    }
+
+    fn from_suggestion(sugg: &CodeSuggestion, cm: Option<&SourceMapperDyn>) -> Vec<Self> {
+        sugg.substitutions
+            .iter()
+            .flat_map(|substitution| {
+                substitution.parts.iter().filter_map(move |part| {
+                    Self::from_span_full(
+                        part.span,
+                        true,
+                        None,
+                        Some((&part.snippet, sugg.applicability)),
+                        cm,
+                    )
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "json-emitter")]
+
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+use swc_common::{
+    errors::{json::JsonDiagnostic, Handler, JsonEmitter},
+    sync::Lrc,
+    BytePos, FileName, FilePathMapping, SourceMap, Span, GLOBALS,
+};
+
+#[derive(Clone, Default)]
+struct Buf(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buf {
+    fn write(&mut self, d: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(d);
+        Ok(d.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn spans_and_suggestions() {
+    GLOBALS.set(&Default::default(), || {
+        let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let fm = cm.new_source_file(
+            FileName::Real("input.js".into()),
+            "let a = 1;\nwith (a) {}\n".into(),
+        );
+        let buf = Buf::default();
+        let handler = Handler::with_emitter(
+            true,
+            false,
+            Box::new(JsonEmitter::new(Box::new(buf.clone()), Some(cm.clone()))),
+        );
+
+        let span = Span::new(
+            fm.start_pos + BytePos(11),
+            fm.start_pos + BytePos(15),
+            Default::default(),
+        );
+        handler
+            .struct_span_err(span, "with statement is not allowed")
+            .span_label(span, "used here")
+            .span_suggestion(span, "remove it", String::new())
+            .note("strict mode")
+            .emit();
+
+        let out = String::from_utf8(buf.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(out.lines().count(), 1);
+
+        let d: JsonDiagnostic = serde_json::from_str(out.trim()).unwrap();
+        assert_eq!(d.level, "error");
+        assert_eq!(d.message, "with statement is not allowed");
+        assert_eq!(d.spans.len(), 1);
+        assert_eq!(d.spans[0].file_name, "input.js");
+        assert_eq!(d.spans[0].byte_start, 11);
+        assert_eq!(d.spans[0].byte_end, 15);
+        assert_eq!(d.spans[0].line_start, 2);
+        assert_eq!(d.spans[0].column_start, 1);
+        assert_eq!(d.spans[0].column_end, 5);
+        assert!(d.spans[0].is_primary);
+        assert_eq!(d.spans[0].label.as_deref(), Some("used here"));
+
+        assert_eq!(d.children.len(), 2);
+        assert_eq!(d.children[0].level, "note");
+        assert_eq!(d.children[0].message, "strict mode");
+        assert_eq!(d.children[1].level, "help");
+        assert_eq!(
+            d.children[1].spans[0].suggested_replacement.as_deref(),
+            Some("")
+        );
+    })
+}
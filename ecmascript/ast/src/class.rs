@@ -63,6 +63,9 @@ pub enum ClassMember {
     TsIndexSignature(TsIndexSignature),
     #[tag("EmptyStatement")]
     Empty(EmptyStmt),
+    /// es2022
+    #[tag("StaticBlock")]
+    StaticBlock(StaticBlock),
 }
 
 #[ast_node("ClassProperty")]
@@ -207,6 +210,16 @@ pub struct Constructor {
     pub is_optional: bool,
 }
 
+/// `static { ... }`
+#[ast_node("StaticBlock")]
+#[derive(Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct StaticBlock {
+    pub span: Span,
+
+    pub body: BlockStmt,
+}
+
 #[ast_node("Decorator")]
 #[derive(Eq, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -388,7 +388,13 @@ impl<'a> Emitter<'a> {
         //     self.wr.write_str_lit(node.span, &s)?;
         //     return Ok(());
         // }
-        let value = escape(&self.cm, node.span, &node.value, single_quote);
+        let value = escape(
+            &self.cm,
+            node.span,
+            &node.value,
+            node.has_escape,
+            single_quote,
+        );
         // let value = node.value.replace("\n", "\\n");
 
         let single_quote = single_quote.unwrap_or(false);
@@ -824,9 +830,19 @@ impl<'a> Emitter<'a> {
             ClassMember::PrivateProp(ref n) => emit!(n),
             ClassMember::TsIndexSignature(ref n) => emit!(n),
             ClassMember::Empty(ref n) => emit!(n),
+            ClassMember::StaticBlock(ref n) => emit!(n),
         }
     }
 
+    #[emitter]
+    fn emit_static_block(&mut self, n: &StaticBlock) -> Result {
+        self.emit_leading_comments_of_pos(n.span().lo())?;
+
+        keyword!("static");
+        formatting_space!();
+        emit!(n.body);
+    }
+
     #[emitter]
     fn emit_private_method(&mut self, n: &PrivateMethod) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
@@ -946,6 +962,11 @@ impl<'a> Emitter<'a> {
             space!();
         }
 
+        if n.is_static {
+            keyword!("static");
+            space!();
+        }
+
         emit!(n.key);
         if let Some(type_ann) = &n.type_ann {
             punct!(":");
@@ -2412,9 +2433,47 @@ fn unescape(s: &str) -> String {
     result
 }
 
-fn escape<'s>(cm: &SourceMap, span: Span, s: &'s str, single_quote: Option<bool>) -> Cow<'s, str> {
+/// Escapes `s` like [str::escape_default], but other characters are
+/// printed as-is or escaped as `\uXXXX`, because `\u{...}` is not supported
+/// by es5.
+///
+/// Only the quote used for the literal is escaped if `single_quote` is known.
+fn escape_default(s: &str, single_quote: Option<bool>) -> String {
+    let mut buf = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '\t' => buf.push_str("\\t"),
+            '\r' => buf.push_str("\\r"),
+            '\n' => buf.push_str("\\n"),
+            '\'' if single_quote == Some(false) => buf.push(c),
+            '"' if single_quote == Some(true) => buf.push(c),
+            '\\' | '\'' | '"' => {
+                buf.push('\\');
+                buf.push(c);
+            }
+            // Control characters and line terminators
+            '\0'..='\x1f' | '\x7f'..='\u{9f}' | '\u{2028}' | '\u{2029}' => {
+                buf.push_str(&format!("\\u{:04X}", c as u32))
+            }
+            _ => buf.push(c),
+        }
+    }
+
+    buf
+}
+
+/// Escapes from the source text are reused, unless `has_escape` is cleared by
+/// a pass to print the value in a form supported by es5.
+fn escape<'s>(
+    cm: &SourceMap,
+    span: Span,
+    s: &'s str,
+    has_escape: bool,
+    single_quote: Option<bool>,
+) -> Cow<'s, str> {
     if span.is_dummy() {
-        return Cow::Owned(s.escape_default().to_string());
+        return Cow::Owned(escape_default(s, None));
     }
 
     //
@@ -2422,12 +2481,16 @@ fn escape<'s>(cm: &SourceMap, span: Span, s: &'s str, single_quote: Option<bool>
     let orig = match orig {
         Ok(orig) => orig,
         Err(v) => {
-            return Cow::Owned(s.escape_default().to_string());
+            return Cow::Owned(escape_default(s, None));
         }
     };
 
     if orig.len() <= 2 {
-        return Cow::Owned(s.escape_default().to_string());
+        return Cow::Owned(escape_default(s, None));
+    }
+
+    if !has_escape && orig.contains('\\') {
+        return Cow::Owned(escape_default(s, single_quote));
     }
 
     let mut orig = &*orig;
@@ -2437,7 +2500,7 @@ fn escape<'s>(cm: &SourceMap, span: Span, s: &'s str, single_quote: Option<bool>
     {
         orig = &orig[1..orig.len() - 1];
     } else {
-        return Cow::Owned(s.escape_default().to_string());
+        return Cow::Owned(escape_default(s, None));
     }
 
     let mut buf = String::with_capacity(s.len());
@@ -2463,8 +2526,10 @@ fn escape<'s>(cm: &SourceMap, span: Span, s: &'s str, single_quote: Option<bool>
                         }
                         'u' => match orig_iter.next() {
                             Some('{') => {
-                                loop {
-                                    if orig_iter.next() == Some('}') {
+                                buf.push('{');
+                                for c in &mut orig_iter {
+                                    buf.push(c);
+                                    if c == '}' {
                                         break;
                                     }
                                 }
@@ -174,6 +174,31 @@ fn no_octal_escape() {
     );
 }
 
+#[test]
+fn unicode_code_point_escape() {
+    test_from_to(
+        r#"'\u{1F600}\u{41}b';
+"\u{a}\x41";"#,
+        r#"'\u{1F600}\u{41}b';
+"\u{a}\x41";"#,
+    );
+}
+
+#[test]
+fn class_static_block() {
+    let syntax = Syntax::Es(EsConfig {
+        class_private_props: true,
+        ..Default::default()
+    });
+
+    test_from_to_custom_config(
+        "class Foo { static #a; static { this.b = #a in this; } }",
+        "class Foo{static #a;static{this.b=#a in this;}}",
+        Config { minify: true },
+        syntax,
+    );
+}
+
 #[test]
 fn empty_named_export() {
     test_from_to("export { }", "export { };");
@@ -0,0 +1,528 @@
+//! Module graph of a project, built using the resolver and the loader of
+//! `swc_bundler`.
+
+use crate::{analyze_dependencies, DependencyDescriptor, DependencyKind, DependencySymbol};
+use anyhow::{Context, Error};
+use petgraph::{algo::tarjan_scc, graphmap::DiGraphMap};
+use std::collections::{HashMap, HashSet};
+use swc_atoms::{js_word, JsWord};
+use swc_bundler::{Load, Resolve};
+use swc_common::{
+    comments::SingleThreadedComments, sync::Lrc, FileName, Globals, SourceMap, GLOBALS,
+};
+use swc_ecma_ast::*;
+use swc_ecma_utils::find_ids;
+
+pub type ModuleId = usize;
+
+#[derive(Debug)]
+pub struct GraphModule {
+    pub id: ModuleId,
+    pub file_name: FileName,
+    /// Names exported by this module, including names re-exported using
+    /// `export { foo } from 'mod'`.
+    ///
+    /// Names exported by `export * from 'mod'` are not included.
+    pub exports: Vec<JsWord>,
+    pub dependencies: Vec<Dependency>,
+}
+
+/// An edge of [ModuleGraph].
+#[derive(Debug)]
+pub struct Dependency {
+    pub descriptor: DependencyDescriptor,
+    /// `None` if the resolver failed to resolve the specifier.
+    pub target: Option<FileName>,
+}
+
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    pub entries: Vec<ModuleId>,
+    modules: Vec<GraphModule>,
+    ids: HashMap<FileName, ModuleId>,
+}
+
+/// Builds a [ModuleGraph] by walking the project starting from entries.
+///
+/// Assets (`new URL('./foo.png', import.meta.url)`) are resolved, but not
+/// loaded.
+pub struct GraphBuilder<'a, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    globals: &'a Globals,
+    cm: Lrc<SourceMap>,
+    /// Comments collected by the loader while parsing.
+    comments: &'a SingleThreadedComments,
+    loader: L,
+    resolver: R,
+}
+
+impl<'a, L, R> GraphBuilder<'a, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// `comments` should be the storage the loader passes to the parser, so
+    /// that [DependencyDescriptor::leading_comments] is populated.
+    pub fn new(
+        globals: &'a Globals,
+        cm: Lrc<SourceMap>,
+        comments: &'a SingleThreadedComments,
+        loader: L,
+        resolver: R,
+    ) -> Self {
+        GraphBuilder {
+            globals,
+            cm,
+            comments,
+            loader,
+            resolver,
+        }
+    }
+
+    pub fn build(&self, entries: Vec<FileName>) -> Result<ModuleGraph, Error> {
+        GLOBALS.set(self.globals, || {
+            let mut graph = ModuleGraph::default();
+            let mut queue = vec![];
+
+            for entry in entries {
+                let id = graph.add(entry.clone(), &mut queue);
+                graph.entries.push(id);
+            }
+
+            while let Some(id) = queue.pop() {
+                let file_name = graph.modules[id].file_name.clone();
+                let data = self
+                    .loader
+                    .load(&file_name)
+                    .with_context(|| format!("failed to load {}", file_name))?;
+
+                let descriptors = analyze_dependencies(&data.module, &self.cm, self.comments);
+
+                let mut dependencies = Vec::with_capacity(descriptors.len());
+                for descriptor in descriptors {
+                    let target = self
+                        .resolver
+                        .resolve(&file_name, &descriptor.specifier)
+                        .ok();
+
+                    if let Some(target) = &target {
+                        if descriptor.kind != DependencyKind::Asset {
+                            graph.add(target.clone(), &mut queue);
+                        }
+                    }
+
+                    dependencies.push(Dependency { descriptor, target });
+                }
+
+                let module = &mut graph.modules[id];
+                module.exports = exported_names(&data.module);
+                module.dependencies = dependencies;
+            }
+
+            Ok(graph)
+        })
+    }
+}
+
+impl ModuleGraph {
+    fn add(&mut self, file_name: FileName, queue: &mut Vec<ModuleId>) -> ModuleId {
+        if let Some(&id) = self.ids.get(&file_name) {
+            return id;
+        }
+
+        let id = self.modules.len();
+        self.ids.insert(file_name.clone(), id);
+        self.modules.push(GraphModule {
+            id,
+            file_name,
+            exports: vec![],
+            dependencies: vec![],
+        });
+        queue.push(id);
+        id
+    }
+
+    pub fn modules(&self) -> &[GraphModule] {
+        &self.modules
+    }
+
+    pub fn get(&self, file_name: &FileName) -> Option<&GraphModule> {
+        self.ids.get(file_name).map(|&id| &self.modules[id])
+    }
+
+    /// Returns the loaded module `dep` points to.
+    pub fn target(&self, dep: &Dependency) -> Option<&GraphModule> {
+        if dep.descriptor.kind == DependencyKind::Asset {
+            return None;
+        }
+
+        self.get(dep.target.as_ref()?)
+    }
+
+    /// Returns modules which depend on `file_name`, with the edge.
+    pub fn dependents(&self, file_name: &FileName) -> Vec<(&GraphModule, &Dependency)> {
+        self.modules
+            .iter()
+            .flat_map(|m| m.dependencies.iter().map(move |dep| (m, dep)))
+            .filter(|(_, dep)| dep.target.as_ref() == Some(file_name))
+            .collect()
+    }
+
+    /// Returns strongly connected components of the graph which form a cycle.
+    pub fn cycles(&self) -> Vec<Vec<FileName>> {
+        let mut g = DiGraphMap::<ModuleId, ()>::new();
+        for m in &self.modules {
+            g.add_node(m.id);
+            for dep in &m.dependencies {
+                if let Some(target) = self.target(dep) {
+                    g.add_edge(m.id, target.id, ());
+                }
+            }
+        }
+
+        tarjan_scc(&g)
+            .into_iter()
+            .filter(|scc| scc.len() > 1 || g.contains_edge(scc[0], scc[0]))
+            .map(|scc| {
+                scc.into_iter()
+                    .map(|id| self.modules[id].file_name.clone())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns exports which are not imported by any module of the graph.
+    ///
+    /// All exports of entries are considered as used. Unresolved dependencies
+    /// are ignored.
+    pub fn unused_exports(&self) -> Vec<(FileName, JsWord)> {
+        let mut queue = vec![];
+        for &entry in &self.entries {
+            queue.push((entry, Request::All));
+        }
+
+        for m in &self.modules {
+            for dep in &m.dependencies {
+                match dep.descriptor.kind {
+                    DependencyKind::Export | DependencyKind::ExportType => continue,
+                    _ => {}
+                }
+
+                if let Some(target) = self.target(dep) {
+                    for sym in &dep.descriptor.symbols {
+                        queue.push((target.id, Request::from_symbol(sym)));
+                    }
+                }
+            }
+        }
+
+        let mut used = HashSet::new();
+        let mut done = HashSet::new();
+
+        while let Some((id, req)) = queue.pop() {
+            if !done.insert((id, req.clone())) {
+                continue;
+            }
+            let module = &self.modules[id];
+
+            match req {
+                Request::All | Request::AllButDefault => {
+                    for name in &module.exports {
+                        if req == Request::All || *name != js_word!("default") {
+                            queue.push((id, Request::Name(name.clone())));
+                        }
+                    }
+                    for dep in &module.dependencies {
+                        let target = match self.target(dep) {
+                            Some(v) => v,
+                            None => continue,
+                        };
+                        if dep.descriptor.symbols.contains(&DependencySymbol::All) {
+                            queue.push((target.id, Request::AllButDefault));
+                        }
+                    }
+                }
+
+                Request::Name(name) => {
+                    used.insert((id, name.clone()));
+
+                    let found = module.exports.contains(&name);
+                    for dep in &module.dependencies {
+                        match dep.descriptor.kind {
+                            DependencyKind::Export | DependencyKind::ExportType => {}
+                            _ => continue,
+                        }
+                        let target = match self.target(dep) {
+                            Some(v) => v,
+                            None => continue,
+                        };
+
+                        for sym in &dep.descriptor.symbols {
+                            match sym {
+                                DependencySymbol::Named { imported, local } if *local == name => {
+                                    queue.push((target.id, Request::Name(imported.clone())));
+                                }
+                                DependencySymbol::Namespace(Some(local)) if *local == name => {
+                                    queue.push((target.id, Request::All));
+                                }
+                                // `export *` does not override explicit exports.
+                                DependencySymbol::All if !found => {
+                                    queue.push((target.id, Request::Name(name.clone())));
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        self.modules
+            .iter()
+            .flat_map(|m| {
+                let used = &used;
+                m.exports
+                    .iter()
+                    .filter(move |name| !used.contains(&(m.id, (*name).clone())))
+                    .map(move |name| (m.file_name.clone(), name.clone()))
+            })
+            .collect()
+    }
+}
+
+/// Names requested from a module.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Request {
+    All,
+    /// `export * from 'mod'` does not re-export the default export.
+    AllButDefault,
+    Name(JsWord),
+}
+
+impl Request {
+    fn from_symbol(sym: &DependencySymbol) -> Self {
+        match sym {
+            DependencySymbol::Named { imported, .. } => Request::Name(imported.clone()),
+            DependencySymbol::Namespace(..) => Request::All,
+            DependencySymbol::All => Request::AllButDefault,
+        }
+    }
+}
+
+fn exported_names(module: &Module) -> Vec<JsWord> {
+    let mut names = vec![];
+
+    for item in &module.body {
+        let decl = match item {
+            ModuleItem::ModuleDecl(v) => v,
+            _ => continue,
+        };
+
+        match decl {
+            ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => match decl {
+                Decl::Class(c) => names.push(c.ident.sym.clone()),
+                Decl::Fn(f) => names.push(f.ident.sym.clone()),
+                Decl::Var(v) => {
+                    let ids: Vec<Ident> = find_ids(&v.decls);
+                    names.extend(ids.into_iter().map(|i| i.sym));
+                }
+                Decl::TsInterface(i) => names.push(i.id.sym.clone()),
+                Decl::TsTypeAlias(a) => names.push(a.id.sym.clone()),
+                Decl::TsEnum(e) => names.push(e.id.sym.clone()),
+                Decl::TsModule(m) => {
+                    if let TsModuleName::Ident(i) = &m.id {
+                        names.push(i.sym.clone())
+                    }
+                }
+            },
+            ModuleDecl::ExportNamed(NamedExport { specifiers, .. }) => {
+                for s in specifiers {
+                    names.push(match s {
+                        ExportSpecifier::Namespace(s) => s.name.sym.clone(),
+                        ExportSpecifier::Default(s) => s.exported.sym.clone(),
+                        ExportSpecifier::Named(s) => {
+                            s.exported.as_ref().unwrap_or(&s.orig).sym.clone()
+                        }
+                    })
+                }
+            }
+            ModuleDecl::ExportDefaultDecl(..) | ModuleDecl::ExportDefaultExpr(..) => {
+                names.push(js_word!("default"))
+            }
+            _ => {}
+        }
+    }
+
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::bail;
+    use swc_bundler::ModuleData;
+    use swc_common::FilePathMapping;
+    use swc_ecma_parser::{lexer::Lexer, EsConfig, Parser, StringInput, Syntax};
+    use swc_ecma_transforms::helpers::Helpers;
+
+    struct Loader {
+        cm: Lrc<SourceMap>,
+        comments: SingleThreadedComments,
+        files: HashMap<&'static str, &'static str>,
+    }
+
+    impl Load for Loader {
+        fn load(&self, file: &FileName) -> Result<ModuleData, Error> {
+            let name = match file {
+                FileName::Custom(name) => name,
+                _ => unreachable!(),
+            };
+            let fm = self
+                .cm
+                .new_source_file(file.clone(), self.files[&**name].to_string());
+
+            let lexer = Lexer::new(
+                Syntax::Es(EsConfig {
+                    dynamic_import: true,
+                    import_meta: true,
+                    ..Default::default()
+                }),
+                Default::default(),
+                StringInput::from(&*fm),
+                Some(&self.comments),
+            );
+            let module = Parser::new_from(lexer)
+                .parse_module()
+                .map_err(|err| anyhow::anyhow!("failed to parse {}: {:?}", name, err))?;
+
+            Ok(ModuleData {
+                fm,
+                module,
+                helpers: Helpers::new(false),
+            })
+        }
+    }
+
+    struct Resolver;
+
+    impl Resolve for Resolver {
+        fn resolve(&self, _: &FileName, specifier: &str) -> Result<FileName, Error> {
+            if !specifier.starts_with("./") {
+                bail!("cannot resolve {}", specifier)
+            }
+
+            Ok(FileName::Custom(specifier[2..].to_string()))
+        }
+    }
+
+    fn build(files: Vec<(&'static str, &'static str)>) -> ModuleGraph {
+        let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let comments = SingleThreadedComments::default();
+        let loader = Loader {
+            cm: cm.clone(),
+            comments: comments.clone(),
+            files: files.into_iter().collect(),
+        };
+        let globals = Globals::new();
+
+        GraphBuilder::new(&globals, cm, &comments, loader, Resolver)
+            .build(vec![FileName::Custom("main.js".into())])
+            .unwrap()
+    }
+
+    fn name(s: &str) -> FileName {
+        FileName::Custom(s.into())
+    }
+
+    #[test]
+    fn edges() {
+        let graph = build(vec![
+            (
+                "main.js",
+                "import { a as b } from './a.js';
+                // @some-pragma
+                import fs from 'fs';
+                const logo = new URL('./logo.png', import.meta.url);",
+            ),
+            ("a.js", "export const a = 1;"),
+        ]);
+
+        let main = graph.get(&name("main.js")).unwrap();
+        assert_eq!(main.dependencies.len(), 3);
+
+        assert_eq!(main.dependencies[0].target, Some(name("a.js")));
+        assert_eq!(
+            main.dependencies[0].descriptor.symbols,
+            vec![DependencySymbol::Named {
+                imported: "a".into(),
+                local: "b".into()
+            }]
+        );
+        assert_eq!(main.dependencies[1].target, None);
+        assert_eq!(
+            &*main.dependencies[1].descriptor.leading_comments[0].text,
+            " @some-pragma"
+        );
+        assert_eq!(main.dependencies[2].descriptor.kind, DependencyKind::Asset);
+        assert_eq!(main.dependencies[2].target, Some(name("logo.png")));
+
+        assert_eq!(graph.modules().len(), 2);
+        assert_eq!(graph.dependents(&name("a.js")).len(), 1);
+        assert_eq!(
+            graph.get(&name("a.js")).unwrap().exports,
+            vec![JsWord::from("a")]
+        );
+    }
+
+    #[test]
+    fn cycles() {
+        let graph = build(vec![
+            ("main.js", "import './a.js';"),
+            ("a.js", "import './b.js';"),
+            ("b.js", "import './a.js'; import './c.js';"),
+            ("c.js", ""),
+        ]);
+
+        let mut cycles = graph.cycles();
+        assert_eq!(cycles.len(), 1);
+        cycles[0].sort();
+        assert_eq!(cycles[0], vec![name("a.js"), name("b.js")]);
+    }
+
+    #[test]
+    fn unused_exports() {
+        let graph = build(vec![
+            (
+                "main.js",
+                "import { a, c } from './reexport.js';
+                import * as ns from './ns.js';
+                export const main = 1;",
+            ),
+            (
+                "reexport.js",
+                "export { a } from './a.js';
+                export * from './b.js';",
+            ),
+            ("a.js", "export const a = 1; export const unusedA = 1;"),
+            (
+                "b.js",
+                "export const c = 1; export const unusedB = 1; export default 1;",
+            ),
+            ("ns.js", "export const n = 1; export default 1;"),
+        ]);
+
+        let mut unused = graph.unused_exports();
+        unused.sort();
+        assert_eq!(
+            unused,
+            vec![
+                (name("a.js"), JsWord::from("unusedA")),
+                (name("b.js"), js_word!("default")),
+                (name("b.js"), JsWord::from("unusedB")),
+            ]
+        );
+    }
+}
@@ -1,21 +1,29 @@
-use swc_atoms::JsWord;
+use std::collections::{HashMap, HashSet};
+use swc_atoms::{js_word, JsWord};
 use swc_common::{
     comments::{Comment, SingleThreadedComments},
-    Loc, SourceMap, Span, DUMMY_SP,
+    Loc, SourceMap, Span, SyntaxContext, DUMMY_SP,
 };
 use swc_ecma_ast as ast;
 use swc_ecma_visit::{self, Node, Visit, VisitWith};
 
+#[cfg(feature = "graph")]
+pub mod graph;
+
 pub fn analyze_dependencies(
     module: &ast::Module,
     source_map: &SourceMap,
     comments: &SingleThreadedComments,
 ) -> Vec<DependencyDescriptor> {
+    let mut url_bindings = UrlBindingCollector::default();
+    module.visit_with(&ast::Invalid { span: DUMMY_SP }, &mut url_bindings);
+
     let mut v = DependencyCollector {
         comments,
         source_map,
         items: vec![],
         is_top_level: true,
+        url_bindings: url_bindings.ctxts,
     };
     module.visit_with(&ast::Invalid { span: DUMMY_SP }, &mut v);
     v.items
@@ -28,6 +36,25 @@ pub enum DependencyKind {
     Export,
     ExportType,
     Require,
+    /// `new URL("./foo.png", import.meta.url)`
+    Asset,
+}
+
+/// A symbol imported or re-exported from a dependency.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DependencySymbol {
+    /// `import { foo as bar } from 'mod'` or `export { foo as bar } from
+    /// 'mod'`.
+    ///
+    /// `imported` is `default` for `import foo from 'mod'`.
+    Named { imported: JsWord, local: JsWord },
+    /// The namespace object of the dependency.
+    ///
+    /// The name is `Some` for `import * as ns from 'mod'` and `export * as ns
+    /// from 'mod'`, and `None` for `require('mod')` and `import('mod')`.
+    Namespace(Option<JsWord>),
+    /// `export * from 'mod'`
+    All,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -41,8 +68,18 @@ pub struct DependencyDescriptor {
     /// The location of the import/export statement.
     pub col: usize,
     pub line: usize,
+    /// The span of the import/export statement or the call expression.
+    pub span: Span,
     /// The text specifier associated with the import/export statement.
     pub specifier: JsWord,
+    /// The span of the specifier string literal.
+    pub specifier_span: Span,
+    /// Import assertions like `{ type: "json" }`.
+    pub import_assertions: HashMap<JsWord, JsWord>,
+    /// Symbols imported or re-exported from the dependency.
+    ///
+    /// This is empty for side-effect only imports and assets.
+    pub symbols: Vec<DependencySymbol>,
 }
 
 struct DependencyCollector<'a> {
@@ -52,6 +89,8 @@ struct DependencyCollector<'a> {
     // This field is used to determine if currently visited "require"
     // is top level and "static", or inside module body and "dynamic".
     is_top_level: bool,
+    /// Syntax contexts of the local bindings named `URL`.
+    url_bindings: HashSet<SyntaxContext>,
 }
 
 impl<'a> DependencyCollector<'a> {
@@ -62,75 +101,226 @@ impl<'a> DependencyCollector<'a> {
             .with_leading(span.lo, |comments| comments.to_vec());
         (location, leading_comments)
     }
+
+    fn add(
+        &mut self,
+        kind: DependencyKind,
+        is_dynamic: bool,
+        span: Span,
+        src: &ast::Str,
+        import_assertions: HashMap<JsWord, JsWord>,
+        symbols: Vec<DependencySymbol>,
+    ) {
+        let (location, leading_comments) = self.get_location_and_comments(span);
+        self.items.push(DependencyDescriptor {
+            kind,
+            is_dynamic,
+            leading_comments,
+            col: location.col_display,
+            line: location.line,
+            span,
+            specifier: src.value.clone(),
+            specifier_span: src.span,
+            import_assertions,
+            symbols,
+        });
+    }
+}
+
+/// Converts `{ type: "json" }` to a map.
+fn parse_import_assertions(asserts: Option<&ast::ObjectLit>) -> HashMap<JsWord, JsWord> {
+    let mut map = HashMap::default();
+
+    if let Some(asserts) = asserts {
+        for prop in &asserts.props {
+            if let ast::PropOrSpread::Prop(prop) = prop {
+                if let ast::Prop::KeyValue(ast::KeyValueProp { key, value }) = &**prop {
+                    let key = match key {
+                        ast::PropName::Ident(i) => i.sym.clone(),
+                        ast::PropName::Str(s) => s.value.clone(),
+                        _ => continue,
+                    };
+
+                    if let ast::Expr::Lit(ast::Lit::Str(s)) = &**value {
+                        map.insert(key, s.value.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    map
+}
+
+/// Collects the syntax contexts of the bindings named `URL`.
+///
+/// The contexts are only distinct if the module was processed by the
+/// resolver. Otherwise any binding named `URL` shadows the global.
+#[derive(Default)]
+struct UrlBindingCollector {
+    ctxts: HashSet<SyntaxContext>,
+}
+
+impl UrlBindingCollector {
+    fn add(&mut self, ident: &ast::Ident) {
+        if &*ident.sym == "URL" {
+            self.ctxts.insert(ident.span.ctxt);
+        }
+    }
+}
+
+impl Visit for UrlBindingCollector {
+    fn visit_pat(&mut self, pat: &ast::Pat, _parent: &dyn Node) {
+        if let ast::Pat::Ident(ident) = pat {
+            self.add(ident);
+        }
+
+        swc_ecma_visit::visit_pat(self, pat, _parent);
+    }
+
+    /// Assignment targets are not bindings.
+    fn visit_assign_expr(&mut self, node: &ast::AssignExpr, _parent: &dyn Node) {
+        if let ast::PatOrExpr::Expr(left) = &node.left {
+            left.visit_with(node as _, self);
+        }
+        node.right.visit_with(node as _, self);
+    }
+
+    fn visit_fn_decl(&mut self, node: &ast::FnDecl, _parent: &dyn Node) {
+        self.add(&node.ident);
+        swc_ecma_visit::visit_fn_decl(self, node, _parent);
+    }
+
+    fn visit_fn_expr(&mut self, node: &ast::FnExpr, _parent: &dyn Node) {
+        if let Some(ident) = &node.ident {
+            self.add(ident);
+        }
+        swc_ecma_visit::visit_fn_expr(self, node, _parent);
+    }
+
+    fn visit_class_decl(&mut self, node: &ast::ClassDecl, _parent: &dyn Node) {
+        self.add(&node.ident);
+        swc_ecma_visit::visit_class_decl(self, node, _parent);
+    }
+
+    fn visit_class_expr(&mut self, node: &ast::ClassExpr, _parent: &dyn Node) {
+        if let Some(ident) = &node.ident {
+            self.add(ident);
+        }
+        swc_ecma_visit::visit_class_expr(self, node, _parent);
+    }
+
+    fn visit_import_specifier(&mut self, node: &ast::ImportSpecifier, _parent: &dyn Node) {
+        match node {
+            ast::ImportSpecifier::Named(s) => self.add(&s.local),
+            ast::ImportSpecifier::Default(s) => self.add(&s.local),
+            ast::ImportSpecifier::Namespace(s) => self.add(&s.local),
+        }
+    }
+}
+
+/// Returns true if `e` is `import.meta.url`.
+fn is_import_meta_url(e: &ast::Expr) -> bool {
+    match e {
+        ast::Expr::Member(ast::MemberExpr {
+            obj: ast::ExprOrSuper::Expr(obj),
+            prop,
+            computed: false,
+            ..
+        }) => match (&**obj, &**prop) {
+            (ast::Expr::MetaProp(meta), ast::Expr::Ident(prop)) => {
+                &*meta.meta.sym == "import" && &*meta.prop.sym == "meta" && &*prop.sym == "url"
+            }
+            _ => false,
+        },
+        _ => false,
+    }
 }
 
 impl<'a> Visit for DependencyCollector<'a> {
     fn visit_import_decl(&mut self, node: &ast::ImportDecl, _parent: &dyn Node) {
-        let specifier = node.src.value.clone();
-        let span = node.span;
-        let (location, leading_comments) = self.get_location_and_comments(span);
         let kind = if node.type_only {
             DependencyKind::ImportType
         } else {
             DependencyKind::Import
         };
-        self.items.push(DependencyDescriptor {
+        let symbols = node
+            .specifiers
+            .iter()
+            .map(|s| match s {
+                ast::ImportSpecifier::Named(s) => DependencySymbol::Named {
+                    imported: s.imported.as_ref().unwrap_or(&s.local).sym.clone(),
+                    local: s.local.sym.clone(),
+                },
+                ast::ImportSpecifier::Default(s) => DependencySymbol::Named {
+                    imported: js_word!("default"),
+                    local: s.local.sym.clone(),
+                },
+                ast::ImportSpecifier::Namespace(s) => {
+                    DependencySymbol::Namespace(Some(s.local.sym.clone()))
+                }
+            })
+            .collect();
+
+        self.add(
             kind,
-            is_dynamic: false,
-            leading_comments,
-            col: location.col_display,
-            line: location.line,
-            specifier,
-        });
+            false,
+            node.span,
+            &node.src,
+            parse_import_assertions(node.asserts.as_ref()),
+            symbols,
+        );
     }
 
     fn visit_named_export(&mut self, node: &ast::NamedExport, _parent: &dyn Node) {
         if let Some(src) = &node.src {
-            let specifier = src.value.clone();
-            let span = node.span;
-            let (location, leading_comments) = self.get_location_and_comments(span);
             let kind = if node.type_only {
                 DependencyKind::ExportType
             } else {
                 DependencyKind::Export
             };
-            self.items.push(DependencyDescriptor {
-                kind,
-                is_dynamic: false,
-                leading_comments,
-                col: location.col_display,
-                line: location.line,
-                specifier,
-            });
+            let symbols = node
+                .specifiers
+                .iter()
+                .map(|s| match s {
+                    ast::ExportSpecifier::Named(s) => DependencySymbol::Named {
+                        imported: s.orig.sym.clone(),
+                        local: s.exported.as_ref().unwrap_or(&s.orig).sym.clone(),
+                    },
+                    ast::ExportSpecifier::Default(s) => DependencySymbol::Named {
+                        imported: js_word!("default"),
+                        local: s.exported.sym.clone(),
+                    },
+                    ast::ExportSpecifier::Namespace(s) => {
+                        DependencySymbol::Namespace(Some(s.name.sym.clone()))
+                    }
+                })
+                .collect();
+
+            self.add(kind, false, node.span, src, Default::default(), symbols);
         }
     }
 
     fn visit_export_all(&mut self, node: &ast::ExportAll, _parent: &dyn Node) {
-        let specifier = node.src.value.clone();
-        let span = node.span;
-        let (location, leading_comments) = self.get_location_and_comments(span);
-        self.items.push(DependencyDescriptor {
-            kind: DependencyKind::Export,
-            is_dynamic: false,
-            leading_comments,
-            col: location.col_display,
-            line: location.line,
-            specifier,
-        });
+        self.add(
+            DependencyKind::Export,
+            false,
+            node.span,
+            &node.src,
+            Default::default(),
+            vec![DependencySymbol::All],
+        );
     }
 
     fn visit_ts_import_type(&mut self, node: &ast::TsImportType, _parent: &dyn Node) {
-        let specifier = node.arg.value.clone();
-        let span = node.span;
-        let (location, leading_comments) = self.get_location_and_comments(span);
-        self.items.push(DependencyDescriptor {
-            kind: DependencyKind::ImportType,
-            is_dynamic: false,
-            leading_comments,
-            col: location.col_display,
-            line: location.line,
-            specifier,
-        });
+        self.add(
+            DependencyKind::ImportType,
+            false,
+            node.span,
+            &node.arg,
+            Default::default(),
+            vec![DependencySymbol::Namespace(None)],
+        );
     }
 
     fn visit_module_items(&mut self, items: &[ast::ModuleItem], _parent: &dyn Node) {
@@ -167,17 +357,65 @@ impl<'a> Visit for DependencyCollector<'a> {
         if let Some(arg) = node.args.get(0) {
             if let Lit(lit) = &*arg.expr {
                 if let ast::Lit::Str(str_) = lit {
-                    let specifier = str_.value.clone();
-                    let span = node.span;
-                    let (location, leading_comments) = self.get_location_and_comments(span);
-                    self.items.push(DependencyDescriptor {
+                    // import("./data.json", { assert: { type: "json" } })
+                    let import_assertions = match node.args.get(1).map(|arg| &*arg.expr) {
+                        Some(Object(options)) if kind == DependencyKind::Import => {
+                            options.props.iter().find_map(|prop| match prop {
+                                ast::PropOrSpread::Prop(prop) => match &**prop {
+                                    ast::Prop::KeyValue(ast::KeyValueProp {
+                                        key: ast::PropName::Ident(key),
+                                        value,
+                                    }) if &*key.sym == "assert" => match &**value {
+                                        Object(asserts) => Some(asserts),
+                                        _ => None,
+                                    },
+                                    _ => None,
+                                },
+                                _ => None,
+                            })
+                        }
+                        _ => None,
+                    };
+
+                    self.add(
                         kind,
                         is_dynamic,
-                        leading_comments,
-                        col: location.col_display,
-                        line: location.line,
-                        specifier,
-                    });
+                        node.span,
+                        str_,
+                        parse_import_assertions(import_assertions),
+                        vec![DependencySymbol::Namespace(None)],
+                    );
+                }
+            }
+        }
+    }
+
+    fn visit_new_expr(&mut self, node: &ast::NewExpr, _parent: &dyn Node) {
+        use ast::Expr::*;
+
+        swc_ecma_visit::visit_new_expr(self, node, _parent);
+
+        match &*node.callee {
+            // A local binding named `URL` is not the global.
+            Ident(ident)
+                if &*ident.sym == "URL" && !self.url_bindings.contains(&ident.span.ctxt) => {}
+            _ => return,
+        }
+
+        // new URL("./foo.png", import.meta.url)
+        if let Some(args) = &node.args {
+            if let (Some(first), Some(second)) = (args.get(0), args.get(1)) {
+                if let Lit(ast::Lit::Str(str_)) = &*first.expr {
+                    if is_import_meta_url(&second.expr) {
+                        self.add(
+                            DependencyKind::Asset,
+                            false,
+                            node.span,
+                            str_,
+                            Default::default(),
+                            vec![],
+                        );
+                    }
                 }
             }
         }
@@ -190,9 +428,10 @@ mod tests {
     use swc_common::{
         comments::{Comment, CommentKind},
         sync::Lrc,
-        BytePos, FileName, Span, SyntaxContext,
+        BytePos, FileName, Globals, Span, SyntaxContext, GLOBALS,
     };
     use swc_ecma_parser::{lexer::Lexer, JscTarget, Parser, StringInput, Syntax, TsConfig};
+    use swc_ecma_visit::FoldWith;
 
     fn helper(
         file_name: &str,
@@ -279,7 +518,11 @@ try {
                     leading_comments: Vec::new(),
                     col: 0,
                     line: 1,
-                    specifier: JsWord::from("./test.ts")
+                    specifier: JsWord::from("./test.ts"),
+                    span: Span::new(BytePos(0), BytePos(33), SyntaxContext::empty()),
+                    specifier_span: Span::new(BytePos(21), BytePos(32), SyntaxContext::empty()),
+                    import_assertions: HashMap::new(),
+                    symbols: vec![DependencySymbol::Namespace(Some("bar".into()))],
                 },
                 DependencyDescriptor {
                     kind: DependencyKind::ImportType,
@@ -291,7 +534,14 @@ try {
                     }],
                     col: 0,
                     line: 3,
-                    specifier: JsWord::from("./foo.d.ts")
+                    specifier: JsWord::from("./foo.d.ts"),
+                    span: Span::new(BytePos(47), BytePos(85), SyntaxContext::empty()),
+                    specifier_span: Span::new(BytePos(72), BytePos(84), SyntaxContext::empty()),
+                    import_assertions: HashMap::new(),
+                    symbols: vec![DependencySymbol::Named {
+                        imported: "Foo".into(),
+                        local: "Foo".into(),
+                    }],
                 },
                 DependencyDescriptor {
                     kind: DependencyKind::Export,
@@ -303,7 +553,11 @@ try {
                     }],
                     col: 0,
                     line: 5,
-                    specifier: JsWord::from("./buzz.ts")
+                    specifier: JsWord::from("./buzz.ts"),
+                    span: Span::new(BytePos(114), BytePos(147), SyntaxContext::empty()),
+                    specifier_span: Span::new(BytePos(136), BytePos(147), SyntaxContext::empty()),
+                    import_assertions: HashMap::new(),
+                    symbols: vec![DependencySymbol::Namespace(Some("Buzz".into()))],
                 },
                 DependencyDescriptor {
                     kind: DependencyKind::ExportType,
@@ -322,7 +576,14 @@ try {
                     ],
                     col: 0,
                     line: 10,
-                    specifier: JsWord::from("./fizz.d.ts")
+                    specifier: JsWord::from("./fizz.d.ts"),
+                    span: Span::new(BytePos(180), BytePos(220), SyntaxContext::empty()),
+                    specifier_span: Span::new(BytePos(206), BytePos(219), SyntaxContext::empty()),
+                    import_assertions: HashMap::new(),
+                    symbols: vec![DependencySymbol::Named {
+                        imported: "Fizz".into(),
+                        local: "Fizz".into(),
+                    }],
                 },
                 DependencyDescriptor {
                     kind: DependencyKind::Require,
@@ -330,7 +591,11 @@ try {
                     leading_comments: Vec::new(),
                     col: 17,
                     line: 11,
-                    specifier: JsWord::from("path")
+                    specifier: JsWord::from("path"),
+                    span: Span::new(BytePos(238), BytePos(253), SyntaxContext::empty()),
+                    specifier_span: Span::new(BytePos(246), BytePos(252), SyntaxContext::empty()),
+                    import_assertions: HashMap::new(),
+                    symbols: vec![DependencySymbol::Namespace(None)],
                 },
                 DependencyDescriptor {
                     kind: DependencyKind::Import,
@@ -338,7 +603,11 @@ try {
                     leading_comments: Vec::new(),
                     col: 6,
                     line: 14,
-                    specifier: JsWord::from("./foo1.ts")
+                    specifier: JsWord::from("./foo1.ts"),
+                    span: Span::new(BytePos(274), BytePos(293), SyntaxContext::empty()),
+                    specifier_span: Span::new(BytePos(281), BytePos(292), SyntaxContext::empty()),
+                    import_assertions: HashMap::new(),
+                    symbols: vec![DependencySymbol::Namespace(None)],
                 },
                 DependencyDescriptor {
                     kind: DependencyKind::Import,
@@ -346,7 +615,11 @@ try {
                     leading_comments: Vec::new(),
                     col: 22,
                     line: 17,
-                    specifier: JsWord::from("./foo.ts")
+                    specifier: JsWord::from("./foo.ts"),
+                    span: Span::new(BytePos(324), BytePos(342), SyntaxContext::empty()),
+                    specifier_span: Span::new(BytePos(331), BytePos(341), SyntaxContext::empty()),
+                    import_assertions: HashMap::new(),
+                    symbols: vec![DependencySymbol::Namespace(None)],
                 },
                 DependencyDescriptor {
                     kind: DependencyKind::Require,
@@ -354,9 +627,44 @@ try {
                     leading_comments: Vec::new(),
                     col: 16,
                     line: 23,
-                    specifier: JsWord::from("some_package")
+                    specifier: JsWord::from("some_package"),
+                    span: Span::new(BytePos(395), BytePos(418), SyntaxContext::empty()),
+                    specifier_span: Span::new(BytePos(403), BytePos(417), SyntaxContext::empty()),
+                    import_assertions: HashMap::new(),
+                    symbols: vec![DependencySymbol::Namespace(None)],
                 }
             ]
         );
     }
+
+    #[test]
+    fn test_import_assertions() {
+        let source = r#"const data = await import("./data.json", { assert: { type: "json" } });"#;
+        let (module, source_map, comments) = helper("test.ts", &source).unwrap();
+        let dependencies = analyze_dependencies(&module, &source_map, &comments);
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].specifier, JsWord::from("./data.json"));
+        assert_eq!(
+            dependencies[0].import_assertions,
+            vec![(JsWord::from("type"), JsWord::from("json"))]
+                .into_iter()
+                .collect::<HashMap<_, _>>()
+        );
+    }
+
+    #[test]
+    fn test_local_url_is_not_an_asset() {
+        let source = r#"const a = new URL("./a.png", import.meta.url);
+function foo(URL) {
+    return new URL("./b.png", import.meta.url);
+}"#;
+        let (module, source_map, comments) = helper("test.ts", &source).unwrap();
+        let module = GLOBALS.set(&Globals::new(), || {
+            module.fold_with(&mut swc_ecma_transforms::resolver())
+        });
+        let dependencies = analyze_dependencies(&module, &source_map, &comments);
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].kind, DependencyKind::Asset);
+        assert_eq!(dependencies[0].specifier, JsWord::from("./a.png"));
+    }
 }
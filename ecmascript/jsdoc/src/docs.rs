@@ -0,0 +1,326 @@
+//! Attaches JSDoc comments to the declarations they document.
+
+use crate::ast::{JsDoc, Tag};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{
+    comments::{CommentKind, SingleThreadedComments},
+    BytePos, Span, Spanned, DUMMY_SP,
+};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Node, Visit, VisitWith};
+
+/// Parsed JSDoc comments, keyed by the start of the documented node.
+#[derive(Debug, Default)]
+pub struct DocMap {
+    docs: HashMap<BytePos, JsDoc>,
+}
+
+impl DocMap {
+    /// Returns the JSDoc of a node.
+    ///
+    /// For `export function foo() {}`, the comment is attached to both of the
+    /// export declaration and the function.
+    pub fn get(&self, span: Span) -> Option<&JsDoc> {
+        self.docs.get(&span.lo)
+    }
+
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+}
+
+/// Parses JSDoc comments of declarations, class members and object
+/// properties in `module`.
+///
+/// Only block comments starting with `/**` are considered, and if a node has
+/// multiple of them the last one is used.
+pub fn collect_docs(module: &Module, comments: &SingleThreadedComments) -> DocMap {
+    let mut v = DocCollector {
+        comments,
+        map: Default::default(),
+    };
+    module.visit_with(&Invalid { span: DUMMY_SP } as _, &mut v);
+    v.map
+}
+
+struct DocCollector<'a> {
+    comments: &'a SingleThreadedComments,
+    map: DocMap,
+}
+
+impl DocCollector<'_> {
+    /// Attaches the JSDoc comment at `comment_pos` to `targets`.
+    fn attach(&mut self, comment_pos: BytePos, targets: &[Span]) {
+        let doc = self.comments.with_leading(comment_pos, |comments| {
+            comments
+                .iter()
+                .rev()
+                .find(|c| c.kind == CommentKind::Block && c.text.starts_with('*'))
+                .and_then(|c| crate::parse(c.into()).ok())
+                .map(|(_, doc)| doc)
+        });
+
+        if let Some(doc) = doc {
+            for span in targets {
+                self.map.docs.insert(span.lo, doc.clone());
+            }
+        }
+    }
+}
+
+impl Visit for DocCollector<'_> {
+    fn visit_decl(&mut self, n: &Decl, _: &dyn Node) {
+        self.attach(n.span().lo, &[n.span()]);
+        n.visit_children_with(self);
+    }
+
+    fn visit_export_decl(&mut self, n: &ExportDecl, _: &dyn Node) {
+        self.attach(n.span.lo, &[n.span, n.decl.span()]);
+        n.visit_children_with(self);
+    }
+
+    fn visit_export_default_decl(&mut self, n: &ExportDefaultDecl, _: &dyn Node) {
+        self.attach(n.span.lo, &[n.span, n.decl.span()]);
+        n.visit_children_with(self);
+    }
+
+    fn visit_export_default_expr(&mut self, n: &ExportDefaultExpr, _: &dyn Node) {
+        self.attach(n.span.lo, &[n.span, n.expr.span()]);
+        n.visit_children_with(self);
+    }
+
+    fn visit_class_member(&mut self, n: &ClassMember, _: &dyn Node) {
+        self.attach(n.span().lo, &[n.span()]);
+        n.visit_children_with(self);
+    }
+
+    fn visit_prop(&mut self, n: &Prop, _: &dyn Node) {
+        let span = match n {
+            Prop::KeyValue(p) => p.key.span(),
+            Prop::Method(p) => p.key.span(),
+            Prop::Getter(p) => p.span,
+            Prop::Setter(p) => p.span,
+            _ => n.span(),
+        };
+        self.attach(span.lo, &[span]);
+        n.visit_children_with(self);
+    }
+}
+
+/// Documentation of an exported item.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportDoc {
+    /// Exported name. `default` for default exports.
+    pub name: JsWord,
+    /// Span of the documented declaration.
+    pub span: Span,
+    pub summary: JsWord,
+    pub params: Vec<ParamDoc>,
+    pub returns: Option<ReturnDoc>,
+    /// `Some` if the item is marked as `@deprecated`. It contains the reason,
+    /// which may be empty.
+    pub deprecated: Option<JsWord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParamDoc {
+    pub name: Option<JsWord>,
+    #[serde(rename = "type")]
+    pub ty: Option<JsWord>,
+    pub description: JsWord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnDoc {
+    #[serde(rename = "type")]
+    pub ty: Option<JsWord>,
+    pub description: JsWord,
+}
+
+impl ExportDoc {
+    fn new(name: JsWord, span: Span, doc: &JsDoc) -> Self {
+        let mut summary = clean_text(&doc.description.value);
+        let mut params = vec![];
+        let mut returns = None;
+        let mut deprecated = None;
+
+        for item in &doc.tags {
+            match &item.tag {
+                Tag::Description(d) if summary.is_empty() => summary = clean_text(&d.text.value),
+                Tag::Parameter(p) => {
+                    let desc = clean_text(&p.desc.value);
+                    // The parser does not split `{type} name desc` if a type exists.
+                    let (name, description) = match &p.name {
+                        Some(name) => (Some(name.value.clone()), desc),
+                        None => {
+                            let mut parts = desc.splitn(2, char::is_whitespace);
+                            match parts.next() {
+                                Some(name) if !name.is_empty() => {
+                                    (Some(name.into()), parts.next().unwrap_or("").trim().into())
+                                }
+                                _ => (None, desc.clone()),
+                            }
+                        }
+                    };
+
+                    params.push(ParamDoc {
+                        name,
+                        ty: p.ty.as_ref().map(|v| clean_type(&v.value)),
+                        description,
+                    })
+                }
+                Tag::Return(r) => {
+                    returns = Some(ReturnDoc {
+                        ty: r.ty.as_ref().map(|v| clean_type(&v.value)),
+                        description: clean_text(&r.description.value),
+                    })
+                }
+                Tag::Deprecated(d) => deprecated = Some(clean_text(&d.text.value)),
+                _ => {}
+            }
+        }
+
+        ExportDoc {
+            name,
+            span,
+            summary,
+            params,
+            returns,
+            deprecated,
+        }
+    }
+}
+
+/// Removes leading `*` of each line and surrounding whitespaces.
+fn clean_text(s: &str) -> JsWord {
+    let lines: Vec<_> = s
+        .lines()
+        .map(|line| line.trim().trim_start_matches('*').trim())
+        .collect();
+
+    lines.join("\n").trim().into()
+}
+
+/// `{number}` => `number`
+fn clean_type(s: &str) -> JsWord {
+    let s = s.trim();
+    if s.starts_with('{') && s.ends_with('}') {
+        s[1..s.len() - 1].trim().into()
+    } else {
+        s.into()
+    }
+}
+
+/// Returns the documentation of each documented export of `module`.
+///
+/// `export { foo as bar }` uses the JSDoc of the top-level declaration of
+/// `foo`. Re-exports from other modules are ignored.
+pub fn export_docs(module: &Module, comments: &SingleThreadedComments) -> Vec<ExportDoc> {
+    let docs = collect_docs(module, comments);
+
+    // Top-level declarations, used for `export { foo }`.
+    let mut decls = HashMap::new();
+    for item in &module.body {
+        let decl = match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(e)) => &e.decl,
+            ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+            _ => continue,
+        };
+        for name in decl_names(decl) {
+            decls.insert(name, decl.span());
+        }
+    }
+
+    let mut exports = vec![];
+    let mut add = |name: JsWord, span: Span| {
+        if let Some(doc) = docs.get(span) {
+            exports.push(ExportDoc::new(name, span, doc));
+        }
+    };
+
+    for item in &module.body {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(e)) => {
+                for name in decl_names(&e.decl) {
+                    add(name, e.decl.span());
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(e)) => {
+                add(js_word!("default"), e.decl.span())
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(e)) => {
+                add(js_word!("default"), e.expr.span())
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                specifiers,
+                src: None,
+                ..
+            })) => {
+                for s in specifiers {
+                    if let ExportSpecifier::Named(s) = s {
+                        if let Some(&span) = decls.get(&s.orig.sym) {
+                            let exported = s.exported.as_ref().unwrap_or(&s.orig);
+                            add(exported.sym.clone(), span);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    exports
+}
+
+fn decl_names(decl: &Decl) -> Vec<JsWord> {
+    match decl {
+        Decl::Class(c) => vec![c.ident.sym.clone()],
+        Decl::Fn(f) => vec![f.ident.sym.clone()],
+        Decl::Var(v) => {
+            let mut names = vec![];
+            for decl in &v.decls {
+                pat_names(&decl.name, &mut names);
+            }
+            names
+        }
+        Decl::TsInterface(i) => vec![i.id.sym.clone()],
+        Decl::TsTypeAlias(a) => vec![a.id.sym.clone()],
+        Decl::TsEnum(e) => vec![e.id.sym.clone()],
+        Decl::TsModule(m) => match &m.id {
+            TsModuleName::Ident(i) => vec![i.sym.clone()],
+            TsModuleName::Str(..) => vec![],
+        },
+    }
+}
+
+fn pat_names(pat: &Pat, names: &mut Vec<JsWord>) {
+    match pat {
+        Pat::Ident(i) => names.push(i.sym.clone()),
+        Pat::Array(a) => {
+            for elem in a.elems.iter().flatten() {
+                pat_names(elem, names)
+            }
+        }
+        Pat::Object(o) => {
+            for prop in &o.props {
+                match prop {
+                    ObjectPatProp::KeyValue(p) => pat_names(&p.value, names),
+                    ObjectPatProp::Assign(p) => names.push(p.key.sym.clone()),
+                    ObjectPatProp::Rest(p) => pat_names(&p.arg, names),
+                }
+            }
+        }
+        Pat::Rest(r) => pat_names(&r.arg, names),
+        Pat::Assign(a) => pat_names(&a.left, names),
+        Pat::Invalid(..) | Pat::Expr(..) => {}
+    }
+}
@@ -8,6 +8,7 @@ use nom::{
 use swc_common::{Span, Spanned, SyntaxContext};
 
 pub mod ast;
+pub mod docs;
 mod input;
 
 pub fn parse(i: Input) -> IResult<Input, JsDoc> {
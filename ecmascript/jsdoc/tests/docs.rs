@@ -0,0 +1,110 @@
+use jsdoc::docs::{collect_docs, export_docs, ExportDoc, ParamDoc, ReturnDoc};
+use swc_common::{comments::SingleThreadedComments, FileName};
+use swc_ecma_ast::Module;
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax};
+
+fn parse(src: &str) -> (Module, SingleThreadedComments) {
+    testing::run_test(false, |cm, _| {
+        let comments = SingleThreadedComments::default();
+        let fm = cm.new_source_file(FileName::Anon, src.into());
+        let lexer = Lexer::new(
+            Syntax::default(),
+            Default::default(),
+            StringInput::from(&*fm),
+            Some(&comments),
+        );
+        let module = Parser::new_from(lexer).parse_module().unwrap();
+
+        Ok((module, comments))
+    })
+    .unwrap()
+}
+
+#[test]
+fn export_model() {
+    let (module, comments) = parse(
+        "
+/**
+ * Adds two numbers.
+ * @param {number} a The first number
+ * @param {number} b
+ * @returns {number} The sum
+ */
+export function add(a, b) {
+    return a + b;
+}
+
+/**
+ * @deprecated Use add instead
+ */
+function plus(a, b) {}
+
+// not a jsdoc
+export const undocumented = 1;
+
+export { plus as sum };
+",
+    );
+
+    let docs = export_docs(&module, &comments);
+    let docs: Vec<_> = docs
+        .into_iter()
+        .map(|doc| ExportDoc {
+            span: Default::default(),
+            ..doc
+        })
+        .collect();
+
+    assert_eq!(
+        docs,
+        vec![
+            ExportDoc {
+                name: "add".into(),
+                span: Default::default(),
+                summary: "Adds two numbers.".into(),
+                params: vec![
+                    ParamDoc {
+                        name: Some("a".into()),
+                        ty: Some("number".into()),
+                        description: "The first number".into(),
+                    },
+                    ParamDoc {
+                        name: Some("b".into()),
+                        ty: Some("number".into()),
+                        description: "".into(),
+                    },
+                ],
+                returns: Some(ReturnDoc {
+                    ty: Some("number".into()),
+                    description: "The sum".into(),
+                }),
+                deprecated: None,
+            },
+            ExportDoc {
+                name: "sum".into(),
+                span: Default::default(),
+                summary: "".into(),
+                params: vec![],
+                returns: None,
+                deprecated: Some("Use add instead".into()),
+            },
+        ]
+    );
+}
+
+#[test]
+fn class_members() {
+    let (module, comments) = parse(
+        "
+class Foo {
+    /** Bar */
+    bar() {}
+
+    baz() {}
+}
+",
+    );
+
+    let docs = collect_docs(&module, &comments);
+    assert_eq!(docs.len(), 1);
+}
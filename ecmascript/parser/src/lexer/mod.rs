@@ -225,7 +225,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                 Some('?') => {
                     self.input.bump();
                     self.input.bump();
-                    if self.syntax.typescript() && self.input.cur() == Some('=') {
+                    if self.syntax.logical_assignment() && self.input.cur() == Some('=') {
                         self.input.bump();
                         return Ok(Some(tok!("??=")));
                     }
@@ -336,7 +336,7 @@ impl<'a, I: Input> Lexer<'a, I> {
                 if self.input.cur() == Some(c) {
                     self.input.bump();
 
-                    if self.syntax.typescript() && self.input.cur() == Some('=') {
+                    if self.syntax.logical_assignment() && self.input.cur() == Some('=') {
                         self.input.bump();
                         return Ok(Some(AssignOp(match token {
                             BitAnd => AndAssign,
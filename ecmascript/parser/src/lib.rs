@@ -242,6 +242,11 @@ impl Syntax {
         true
     }
 
+    /// `a ||= b`, `a &&= b` and `a ??= b`
+    pub fn logical_assignment(self) -> bool {
+        true
+    }
+
     pub fn import_meta(self) -> bool {
         match self {
             Syntax::Es(EsConfig {
@@ -322,6 +327,10 @@ pub enum JscTarget {
     Es2019,
     #[serde(rename = "es2020")]
     Es2020,
+    #[serde(rename = "es2021")]
+    Es2021,
+    #[serde(rename = "es2022")]
+    Es2022,
 }
 
 impl Default for JscTarget {
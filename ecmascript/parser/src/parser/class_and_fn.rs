@@ -405,6 +405,12 @@ impl<'a, I: Tokens> Parser<I> {
                     declare,
                     false,
                 );
+            } else if is!('{') {
+                if !decorators.is_empty() {
+                    syntax_error!(decorators[0].span, SyntaxError::InvalidLeadingDecorator)
+                }
+
+                return self.parse_static_block(start);
             } else {
                 // TODO: error if static contains escape
             }
@@ -419,6 +425,32 @@ impl<'a, I: Tokens> Parser<I> {
         )
     }
 
+    /// `static { ... }`
+    fn parse_static_block(&mut self, start: BytePos) -> PResult<ClassMember> {
+        let ctx = Context {
+            in_async: false,
+            in_generator: false,
+            in_function: false,
+            is_break_allowed: false,
+            is_continue_allowed: false,
+            ..self.ctx()
+        };
+        let state = State {
+            labels: vec![],
+            ..Default::default()
+        };
+        let body = self
+            .with_ctx(ctx)
+            .with_state(state)
+            .include_in_expr(true)
+            .parse_block(false)?;
+
+        Ok(ClassMember::StaticBlock(StaticBlock {
+            span: span!(start),
+            body,
+        }))
+    }
+
     #[allow(clippy::cognitive_complexity)]
     fn parse_class_member_with_is_static(
         &mut self,
@@ -1288,4 +1320,114 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn static_block() {
+        assert_eq_ignore_span!(
+            expr("(class { static { this.a = 1; } static() {} })"),
+            Box::new(Expr::Paren(ParenExpr {
+                span,
+                expr: Box::new(Expr::Class(ClassExpr {
+                    ident: None,
+                    class: Class {
+                        decorators: vec![],
+                        span,
+                        body: vec![
+                            ClassMember::StaticBlock(StaticBlock {
+                                span,
+                                body: BlockStmt {
+                                    span,
+                                    stmts: vec![Stmt::Expr(ExprStmt {
+                                        span,
+                                        expr: expr("this.a = 1"),
+                                    })],
+                                },
+                            }),
+                            ClassMember::Method(ClassMethod {
+                                span,
+                                key: PropName::Ident(Ident::new("static".into(), span)),
+                                function: Function {
+                                    params: vec![],
+                                    decorators: vec![],
+                                    span,
+                                    body: Some(BlockStmt {
+                                        span,
+                                        stmts: vec![],
+                                    }),
+                                    is_generator: false,
+                                    is_async: false,
+                                    type_params: None,
+                                    return_type: None,
+                                },
+                                kind: MethodKind::Method,
+                                is_static: false,
+                                accessibility: None,
+                                is_abstract: false,
+                                is_optional: false,
+                            }),
+                        ],
+                        super_class: None,
+                        implements: vec![],
+                        is_abstract: false,
+                        super_type_params: None,
+                        type_params: None,
+                    },
+                })),
+            }))
+        );
+    }
+
+    #[test]
+    fn private_in_object() {
+        assert_eq_ignore_span!(
+            test_parser("#x in obj && a", Syntax::default(), |p| {
+                p.include_in_expr(true).parse_expr()
+            }),
+            Box::new(Expr::Bin(BinExpr {
+                span,
+                op: op!("&&"),
+                left: Box::new(Expr::Bin(BinExpr {
+                    span,
+                    op: op!("in"),
+                    left: Box::new(Expr::PrivateName(PrivateName {
+                        span,
+                        id: Ident::new("x".into(), span),
+                    })),
+                    right: expr("obj"),
+                })),
+                right: expr("a"),
+            }))
+        );
+    }
+
+    #[test]
+    fn private_in_logical_operand() {
+        assert_eq_ignore_span!(
+            test_parser("a || #x in obj", Syntax::default(), |p| {
+                p.include_in_expr(true).parse_expr()
+            }),
+            Box::new(Expr::Bin(BinExpr {
+                span,
+                op: op!("||"),
+                left: expr("a"),
+                right: Box::new(Expr::Bin(BinExpr {
+                    span,
+                    op: op!("in"),
+                    left: Box::new(Expr::PrivateName(PrivateName {
+                        span,
+                        id: Ident::new("x".into(), span),
+                    })),
+                    right: expr("obj"),
+                })),
+            }))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected an expression")]
+    fn private_in_arithmetic_operand() {
+        test_parser("a + #x in obj", Syntax::default(), |p| {
+            p.include_in_expr(true).parse_expr()
+        });
+    }
 }
@@ -10,7 +10,7 @@ impl<'a, I: Tokens> Parser<I> {
     pub(super) fn parse_bin_expr(&mut self) -> PResult<Box<Expr>> {
         let ctx = self.ctx();
 
-        let left = match self.parse_unary_expr() {
+        let left = match self.parse_bin_operand(0) {
             Ok(v) => v,
             Err(err) => match cur!(true)? {
                 &Word(Word::Keyword(Keyword::In)) if ctx.include_in_expr => {
@@ -31,6 +31,27 @@ impl<'a, I: Tokens> Parser<I> {
         self.parse_bin_op_recursively(left, 0)
     }
 
+    /// Parses an unary expression, or `#x` of `#x in obj`.
+    ///
+    /// `#x` is only allowed at the start of a relational expression, so it's
+    /// rejected if `min_prec` is for an operator binding tighter than `in`.
+    fn parse_bin_operand(&mut self, min_prec: u8) -> PResult<Box<Expr>> {
+        if is!('#') {
+            if min_prec >= op!("in").precedence() {
+                unexpected!("an expression")
+            }
+
+            let name = self.parse_private_name()?;
+            if !is!("in") || !self.ctx().include_in_expr {
+                unexpected!("in")
+            }
+
+            return Ok(Box::new(Expr::PrivateName(name)));
+        }
+
+        self.parse_unary_expr()
+    }
+
     /// Parse binary operators with the operator precedence parsing
     /// algorithm. `left` is the left-hand side of the operator.
     /// `minPrec` provides context that allows the function to stop and
@@ -167,16 +188,14 @@ impl<'a, I: Tokens> Parser<I> {
         }
 
         let right = {
-            let left_of_right = self.parse_unary_expr()?;
-            self.parse_bin_op_recursively(
-                left_of_right,
-                if op == op!("**") {
-                    // exponential operator is right associative
-                    op.precedence() - 1
-                } else {
-                    op.precedence()
-                },
-            )?
+            let prec = if op == op!("**") {
+                // exponential operator is right associative
+                op.precedence() - 1
+            } else {
+                op.precedence()
+            };
+            let left_of_right = self.parse_bin_operand(prec)?;
+            self.parse_bin_op_recursively(left_of_right, prec)?
         };
         /* this check is for all ?? operators
          * a ?? b && c for this example
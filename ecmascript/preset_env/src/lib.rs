@@ -14,7 +14,7 @@ use swc_atoms::{js_word, JsWord};
 use swc_common::{chain, FromVariant, Mark, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_transforms::{
-    compat::{es2015, es2016, es2017, es2018, es2020, es3},
+    compat::{es2015, es2016, es2017, es2018, es2020, es2021, es2022, es3, Assumptions},
     pass::{noop, Optional},
     util::prepend_stmts,
 };
@@ -29,7 +29,11 @@ mod transform_data;
 mod version;
 
 pub fn preset_env(global_mark: Mark, c: Config) -> impl Fold {
-    let loose = c.loose;
+    let assumptions = if c.loose {
+        Assumptions::all()
+    } else {
+        c.assumptions
+    };
     let targets: Versions = c.targets.try_into().expect("failed to parse targets");
     let is_any_target = targets.is_any_target();
 
@@ -64,15 +68,84 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Fold {
         }};
     }
 
+    // ES2022
+    let pass = {
+        // Static blocks and private brand checks are lowered by one pass.
+        let enable = should_enable!(ClassStaticBlock, false)
+            || should_enable!(PrivatePropertyInObject, false);
+        if c.debug {
+            println!(
+                "{}: {:?}",
+                transform_data::Feature::ClassStaticBlock.as_str(),
+                enable
+            );
+        }
+        chain!(
+            pass,
+            Optional::new(
+                es2022::es2022_with_config(es2022::Config {
+                    class_properties: es2020::class_properties::Config {
+                        set_public_class_fields: assumptions.set_public_class_fields,
+                    },
+                }),
+                enable
+            )
+        )
+    };
+
+    // ES2021
+    let pass = add!(
+        pass,
+        LogicalAssignmentOperators,
+        es2021::logical_assignments()
+    );
+
     // ES2020
 
-    let pass = add!(pass, NullishCoalescing, es2020::nullish_coalescing());
-    let pass = add!(pass, OptionalChaining, es2020::optional_chaining());
-    let pass = add!(pass, ClassProperties, es2020::class_properties());
+    let pass = add!(
+        pass,
+        NullishCoalescing,
+        es2020::nullish_coalescing_with_config(es2020::nullish_coalescing::Config {
+            no_document_all: assumptions.no_document_all
+        })
+    );
+    let pass = add!(
+        pass,
+        OptionalChaining,
+        es2020::optional_chaining_with_config(es2020::opt_chaining::Config {
+            no_document_all: assumptions.no_document_all
+        })
+    );
+    let pass = add!(
+        pass,
+        ClassProperties,
+        es2020::class_properties_with_config(es2020::class_properties::Config {
+            set_public_class_fields: assumptions.set_public_class_fields
+        })
+    );
 
     // ES2018
-    let pass = add!(pass, ObjectRestSpread, es2018::object_rest_spread());
+    let pass = add!(
+        pass,
+        AsyncGeneratorFunctions,
+        es2018::async_generator_functions()
+    );
+    let pass = add!(
+        pass,
+        ObjectRestSpread,
+        es2018::object_rest_spread_with_config(es2018::object_rest_spread::Config {
+            object_rest_no_symbols: assumptions.object_rest_no_symbols,
+            set_spread_properties: assumptions.set_spread_properties,
+        })
+    );
     let pass = add!(pass, OptionalCatchBinding, es2018::optional_catch_binding());
+    let pass = add!(pass, DotAllRegex, es2018::dotall_regex());
+    let pass = add!(pass, UnicodePropertyRegex, es2018::unicode_property_regex());
+    let pass = add!(
+        pass,
+        NamedCapturingGroupsRegex,
+        es2018::named_capturing_groups_regex()
+    );
 
     // ES2017
     let pass = add!(pass, AsyncToGenerator, es2017::async_to_generator());
@@ -82,19 +155,41 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Fold {
 
     // ES2015
     let pass = add!(pass, BlockScopedFunctions, es2015::block_scoped_functions());
-    let pass = add!(pass, TemplateLiterals, es2015::template_literal(), true);
-    let pass = add!(pass, Classes, es2015::classes());
+    let pass = add!(
+        pass,
+        TemplateLiterals,
+        es2015::template_literal_with_config(es2015::template_literal::Config {
+            ignore_to_primitive_hint: assumptions.ignore_to_primitive_hint,
+            mutable_template_object: assumptions.mutable_template_object,
+        }),
+        true
+    );
+    let pass = add!(pass, NewTarget, es2015::new_target());
+    let pass = add!(
+        pass,
+        Classes,
+        es2015::classes_with_config(es2015::classes::Config {
+            no_class_calls: assumptions.no_class_calls,
+            constant_super: assumptions.constant_super,
+            set_class_methods: assumptions.set_class_methods,
+        })
+    );
+    let pass = add!(pass, ObjectSuper, es2015::object_super());
     let pass = add!(
         pass,
         Spread,
-        es2015::spread(es2015::spread::Config { loose }),
+        es2015::spread(es2015::spread::Config {
+            loose: assumptions.iterable_is_array
+        }),
         true
     );
     let pass = add!(pass, FunctionName, es2015::function_name());
     let pass = add!(pass, ArrowFunctions, es2015::arrow());
     let pass = add!(pass, DuplicateKeys, es2015::duplicate_keys());
+    let pass = add!(pass, Literals, es2015::literals());
+    let pass = add!(pass, UnicodeRegex, es2015::unicode_regex());
     let pass = add!(pass, StickyRegex, es2015::sticky_regex());
-    // TODO:    InstanceOf,
+    let pass = add!(pass, InstanceOf, es2015::instance_of());
     let pass = add!(pass, TypeOfSymbol, es2015::typeof_symbol());
     let pass = add!(pass, ShorthandProperties, es2015::shorthand());
     let pass = add!(pass, Parameters, es2015::parameters());
@@ -102,35 +197,31 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Fold {
         pass,
         ForOf,
         es2015::for_of(es2015::for_of::Config {
-            assume_array: loose
+            assume_array: assumptions.iterable_is_array
         }),
         true
     );
     let pass = add!(
         pass,
         ComputedProperties,
-        es2015::computed_properties(),
+        es2015::computed_properties_with_config(es2015::computed_props::Config {
+            set_computed_properties: assumptions.set_computed_properties
+        }),
         true
     );
     let pass = add!(
         pass,
         Destructuring,
-        es2015::destructuring(es2015::destructuring::Config { loose }),
+        es2015::destructuring(es2015::destructuring::Config {
+            loose: assumptions.iterable_is_array
+        }),
         true
     );
     let pass = add!(pass, Regenerator, es2015::regenerator(global_mark), true);
     let pass = add!(pass, BlockScoping, es2015::block_scoping(), true);
 
     // TODO:
-    //    Literals,
-    //    ObjectSuper,
-    //    DotAllRegex,
-    //    UnicodeRegex,
-    //    NewTarget,
-    //    AsyncGeneratorFunctions,
-    //    UnicodePropertyRegex,
     //    JsonStrings,
-    //    NamedCapturingGroupsRegex,
 
     // ES 3
     let pass = add!(pass, PropertyLiterals, es3::property_literals());
@@ -164,7 +255,7 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Fold {
 }
 
 /// A map without allocation.
-#[derive(Debug, Default, Deserialize, Clone, Copy, StaticMap)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, StaticMap)]
 #[serde(deny_unknown_fields)]
 pub struct BrowserData<T: Default> {
     #[serde(default)]
@@ -330,7 +421,7 @@ impl Fold for Polyfills {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Mode {
     #[serde(rename = "usage")]
     Usage,
@@ -390,7 +481,7 @@ impl BrowserData<Option<Version>> {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Config {
     #[serde(default)]
@@ -405,6 +496,10 @@ pub struct Config {
     #[serde(default)]
     pub loose: bool,
 
+    /// Ignored if `loose` is true, as it implies all assumptions.
+    #[serde(default)]
+    pub assumptions: Assumptions,
+
     /// Skipped es features.
     ///
     /// e.g.)
@@ -436,7 +531,7 @@ fn default_targets() -> Option<Targets> {
     Some(Targets::Query(Query::Single("".into())))
 }
 
-#[derive(Debug, Clone, Deserialize, FromVariant)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromVariant)]
 #[serde(untagged)]
 pub enum FeatureOrModule {
     Feature(Feature),
@@ -461,7 +556,7 @@ impl FeatureOrModule {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, FromVariant)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromVariant)]
 #[serde(untagged)]
 pub enum Targets {
     Query(Query),
@@ -470,19 +565,21 @@ pub enum Targets {
     HashMap(FxHashMap<String, QueryOrVersion>),
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct EsModules {
     esmodules: bool,
 }
 
-#[derive(Debug, Clone, Deserialize, FromVariant)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromVariant)]
 #[serde(untagged)]
 pub enum QueryOrVersion {
     Query(Query),
     Version(Version),
 }
 
-#[derive(Debug, Clone, Deserialize, FromVariant, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[derive(
+    Debug, Clone, Serialize, Deserialize, FromVariant, Eq, PartialEq, PartialOrd, Ord, Hash,
+)]
 #[serde(untagged)]
 pub enum Query {
     Single(String),
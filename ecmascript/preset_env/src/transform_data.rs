@@ -70,6 +70,9 @@ pub enum Feature {
     /// `transform-typeof-symbol`
     TypeOfSymbol,
 
+    /// `transform-instanceof`
+    InstanceOf,
+
     /// `transform-new-target`
     NewTarget,
 
@@ -109,6 +112,15 @@ pub enum Feature {
     /// `transform-reserved-words`
     ReservedWords,
 
+    /// `proposal-class-static-block`
+    ClassStaticBlock,
+
+    /// `proposal-private-property-in-object`
+    PrivatePropertyInObject,
+
+    /// `proposal-logical-assignment-operators`
+    LogicalAssignmentOperators,
+
     /// `proposal-nullish-coalescing-operator`
     NullishCoalescing,
 
@@ -169,4 +181,40 @@ mod tests {
             true
         ));
     }
+
+    #[test]
+    fn instance_of() {
+        assert!(Feature::InstanceOf.should_enable(
+            BrowserData {
+                chrome: Some("49.0.0".parse().unwrap()),
+                ..Default::default()
+            },
+            false
+        ));
+        assert!(!Feature::InstanceOf.should_enable(
+            BrowserData {
+                chrome: Some("50.0.0".parse().unwrap()),
+                ..Default::default()
+            },
+            false
+        ));
+    }
+
+    #[test]
+    fn class_static_block() {
+        assert!(Feature::ClassStaticBlock.should_enable(
+            BrowserData {
+                chrome: Some("93.0.0".parse().unwrap()),
+                ..Default::default()
+            },
+            false
+        ));
+        assert!(!Feature::ClassStaticBlock.should_enable(
+            BrowserData {
+                chrome: Some("94.0.0".parse().unwrap()),
+                ..Default::default()
+            },
+            false
+        ));
+    }
 }
@@ -1,5 +1,5 @@
 use crate::Versions;
-use serde::{de, de::Visitor, Deserialize, Deserializer};
+use serde::{de, de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
 use std::{cmp, cmp::Ordering, fmt, hash, str::FromStr};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -154,6 +154,19 @@ impl<'de> Deserialize<'de> for Version {
     }
 }
 
+/// Serialized as a string which can be deserialized.
+impl Serialize for Version {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&format_args!(
+            "{}.{}.{}",
+            self.major, self.minor, self.patch
+        ))
+    }
+}
+
 pub fn should_enable(target: Versions, feature: Versions, default: bool) -> bool {
     if target
         .iter()
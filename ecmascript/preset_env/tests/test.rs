@@ -186,6 +186,7 @@ fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
                     skip: vec![],
                     // TODO
                     loose: true,
+                    assumptions: Default::default(),
                     // TODO
                     dynamic_import: true,
                     include: c.include,
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+/// Assumptions about the input code, which allow compat passes to emit
+/// smaller and faster output.
+///
+/// See https://babeljs.io/docs/en/assumptions
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Assumptions {
+    /// Classes are never called without `new`, so `_classCallCheck` is not
+    /// required.
+    #[serde(default)]
+    pub no_class_calls: bool,
+
+    /// The prototype of a class is never changed, so `super.foo` can be
+    /// resolved without `_get`.
+    #[serde(default)]
+    pub constant_super: bool,
+
+    /// Class methods can be assigned to the prototype instead of being
+    /// defined with `_createClass`.
+    #[serde(default)]
+    pub set_class_methods: bool,
+
+    /// Public class fields can be assigned instead of being defined with
+    /// `_defineProperty`.
+    #[serde(default)]
+    pub set_public_class_fields: bool,
+
+    /// Computed properties of object literals can be assigned instead of
+    /// being defined with `_defineProperty`.
+    #[serde(default)]
+    pub set_computed_properties: bool,
+
+    /// Template literals can be concatenated with `+`, which ignores the
+    /// `toPrimitive` hint of interpolated objects.
+    #[serde(default)]
+    pub ignore_to_primitive_hint: bool,
+
+    /// Template objects of tagged templates are never mutated, so they don't
+    /// have to be frozen.
+    #[serde(default)]
+    pub mutable_template_object: bool,
+
+    /// Object spread can assign properties with `_extends` instead of
+    /// defining them.
+    #[serde(default)]
+    pub set_spread_properties: bool,
+
+    /// Objects never have symbol keys, so object rest ignores them.
+    #[serde(default)]
+    pub object_rest_no_symbols: bool,
+
+    /// `document.all` is never used, so `a == null` can be used to check for
+    /// `null` and `undefined`.
+    #[serde(default)]
+    pub no_document_all: bool,
+
+    /// Iterables used by spread, destructuring and `for of` are always arrays.
+    #[serde(default)]
+    pub iterable_is_array: bool,
+}
+
+impl Assumptions {
+    /// Assumptions made by the `loose` mode.
+    pub fn all() -> Self {
+        Assumptions {
+            no_class_calls: true,
+            constant_super: true,
+            set_class_methods: true,
+            set_public_class_fields: true,
+            set_computed_properties: true,
+            ignore_to_primitive_hint: true,
+            mutable_template_object: true,
+            set_spread_properties: true,
+            object_rest_no_symbols: true,
+            no_document_all: true,
+            iterable_is_array: true,
+        }
+    }
+}
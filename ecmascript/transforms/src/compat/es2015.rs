@@ -1,10 +1,25 @@
 pub use self::{
-    arrow::arrow, block_scoped_fn::block_scoped_functions, block_scoping::block_scoping,
-    classes::classes, computed_props::computed_properties, destructuring::destructuring,
-    duplicate_keys::duplicate_keys, for_of::for_of, function_name::function_name,
-    instanceof::instance_of, parameters::parameters, regenerator::regenerator,
-    shorthand_property::shorthand, spread::spread, sticky_regex::sticky_regex,
-    template_literal::template_literal, typeof_symbol::typeof_symbol,
+    arrow::arrow,
+    block_scoped_fn::block_scoped_functions,
+    block_scoping::block_scoping,
+    classes::{classes, classes_with_config},
+    computed_props::{computed_properties, computed_properties_with_config},
+    destructuring::destructuring,
+    duplicate_keys::duplicate_keys,
+    for_of::for_of,
+    function_name::function_name,
+    instanceof::instance_of,
+    literals::literals,
+    new_target::new_target,
+    object_super::object_super,
+    parameters::parameters,
+    regenerator::regenerator,
+    shorthand_property::shorthand,
+    spread::spread,
+    sticky_regex::sticky_regex,
+    template_literal::{template_literal, template_literal_with_config},
+    typeof_symbol::typeof_symbol,
+    unicode_regex::unicode_regex,
 };
 use serde::Deserialize;
 use swc_common::{chain, Mark};
@@ -14,24 +29,31 @@ mod arrow;
 mod block_scoped_fn;
 mod block_scoping;
 pub mod classes;
-mod computed_props;
+pub mod computed_props;
 pub mod destructuring;
 mod duplicate_keys;
 pub mod for_of;
 mod function_name;
 mod instanceof;
+mod literals;
+mod new_target;
+mod object_super;
 mod parameters;
 mod regenerator;
 mod shorthand_property;
 pub mod spread;
 mod sticky_regex;
-mod template_literal;
+pub mod template_literal;
 mod typeof_symbol;
+mod unicode_regex;
 
 fn exprs() -> impl Fold {
     chain!(
         arrow(),
         duplicate_keys(),
+        literals(),
+        // Should come before sticky_regex, which creates `RegExp` objects.
+        unicode_regex(),
         sticky_regex(),
         instance_of(),
         typeof_symbol(),
@@ -43,8 +65,12 @@ fn exprs() -> impl Fold {
 pub fn es2015(global_mark: Mark, c: Config) -> impl Fold {
     chain!(
         block_scoped_functions(),
-        template_literal(),
-        classes(),
+        template_literal_with_config(c.template_literal),
+        // Should come before classes and arrow
+        new_target(),
+        classes_with_config(c.classes),
+        // Should come before shorthand, which converts methods to functions.
+        object_super(),
         spread(c.spread),
         function_name(),
         exprs(),
@@ -53,7 +79,7 @@ pub fn es2015(global_mark: Mark, c: Config) -> impl Fold {
         // See: https://github.com/swc-project/swc/issues/1036
         regenerator(global_mark),
         parameters(),
-        computed_properties(),
+        computed_properties_with_config(c.computed_props),
         destructuring(c.destructuring),
         block_scoping(),
     )
@@ -61,6 +87,15 @@ pub fn es2015(global_mark: Mark, c: Config) -> impl Fold {
 
 #[derive(Debug, Default, Deserialize)]
 pub struct Config {
+    #[serde(flatten)]
+    pub classes: classes::Config,
+
+    #[serde(flatten)]
+    pub template_literal: template_literal::Config,
+
+    #[serde(flatten)]
+    pub computed_props: computed_props::Config,
+
     #[serde(flatten)]
     pub for_of: for_of::Config,
 
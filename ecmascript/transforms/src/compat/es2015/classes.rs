@@ -9,9 +9,10 @@ use self::{
 };
 use crate::util::{
     alias_if_required, default_constructor, prepend, prop_name_to_expr, ExprFactory, IsDirective,
-    ModuleItemLike, StmtLike,
+    ModuleItemLike, StmtLike, HANDLER,
 };
 use fxhash::FxBuildHasher;
+use serde::Deserialize;
 use std::iter;
 use swc_common::{Mark, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
@@ -25,7 +26,30 @@ mod prop_name;
 mod super_field;
 
 pub fn classes() -> impl Fold {
-    Classes::default()
+    classes_with_config(Default::default())
+}
+
+pub fn classes_with_config(c: Config) -> impl Fold {
+    Classes {
+        c,
+        ..Default::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Don't inject `_classCallCheck`.
+    #[serde(default)]
+    pub no_class_calls: bool,
+
+    /// Resolve `super.foo` from the super class instead of using `_get`.
+    #[serde(default)]
+    pub constant_super: bool,
+
+    /// Assign methods to the prototype instead of using `_createClass`.
+    #[serde(default)]
+    pub set_class_methods: bool,
 }
 
 type IndexMap<K, V> = indexmap::IndexMap<K, V, FxBuildHasher>;
@@ -63,11 +87,15 @@ type IndexMap<K, V> = indexmap::IndexMap<K, V, FxBuildHasher>;
 /// ```
 #[derive(Default, Clone, Copy)]
 struct Classes {
+    c: Config,
     in_strict: bool,
 }
 
 struct Data {
     key_prop: Box<Prop>,
+    /// Used as the property of `Foo.prototype.foo = function () {}`.
+    key_expr: Box<Expr>,
+    computed: bool,
     method: Option<Box<Expr>>,
     set: Option<Box<Expr>>,
     get: Option<Box<Expr>>,
@@ -381,7 +409,11 @@ impl Classes {
             match member {
                 ClassMember::Constructor(c) => {
                     if constructor.is_some() {
-                        unimplemented!("multiple constructor")
+                        HANDLER.with(|handler| {
+                            handler
+                                .struct_span_err(c.span, "A class may only have one constructor")
+                                .emit()
+                        });
                     } else {
                         constructor = Some(c)
                     }
@@ -395,6 +427,14 @@ impl Classes {
                 ClassMember::PrivateProp(..) => unreachable!(
                     "classes pass: private property\nclass_properties pass should remove this"
                 ),
+                ClassMember::StaticBlock(b) => HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(
+                            b.span,
+                            "static blocks should be removed by the class_properties pass",
+                        )
+                        .emit()
+                }),
                 ClassMember::TsIndexSignature(..) => {
                     // We just strip this.
                 }
@@ -540,6 +580,7 @@ impl Classes {
             // Handle `super.XX`
             body = self.handle_super_access(
                 &class_name,
+                super_class_ident.as_ref(),
                 body,
                 if is_this_declared {
                     Some(this_mark)
@@ -549,7 +590,9 @@ impl Classes {
             );
 
             // inject _classCallCheck(this, Bar);
-            inject_class_call_check(&mut body, class_name.clone());
+            if !self.c.no_class_calls {
+                inject_class_call_check(&mut body, class_name.clone());
+            }
 
             stmts.push(Stmt::Decl(Decl::Fn(FnDecl {
                 ident: class_name.clone(),
@@ -566,7 +609,11 @@ impl Classes {
 
         // convert class methods
         // stmts.extend(self.fold_class_methods(class_name.clone(), priv_methods));
-        stmts.extend(self.fold_class_methods(class_name.clone(), methods));
+        stmts.extend(self.fold_class_methods(
+            class_name.clone(),
+            super_class_ident.as_ref(),
+            methods,
+        ));
 
         if stmts.first().map(|v| !v.is_use_strict()).unwrap_or(false) && !self.in_strict {
             prepend(
@@ -615,6 +662,7 @@ impl Classes {
     fn handle_super_access(
         &mut self,
         class_name: &Ident,
+        super_class_ident: Option<&Ident>,
         body: Vec<Stmt>,
         this_mark: Option<Mark>,
     ) -> Vec<Stmt> {
@@ -629,6 +677,7 @@ impl Classes {
             in_nested_scope: false,
             in_injected_define_property_call: false,
             this_alias_mark: None,
+            constant_super_class: super_class_ident.filter(|_| self.c.constant_super),
         };
 
         let mut body = body.fold_with(&mut folder);
@@ -665,7 +714,12 @@ impl Classes {
         body
     }
 
-    fn fold_class_methods(&mut self, class_name: Ident, methods: Vec<ClassMethod>) -> Vec<Stmt> {
+    fn fold_class_methods(
+        &mut self,
+        class_name: Ident,
+        super_class_ident: Option<&Ident>,
+        methods: Vec<ClassMethod>,
+    ) -> Vec<Stmt> {
         if methods.is_empty() {
             return vec![];
         }
@@ -744,6 +798,57 @@ impl Classes {
             .into_stmt()
         }
 
+        /// Foo.prototype.foo = function foo() {};
+        ///
+        /// Getters and setters are left as is, as they should be defined.
+        fn assign_methods(
+            stmts: &mut Vec<Stmt>,
+            class_name: &Ident,
+            props: IndexMap<HashKey, Data>,
+            is_static: bool,
+        ) -> IndexMap<HashKey, Data> {
+            let mut rest = IndexMap::default();
+
+            for (key, data) in props {
+                match data {
+                    Data {
+                        key_expr,
+                        computed,
+                        method: Some(method),
+                        get: None,
+                        set: None,
+                        ..
+                    } => {
+                        let obj = if is_static {
+                            Expr::Ident(class_name.clone())
+                        } else {
+                            class_name.clone().make_member(quote_ident!("prototype"))
+                        };
+
+                        stmts.push(
+                            AssignExpr {
+                                span: DUMMY_SP,
+                                left: PatOrExpr::Expr(Box::new(Expr::Member(MemberExpr {
+                                    span: DUMMY_SP,
+                                    obj: obj.as_obj(),
+                                    prop: key_expr,
+                                    computed,
+                                }))),
+                                op: op!("="),
+                                right: method,
+                            }
+                            .into_stmt(),
+                        );
+                    }
+                    _ => {
+                        rest.insert(key, data);
+                    }
+                }
+            }
+
+            rest
+        }
+
         let (mut props, mut static_props) = (IndexMap::default(), IndexMap::default());
 
         for m in methods {
@@ -754,6 +859,8 @@ impl Classes {
                 _ => false,
             };
             let prop_name = prop_name_to_expr(m.key);
+            let key_expr = Box::new(prop_name.clone());
+            let key_computed = computed || !matches!(prop_name, Expr::Ident(..));
 
             let append_to: &mut IndexMap<_, _> = if m.is_static {
                 &mut static_props
@@ -771,6 +878,7 @@ impl Classes {
                 in_nested_scope: false,
                 in_injected_define_property_call: false,
                 this_alias_mark: None,
+                constant_super_class: super_class_ident.filter(|_| self.c.constant_super),
             };
             let mut function = m.function.fold_with(&mut folder);
 
@@ -820,6 +928,8 @@ impl Classes {
 
             let data = append_to.entry(key).or_insert_with(|| Data {
                 key_prop,
+                key_expr,
+                computed: key_computed,
                 get: None,
                 set: None,
                 method: None,
@@ -831,10 +941,16 @@ impl Classes {
             }
         }
 
+        let mut stmts = vec![];
+        if self.c.set_class_methods {
+            props = assign_methods(&mut stmts, &class_name, props, false);
+            static_props = assign_methods(&mut stmts, &class_name, static_props, true);
+        }
+
         if props.is_empty() && static_props.is_empty() {
-            return vec![];
+            return stmts;
         }
-        vec![mk_create_class_call(
+        stmts.push(mk_create_class_call(
             class_name,
             mk_arg_obj_for_create_class(props),
             if static_props.is_empty() {
@@ -842,7 +958,8 @@ impl Classes {
             } else {
                 Some(mk_arg_obj_for_create_class(static_props))
             },
-        )]
+        ));
+        stmts
     }
 }
 
@@ -38,6 +38,10 @@ pub(crate) struct SuperFieldAccessFolder<'a> {
 
     /// `Some(mark)` if `var this2 = this`is required.
     pub this_alias_mark: Option<Mark>,
+
+    /// `Some(_super)` if the prototype of the class is assumed to be constant,
+    /// so `super.foo` can be resolved from the super class directly.
+    pub constant_super_class: Option<&'a Ident>,
 }
 
 struct SuperCalleeFolder<'a> {
@@ -55,6 +59,8 @@ struct SuperCalleeFolder<'a> {
 
     /// `Some(mark)` if `var this2 = this`is required.
     this_alias_mark: Option<Mark>,
+
+    constant_super_class: Option<&'a Ident>,
 }
 
 macro_rules! mark_nested {
@@ -107,6 +113,7 @@ impl<'a> Fold for SuperCalleeFolder<'a> {
                             span: super_token, ..
                         }),
                     prop,
+                    computed,
                     ..
                 }) => {
                     let op = match op {
@@ -118,6 +125,7 @@ impl<'a> Fold for SuperCalleeFolder<'a> {
                         super_token,
                         true,
                         prop,
+                        computed,
                         op,
                         Box::new(Expr::Lit(Lit::Num(Number {
                             span: DUMMY_SP,
@@ -149,9 +157,17 @@ impl<'a> Fold for SuperCalleeFolder<'a> {
                                     span: super_token, ..
                                 }),
                             prop,
+                            computed,
                             ..
                         }) => {
-                            return self.super_to_set_call(super_token, false, prop, op, right);
+                            return self.super_to_set_call(
+                                super_token,
+                                false,
+                                prop,
+                                computed,
+                                op,
+                                right,
+                            );
                         }
                         _ => {
                             left = PatOrExpr::Expr(expr);
@@ -167,9 +183,17 @@ impl<'a> Fold for SuperCalleeFolder<'a> {
                                         span: super_token, ..
                                     }),
                                 prop,
+                                computed,
                                 ..
                             }) => {
-                                return self.super_to_set_call(super_token, false, prop, op, right);
+                                return self.super_to_set_call(
+                                    super_token,
+                                    false,
+                                    prop,
+                                    computed,
+                                    op,
+                                    right,
+                                );
                             }
                             _ => {
                                 left = PatOrExpr::Pat(Box::new(Pat::Expr(expr)));
@@ -206,10 +230,8 @@ impl<'a> Fold for SuperCalleeFolder<'a> {
 }
 
 impl<'a> SuperCalleeFolder<'a> {
-    fn super_to_get_call(&mut self, super_token: Span, prop: Box<Expr>, computed: bool) -> Expr {
-        self.inject_get = true;
-
-        let proto_arg = get_prototype_of(if self.is_static {
+    fn proto_arg(&self) -> ExprOrSpread {
+        get_prototype_of(if self.is_static {
             // Foo
             Expr::Ident(self.class_name.clone())
         } else {
@@ -218,7 +240,29 @@ impl<'a> SuperCalleeFolder<'a> {
                 .clone()
                 .make_member(quote_ident!("prototype"))
         })
-        .as_arg();
+        .as_arg()
+    }
+
+    fn super_to_get_call(&mut self, super_token: Span, prop: Box<Expr>, computed: bool) -> Expr {
+        self.inject_get = true;
+
+        if let Some(super_class) = self.constant_super_class {
+            // _super.prototype.foo
+            let obj = if self.is_static {
+                Expr::Ident(super_class.clone())
+            } else {
+                super_class.clone().make_member(quote_ident!("prototype"))
+            };
+
+            return Expr::Member(MemberExpr {
+                span: super_token,
+                obj: obj.as_obj(),
+                prop,
+                computed,
+            });
+        }
+
+        let proto_arg = self.proto_arg();
 
         let prop_arg = match *prop {
             Expr::Ident(Ident {
@@ -262,6 +306,7 @@ impl<'a> SuperCalleeFolder<'a> {
         super_token: Span,
         is_update: bool,
         prop: Box<Expr>,
+        computed: bool,
         op: AssignOp,
         rhs: Box<Expr>,
     ) -> Expr {
@@ -292,24 +337,17 @@ impl<'a> SuperCalleeFolder<'a> {
             }
         }
 
-        let proto_arg = get_prototype_of(
-            self.class_name
-                .clone()
-                .make_member(quote_ident!("prototype")),
-        )
-        .as_arg();
+        let proto_arg = self.proto_arg();
 
         let prop_arg = match *prop {
             Expr::Ident(Ident {
-                sym: ref value,
-                span,
-                ..
-            }) => Expr::Lit(Lit::Str(Str {
+                sym: value, span, ..
+            }) if !computed => Expr::Lit(Lit::Str(Str {
                 span,
-                value: value.clone(),
+                value,
                 has_escape: false,
             })),
-            ref e => e.clone(),
+            e => e,
         };
         let prop_arg = match op {
             op!("=") => prop_arg.as_arg(),
@@ -317,7 +355,7 @@ impl<'a> SuperCalleeFolder<'a> {
                 span: DUMMY_SP,
                 left: PatOrExpr::Pat(Box::new(Pat::Ident(ref_ident.clone()))),
                 op: op!("="),
-                right: prop,
+                right: Box::new(prop_arg),
             }
             .as_arg(),
         };
@@ -447,6 +485,7 @@ impl<'a> Fold for SuperFieldAccessFolder<'a> {
             is_static: self.is_static,
             in_nested_scope: self.in_nested_scope,
             this_alias_mark: self.this_alias_mark,
+            constant_super_class: self.constant_super_class,
         };
 
         let should_invoke_call = match n {
@@ -1,4 +1,5 @@
 use crate::util::{ExprFactory, StmtLike};
+use serde::Deserialize;
 use swc_common::{Mark, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{noop_fold_type, Fold, FoldWith, Node, Visit, VisitWith};
@@ -35,13 +36,29 @@ use swc_ecma_visit::{noop_fold_type, Fold, FoldWith, Node, Visit, VisitWith};
 /// TODO(kdy1): cache reference like (_f = f, mutatorMap[_f].get = function(){})
 ///     instead of (mutatorMap[f].get = function(){}
 pub fn computed_properties() -> impl Fold {
-    ComputedProps
+    computed_properties_with_config(Default::default())
 }
 
-struct ComputedProps;
+pub fn computed_properties_with_config(c: Config) -> impl Fold {
+    ComputedProps { c }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Assign properties (`_obj[key] = value`) instead of using
+    /// `_defineProperty`.
+    #[serde(default)]
+    pub set_computed_properties: bool,
+}
+
+struct ComputedProps {
+    c: Config,
+}
 
 #[derive(Default)]
 struct ObjectLitFolder {
+    c: Config,
     vars: Vec<VarDeclarator>,
     used_define_enum_props: bool,
 }
@@ -72,9 +89,10 @@ impl Fold for ObjectLitFolder {
                     props.drain(0..idx).collect()
                 };
 
-                let props_cnt = props.len();
+                // `_defineProperty({}, key, value)` if there's only one property.
+                let is_single = props.len() == 1 && !self.c.set_computed_properties;
 
-                exprs.push(if props_cnt == 1 {
+                exprs.push(if is_single {
                     Box::new(Expr::Object(ObjectLit {
                         span: DUMMY_SP,
                         props: obj_props,
@@ -209,10 +227,34 @@ impl Fold for ObjectLitFolder {
                                 }),
                             ),
                         },
-                        PropOrSpread::Spread(..) => unimplemented!("computed spread property"),
+                        PropOrSpread::Spread(SpreadElement { expr, .. }) => {
+                            // _objectSpread(_obj, spread)
+                            exprs.push(Box::new(Expr::Call(CallExpr {
+                                span,
+                                callee: if self.c.set_computed_properties {
+                                    helper!(extends, "extends")
+                                } else {
+                                    helper!(object_spread, "objectSpread")
+                                },
+                                args: vec![obj_ident.clone().as_arg(), expr.as_arg()],
+                                type_args: Default::default(),
+                            })));
+                            continue;
+                        }
                     };
 
-                    if props_cnt == 1 {
+                    if self.c.set_computed_properties {
+                        // _obj[key] = value
+                        exprs.push(Box::new(Expr::Assign(AssignExpr {
+                            span,
+                            left: PatOrExpr::Expr(Box::new(obj_ident.clone().computed_member(key))),
+                            op: op!("="),
+                            right: Box::new(value),
+                        })));
+                        continue;
+                    }
+
+                    if is_single {
                         return Expr::Call(CallExpr {
                             span,
                             callee: helper!(define_property, "defineProperty"),
@@ -318,7 +360,10 @@ impl ComputedProps {
                 continue;
             }
 
-            let mut folder = ObjectLitFolder::default();
+            let mut folder = ObjectLitFolder {
+                c: self.c,
+                ..Default::default()
+            };
             let stmt = stmt.fold_with(&mut folder);
 
             // Add variable declaration
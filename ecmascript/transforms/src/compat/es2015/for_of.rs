@@ -383,9 +383,9 @@ impl Actual {
                 span: DUMMY_SP,
                 stmts: vec![make_finally_block(
                     iterator_return,
-                    &normal_completion_ident,
-                    error_flag_ident,
-                    error_ident,
+                    Expr::Ident(normal_completion_ident),
+                    Expr::Ident(error_flag_ident),
+                    Expr::Ident(error_ident),
                 )],
             }),
         })
@@ -426,11 +426,11 @@ impl Fold for Actual {
 ///     }
 ///   }
 /// ```
-fn make_finally_block(
+pub(super) fn make_finally_block(
     iterator_return: Box<Expr>,
-    normal_completion_ident: &Ident,
-    error_flag_ident: Ident,
-    error_ident: Ident,
+    normal_completion: Expr,
+    error_flag: Expr,
+    error: Expr,
 ) -> Stmt {
     Stmt::Try(TryStmt {
         span: DUMMY_SP,
@@ -448,7 +448,7 @@ fn make_finally_block(
                         left: Box::new(Expr::Unary(UnaryExpr {
                             span: DUMMY_SP,
                             op: op!("!"),
-                            arg: Box::new(Expr::Ident(normal_completion_ident.clone())),
+                            arg: Box::new(normal_completion),
                         })),
                         op: op!("&&"),
                         right: Box::new(Expr::Bin(BinExpr {
@@ -482,12 +482,12 @@ fn make_finally_block(
                 // }
                 Stmt::If(IfStmt {
                     span: DUMMY_SP,
-                    test: Box::new(Expr::Ident(error_flag_ident)),
+                    test: Box::new(error_flag),
                     cons: Box::new(Stmt::Block(BlockStmt {
                         span: DUMMY_SP,
                         stmts: vec![Stmt::Throw(ThrowStmt {
                             span: DUMMY_SP,
-                            arg: Box::new(Expr::Ident(error_ident)),
+                            arg: Box::new(error),
                         })],
                     })),
                     alt: None,
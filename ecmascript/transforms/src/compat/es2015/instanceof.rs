@@ -48,6 +48,8 @@ impl Fold for InstanceOf {
                     if e.op == op!("instanceof") {
                         self.found = true
                     }
+
+                    e.visit_children_with(self);
                 }
             }
             let mut v = Visitor { found: false };
@@ -0,0 +1,86 @@
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_fold_type, Fold};
+
+/// `@babel/plugin-transform-literals`
+///
+/// Numeric literals are printed from their values, so binary and octal
+/// literals are already emitted as decimal numbers. Escape sequences of string
+/// literals are printed from their values instead of the source text, so that
+/// escapes like `\u{...}` are printed in a form supported by es5.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// var b = 0b11;
+/// var o = 0o7;
+/// var u = 'Hello\u{000A}\u{0009}!';
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var b = 3;
+/// var o = 7;
+/// var u = 'Hello\n\t!';
+/// ```
+pub fn literals() -> impl Fold {
+    Literals
+}
+
+#[derive(Clone, Copy)]
+struct Literals;
+
+impl Fold for Literals {
+    noop_fold_type!();
+
+    fn fold_str(&mut self, s: Str) -> Str {
+        // The code generator prints the value instead of the source text if
+        // `has_escape` is cleared.
+        Str {
+            has_escape: false,
+            ..s
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| literals(),
+        numeric_literals,
+        r#"var a = 0b11, b = 0B10, c = 0o7, d = 0O17, e = 0x10;"#,
+        r#"var a = 3, b = 2, c = 7, d = 15, e = 16;"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| literals(),
+        unicode_escapes,
+        r#"var a = 'Hello\u{000A}\u{0009}!', b = "\u{1F600}", c = { "\u{62}": 1 };"#,
+        r#"var a = 'Hello\n\t!', b = "😀", c = { "b": 1 };"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| literals(),
+        preserve_unescaped,
+        r#"var a = 'foo', b = "bar";"#,
+        r#"var a = 'foo', b = "bar";"#
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| literals(),
+        exec,
+        r#"
+expect(0b1010 + 0o17).toBe(25);
+expect("\u{1F600}").toBe("😀");
+expect('\u{41}\x42C\'').toBe("ABC'");
+"#
+    );
+}
@@ -0,0 +1,262 @@
+use crate::util::ExprFactory;
+use std::mem::replace;
+use swc_atoms::js_word;
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_utils::{private_ident, quote_ident, undefined};
+use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
+
+/// `@babel/plugin-transform-new-target`
+///
+/// `new.target` is replaced with `this.constructor` in constructors and with
+/// `void 0` in methods. Anonymous function expressions are named so that they
+/// can be referenced.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// function Foo() {
+///   console.log(new.target);
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function Foo() {
+///   console.log(this instanceof Foo ? this.constructor : void 0);
+/// }
+/// ```
+pub fn new_target() -> impl Fold {
+    NewTarget::default()
+}
+
+#[derive(Default)]
+struct NewTarget {
+    ctx: Option<Ctx>,
+    /// True if `new.target` is used in the current function.
+    used: bool,
+}
+
+#[derive(Clone)]
+enum Ctx {
+    Constructor,
+    Method,
+    Fn(Ident),
+}
+
+impl NewTarget {
+    fn fold_in_ctx<T>(&mut self, ctx: Ctx, node: T) -> T
+    where
+        T: FoldWith<Self>,
+    {
+        let old_ctx = replace(&mut self.ctx, Some(ctx));
+        let old_used = replace(&mut self.used, false);
+
+        let node = node.fold_children_with(self);
+
+        self.ctx = old_ctx;
+        self.used = old_used;
+
+        node
+    }
+}
+
+impl Fold for NewTarget {
+    noop_fold_type!();
+
+    fn fold_class_method(&mut self, m: ClassMethod) -> ClassMethod {
+        self.fold_in_ctx(Ctx::Method, m)
+    }
+
+    fn fold_class_prop(&mut self, p: ClassProp) -> ClassProp {
+        self.fold_in_ctx(Ctx::Method, p)
+    }
+
+    fn fold_constructor(&mut self, c: Constructor) -> Constructor {
+        self.fold_in_ctx(Ctx::Constructor, c)
+    }
+
+    fn fold_expr(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::MetaProp(MetaPropExpr {
+                meta:
+                    Ident {
+                        sym: js_word!("new"),
+                        ..
+                    },
+                prop:
+                    Ident {
+                        span,
+                        sym: ref target,
+                        ..
+                    },
+            }) if &**target == "target" => {
+                self.used = true;
+
+                match self.ctx {
+                    Some(Ctx::Constructor) => {
+                        Expr::This(ThisExpr { span }).make_member(quote_ident!("constructor"))
+                    }
+                    Some(Ctx::Method) => *undefined(span),
+                    // this instanceof Foo ? this.constructor : void 0
+                    Some(Ctx::Fn(ref name)) => Expr::Cond(CondExpr {
+                        span,
+                        test: Box::new(Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: Box::new(Expr::This(ThisExpr { span: DUMMY_SP })),
+                            op: op!("instanceof"),
+                            right: Box::new(Expr::Ident(name.clone())),
+                        })),
+                        cons: Box::new(
+                            Expr::This(ThisExpr { span: DUMMY_SP })
+                                .make_member(quote_ident!("constructor")),
+                        ),
+                        alt: undefined(DUMMY_SP),
+                    }),
+                    // Syntax error
+                    None => e,
+                }
+            }
+            _ => e.fold_children_with(self),
+        }
+    }
+
+    fn fold_fn_decl(&mut self, f: FnDecl) -> FnDecl {
+        let ctx = Ctx::Fn(f.ident.clone());
+        self.fold_in_ctx(ctx, f)
+    }
+
+    fn fold_fn_expr(&mut self, f: FnExpr) -> FnExpr {
+        let name = f.ident.clone().unwrap_or_else(|| private_ident!("_target"));
+
+        let old_ctx = replace(&mut self.ctx, Some(Ctx::Fn(name.clone())));
+        let old_used = replace(&mut self.used, false);
+
+        let mut f = f.fold_children_with(self);
+        if self.used && f.ident.is_none() {
+            f.ident = Some(name);
+        }
+
+        self.ctx = old_ctx;
+        self.used = old_used;
+
+        f
+    }
+
+    fn fold_getter_prop(&mut self, p: GetterProp) -> GetterProp {
+        self.fold_in_ctx(Ctx::Method, p)
+    }
+
+    fn fold_method_prop(&mut self, p: MethodProp) -> MethodProp {
+        self.fold_in_ctx(Ctx::Method, p)
+    }
+
+    fn fold_private_method(&mut self, m: PrivateMethod) -> PrivateMethod {
+        self.fold_in_ctx(Ctx::Method, m)
+    }
+
+    fn fold_private_prop(&mut self, p: PrivateProp) -> PrivateProp {
+        self.fold_in_ctx(Ctx::Method, p)
+    }
+
+    fn fold_setter_prop(&mut self, p: SetterProp) -> SetterProp {
+        self.fold_in_ctx(Ctx::Method, p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        function,
+        r#"
+function Foo() {
+    const a = () => new.target;
+    return new.target;
+}
+"#,
+        r#"
+function Foo() {
+    const a = () => this instanceof Foo ? this.constructor : void 0;
+    return this instanceof Foo ? this.constructor : void 0;
+}
+"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        anonymous_function,
+        r#"
+var a = function () { return new.target; };
+var b = function () {};
+"#,
+        r#"
+var a = function _target() { return this instanceof _target ? this.constructor : void 0; };
+var b = function () {};
+"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+            class_props: true,
+            ..Default::default()
+        }),
+        |_| new_target(),
+        class,
+        r#"
+class Foo {
+    constructor() {
+        this.a = new.target;
+    }
+    static b() { return new.target; }
+    c = new.target;
+}
+var o = {
+    d() { return new.target; },
+    get e() { return new.target; },
+};
+"#,
+        r#"
+class Foo {
+    constructor() {
+        this.a = this.constructor;
+    }
+    static b() { return void 0; }
+    c = void 0;
+}
+var o = {
+    d() { return void 0; },
+    get e() { return void 0; },
+};
+"#
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        exec,
+        r#"
+function Foo() {
+    return { target: new.target };
+}
+expect(new Foo().target).toBe(Foo);
+expect(Foo().target).toBe(undefined);
+
+class Bar {
+    constructor() {
+        this.target = new.target;
+    }
+}
+class Baz extends Bar {}
+expect(new Bar().target).toBe(Bar);
+expect(new Baz().target).toBe(Baz);
+"#
+    );
+}
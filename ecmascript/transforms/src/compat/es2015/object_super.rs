@@ -0,0 +1,378 @@
+use super::classes::SuperFieldAccessFolder;
+use crate::util::{prepend, StmtLike};
+use std::mem::take;
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_utils::private_ident;
+use swc_ecma_visit::{noop_fold_type, noop_visit_type, Fold, FoldWith, Node, Visit, VisitWith};
+
+/// `@babel/plugin-transform-object-super`
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// let obj = {
+///   foo() {
+///     return super.foo();
+///   }
+/// };
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var _obj;
+/// let obj = _obj = {
+///   foo() {
+///     return _get(_getPrototypeOf(_obj), "foo", this).call(this);
+///   }
+/// };
+/// ```
+pub fn object_super() -> impl Fold {
+    ObjectSuper::default()
+}
+
+#[derive(Default)]
+struct ObjectSuper {
+    vars: Vec<VarDeclarator>,
+}
+
+impl ObjectSuper {
+    fn fold_stmt_like<T>(&mut self, stmts: Vec<T>) -> Vec<T>
+    where
+        T: FoldWith<Self> + StmtLike,
+    {
+        let old = take(&mut self.vars);
+        let mut buf = Vec::with_capacity(stmts.len() + 1);
+
+        for stmt in stmts {
+            let stmt = stmt.fold_with(self);
+
+            if !self.vars.is_empty() {
+                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    decls: take(&mut self.vars),
+                    declare: false,
+                }))));
+            }
+
+            buf.push(stmt);
+        }
+
+        self.vars = old;
+        buf
+    }
+}
+
+impl Fold for ObjectSuper {
+    noop_fold_type!();
+
+    fn fold_expr(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children_with(self);
+
+        match e {
+            Expr::Object(ObjectLit { span, props }) if has_super(&props) => {
+                let obj = private_ident!("_obj");
+                let props = props
+                    .into_iter()
+                    .map(|prop| match prop {
+                        PropOrSpread::Prop(prop) => {
+                            PropOrSpread::Prop(Box::new(fold_prop(&obj, *prop)))
+                        }
+                        _ => prop,
+                    })
+                    .collect();
+
+                self.vars.push(VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(obj.clone()),
+                    init: None,
+                    definite: false,
+                });
+
+                Expr::Assign(AssignExpr {
+                    span,
+                    op: op!("="),
+                    left: PatOrExpr::Pat(Box::new(Pat::Ident(obj))),
+                    right: Box::new(Expr::Object(ObjectLit { span, props })),
+                })
+            }
+            _ => e,
+        }
+    }
+
+    fn fold_module_items(&mut self, items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        self.fold_stmt_like(items)
+    }
+
+    fn fold_stmts(&mut self, stmts: Vec<Stmt>) -> Vec<Stmt> {
+        self.fold_stmt_like(stmts)
+    }
+}
+
+/// Replaces `super` in methods, getters and setters of the object `obj`.
+fn fold_prop(obj: &Ident, prop: Prop) -> Prop {
+    let mut vars = vec![];
+    let mut folder = SuperFieldAccessFolder {
+        class_name: obj,
+        vars: &mut vars,
+        constructor_this_mark: None,
+        // `super` of object methods refers to the prototype of the object.
+        is_static: true,
+        folding_constructor: false,
+        in_nested_scope: false,
+        in_injected_define_property_call: false,
+        this_alias_mark: None,
+        constant_super_class: None,
+    };
+
+    let mut prop = match prop {
+        Prop::Method(..) | Prop::Getter(..) | Prop::Setter(..) => prop.fold_with(&mut folder),
+        _ => return prop,
+    };
+
+    let body = match prop {
+        Prop::Method(MethodProp {
+            function: Function { ref mut body, .. },
+            ..
+        })
+        | Prop::Getter(GetterProp { ref mut body, .. })
+        | Prop::Setter(SetterProp { ref mut body, .. }) => body,
+        _ => unreachable!(),
+    };
+    if let Some(body) = body {
+        if !vars.is_empty() {
+            prepend(
+                &mut body.stmts,
+                Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    declare: false,
+                    decls: vars,
+                })),
+            );
+        }
+    }
+
+    prop
+}
+
+fn has_super(props: &[PropOrSpread]) -> bool {
+    struct Visitor {
+        found: bool,
+    }
+
+    impl Visit for Visitor {
+        noop_visit_type!();
+
+        /// `super` of nested classes is handled separately.
+        fn visit_class(&mut self, _: &Class, _: &dyn Node) {}
+
+        /// `super` in methods of nested objects refers to the nested object,
+        /// but `super` in other properties belongs to the enclosing method.
+        fn visit_prop(&mut self, p: &Prop, _: &dyn Node) {
+            match p {
+                Prop::Method(MethodProp { key, .. })
+                | Prop::Getter(GetterProp { key, .. })
+                | Prop::Setter(SetterProp { key, .. }) => {
+                    key.visit_with(p as _, self);
+                }
+                _ => p.visit_children_with(self),
+            }
+        }
+
+        fn visit_member_expr(&mut self, e: &MemberExpr, _: &dyn Node) {
+            if let ExprOrSuper::Super(..) = e.obj {
+                self.found = true;
+            }
+
+            e.visit_children_with(self);
+        }
+    }
+
+    let mut v = Visitor { found: false };
+    for prop in props {
+        if let PropOrSpread::Prop(prop) = prop {
+            match **prop {
+                Prop::Method(MethodProp { ref function, .. }) => {
+                    function.visit_with(&Invalid { span: DUMMY_SP } as _, &mut v)
+                }
+                Prop::Getter(GetterProp { ref body, .. })
+                | Prop::Setter(SetterProp { ref body, .. }) => {
+                    body.visit_with(&Invalid { span: DUMMY_SP } as _, &mut v)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    v.found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        method,
+        r#"
+let obj = {
+    foo() {
+        return super.foo(1);
+    },
+    bar: 1,
+};
+"#,
+        r#"
+var _obj;
+let obj = _obj = {
+    foo() {
+        return _get(_getPrototypeOf(_obj), "foo", this).call(this, 1);
+    },
+    bar: 1,
+};
+"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        get_and_set,
+        r#"
+function f() {
+    return {
+        get a() {
+            return super.a;
+        },
+        set a(v) {
+            super.a = v;
+        },
+        b() {
+            return () => super[key];
+        },
+    };
+}
+"#,
+        r#"
+function f() {
+    var _obj;
+    return _obj = {
+        get a() {
+            return _get(_getPrototypeOf(_obj), "a", this);
+        },
+        set a(v) {
+            _set(_getPrototypeOf(_obj), "a", v, this, true);
+        },
+        b() {
+            return () => _get(_getPrototypeOf(_obj), key, this);
+        },
+    };
+}
+"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        nested,
+        r#"
+const a = {
+    foo() {
+        return {
+            bar() {
+                return super.bar();
+            }
+        };
+    }
+};
+const b = { c() {} };
+"#,
+        r#"
+const a = {
+    foo() {
+        var _obj;
+        return _obj = {
+            bar() {
+                return _get(_getPrototypeOf(_obj), "bar", this).call(this);
+            }
+        };
+    }
+};
+const b = { c() {} };
+"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        nested_key_value,
+        r#"
+const a = {
+    foo() {
+        return { v: super.x };
+    }
+};
+"#,
+        r#"
+var _obj;
+const a = _obj = {
+    foo() {
+        return {
+            v: _get(_getPrototypeOf(_obj), "x", this)
+        };
+    }
+};
+"#
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        exec,
+        r#"
+const proto = {
+    value: 1,
+    greet(name) {
+        return "hello " + name;
+    },
+};
+const obj = {
+    __proto__: proto,
+    greet(name) {
+        return super.greet(name) + "!";
+    },
+    get value() {
+        return super.value + 1;
+    },
+};
+expect(obj.greet("world")).toBe("hello world!");
+expect(obj.value).toBe(2);
+"#
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        exec_assignment,
+        r#"
+const key = "b";
+const proto = { a: 1, b: 5 };
+const obj = {
+    __proto__: proto,
+    update() {
+        super.a += 2;
+        super[key]++;
+        return this.a + this.b;
+    },
+};
+expect(obj.update()).toBe(9);
+expect(proto.a).toBe(1);
+expect(proto.b).toBe(5);
+"#
+    );
+}
@@ -1,7 +1,10 @@
 use super::leap::{Entry, LeapManager};
 use crate::{
-    compat::es2015::regenerator::leap::{CatchEntry, FinallyEntry, TryEntry},
-    util::{ident::IdentLike, undefined, ExprFactory},
+    compat::es2015::{
+        for_of::make_finally_block,
+        regenerator::leap::{CatchEntry, FinallyEntry, TryEntry},
+    },
+    util::{find_ids, ident::IdentLike, prop_name_to_expr_value, undefined, ExprFactory, HANDLER},
 };
 use smallvec::SmallVec;
 use std::mem::replace;
@@ -56,6 +59,10 @@ pub(super) struct CaseHandler<'a> {
     leaps: LeapManager,
 
     try_entries: SmallVec<[TryEntry; 8]>,
+
+    /// Variables which should be declared in the outer function, like bindings
+    /// of a destructuring pattern in a catch clause.
+    pub vars: Vec<Ident>,
 }
 
 impl<'a> CaseHandler<'a> {
@@ -70,6 +77,7 @@ impl<'a> CaseHandler<'a> {
 
             leaps: Default::default(),
             try_entries: Default::default(),
+            vars: Default::default(),
         }
     }
 }
@@ -309,7 +317,14 @@ impl CaseHandler<'_> {
                 })
             }
 
-            Expr::Await(..) => unimplemented!("regenerator: await in generator"),
+            Expr::Await(e) => {
+                report(
+                    e.span,
+                    "await in a generator function should be lowered by the async generator pass \
+                     before the regenerator",
+                );
+                return Expr::Await(e);
+            }
 
             Expr::Paren(ParenExpr { span, expr }) => {
                 return Expr::Paren(ParenExpr {
@@ -597,11 +612,50 @@ impl CaseHandler<'_> {
                 finish!(expr)
             }
 
+            Expr::Assign(AssignExpr {
+                op: op!("="),
+                left: PatOrExpr::Pat(pat),
+                right,
+                ..
+            }) if match *pat {
+                Pat::Expr(..) => false,
+                _ => contains_leap(&*pat),
+            } =>
+            {
+                // Default values and computed keys are evaluated conditionally,
+                // so the pattern is assigned one binding at a time.
+                //
+                //   ({ a = yield b } = c)
+                //
+                // becomes
+                //
+                //   context.t0 = c
+                //   context.t1 = context.t0
+                //   context.t2 = context.t1.a
+                //   if (context.t2 === void 0) context.t2 = yield b
+                //   a = context.t2
+                let right = self.explode_expr(*right, false);
+                let tmp = self.make_var();
+                self.emit_assign(tmp.clone(), right);
+
+                self.explode_pat_assign(*pat, tmp.clone());
+
+                if ignore_result {
+                    *undefined(DUMMY_SP)
+                } else {
+                    tmp
+                }
+            }
+
             Expr::Assign(e @ AssignExpr { op: op!("="), .. }) => {
                 let expr: Expr = AssignExpr {
                     left: match e.left {
                         PatOrExpr::Expr(e) => e.map(|e| self.explode_expr(e, false)).into(),
-                        _ => e.left,
+                        PatOrExpr::Pat(pat) => match *pat {
+                            // `a[yield b] = c`
+                            Pat::Expr(e) => e.map(|e| self.explode_expr(e, false)).into(),
+                            pat => PatOrExpr::Pat(Box::new(pat)),
+                        },
                     },
                     op: op!("="),
                     right: e.right.map(|e| self.explode_expr(e, false)),
@@ -625,9 +679,25 @@ impl CaseHandler<'_> {
                 // Fixes https://github.com/facebook/regenerator/issues/345.
 
                 let left = match e.left {
-                    PatOrExpr::Expr(e) => e.map(|e| self.explode_expr(e, false)),
-                    _ => unimplemented!("AssignmentPattern in generator"),
+                    PatOrExpr::Expr(e) => e,
+                    PatOrExpr::Pat(pat) => match *pat {
+                        Pat::Ident(i) => Box::new(Expr::Ident(i)),
+                        Pat::Expr(e) => e,
+                        pat => {
+                            // Not a valid assignment target, and rejected by the
+                            // parser.
+                            report(
+                                pat.span(),
+                                "destructuring pattern is not allowed in a compound assignment",
+                            );
+                            return Expr::Assign(AssignExpr {
+                                left: PatOrExpr::Pat(Box::new(pat)),
+                                ..e
+                            });
+                        }
+                    },
                 };
+                let left = left.map(|e| self.explode_expr(e, false));
                 let tmp = self.make_var();
                 self.emit_assign(tmp.clone(), *left.clone());
 
@@ -942,7 +1012,13 @@ impl CaseHandler<'_> {
             Stmt::Empty(..) | Stmt::Debugger(..) => {}
             Stmt::Block(s) => self.explode_stmts(s.stmts),
 
-            Stmt::With(..) => panic!("WithStatement not supported in generator functions"),
+            Stmt::With(s) => {
+                report(
+                    s.span,
+                    "with statement is not supported in generator functions",
+                );
+                self.emit(Stmt::With(s))
+            }
 
             Stmt::Expr(ExprStmt { span, expr, .. }) => {
                 let expr = expr.map(|expr| self.explode_expr(expr, true));
@@ -1109,8 +1185,8 @@ impl CaseHandler<'_> {
                 let catch_entry = handler.as_ref().map(|handler| CatchEntry {
                     first_loc: catch_loc.unwrap(),
                     param_id: match handler.param {
-                        Some(Pat::Ident(ref i)) => i.to_id(),
-                        _ => unimplemented!("regenerator: complex pattern in catch clause"),
+                        Some(Pat::Ident(ref i)) => Some(i.to_id()),
+                        _ => None,
                     },
                 });
 
@@ -1157,12 +1233,38 @@ impl CaseHandler<'_> {
                         //    catchParamName: handler.param.name
                         //});
                         handler = handler.map(|handler| {
-                            let body = handler.body.fold_with(&mut CatchParamHandler {
+                            let mut body = handler.body.fold_with(&mut CatchParamHandler {
                                 safe_param: &safe_param,
                                 param: handler.param.as_ref(),
                             });
 
-                            CatchClause { body, ..handler }
+                            match handler.param {
+                                Some(Pat::Ident(..)) | None => {}
+                                // catch ({ message }) {}
+                                //
+                                // is converted to
+                                //
+                                //   ({ message } = _ctx.t0);
+                                Some(pat) => {
+                                    folder.vars.extend(find_ids::<_, Ident>(&pat));
+                                    body.stmts.insert(
+                                        0,
+                                        AssignExpr {
+                                            span: DUMMY_SP,
+                                            op: op!("="),
+                                            left: PatOrExpr::Pat(Box::new(pat)),
+                                            right: Box::new(safe_param.clone()),
+                                        }
+                                        .into_stmt(),
+                                    );
+                                }
+                            }
+
+                            CatchClause {
+                                body,
+                                param: None,
+                                ..handler
+                            }
                         });
 
                         folder.with_entry(
@@ -1360,21 +1462,7 @@ impl CaseHandler<'_> {
                 {
                     let right =
                         Box::new(key_info_tmp_var.clone().make_member(quote_ident!("value")));
-                    match s.left {
-                        VarDeclOrPat::VarDecl(var) => unreachable!(
-                            "VarDeclaration in for-in statement must be hoisted: {:?}",
-                            var
-                        ),
-                        VarDeclOrPat::Pat(pat) => self.emit(
-                            AssignExpr {
-                                span: DUMMY_SP,
-                                op: op!("="),
-                                left: PatOrExpr::Pat(Box::new(pat)),
-                                right,
-                            }
-                            .into_stmt(),
-                        ),
-                    }
+                    self.emit_loop_left(s.left, right);
                 }
 
                 self.with_entry(
@@ -1393,14 +1481,374 @@ impl CaseHandler<'_> {
                 self.mark(after);
             }
 
-            Stmt::ForOf(s) => unreachable!(
-                "for-of statement should be removed by es2015::for_of pass\n{:?}",
+            Stmt::ForOf(
                 s
-            ),
+                @
+                ForOfStmt {
+                    await_token: Some(..),
+                    ..
+                },
+            ) => {
+                report(
+                    s.span,
+                    "for await statement should be lowered by the async generator pass before the \
+                     regenerator",
+                );
+                self.emit(Stmt::ForOf(s))
+            }
+
+            // The loop is lowered like es2015::for_of so that the iterator is
+            // closed on an abrupt completion.
+            Stmt::ForOf(s) => {
+                let normal_completion = self.make_var();
+                let error_flag = self.make_var();
+                let error = self.make_var();
+                let iter = self.make_var();
+                let step = self.make_var();
+
+                self.emit_assign(
+                    normal_completion.clone(),
+                    Expr::Lit(Lit::Bool(Bool {
+                        span: DUMMY_SP,
+                        value: true,
+                    })),
+                );
+                self.emit_assign(
+                    error_flag.clone(),
+                    Expr::Lit(Lit::Bool(Bool {
+                        span: DUMMY_SP,
+                        value: false,
+                    })),
+                );
+                self.emit_assign(error.clone(), *undefined(DUMMY_SP));
+
+                let body = Stmt::Block(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![
+                        loop_left_to_stmt(
+                            s.left,
+                            Box::new(step.clone().make_member(quote_ident!("value"))),
+                        ),
+                        *s.body,
+                    ],
+                });
+
+                let for_stmt = Stmt::For(ForStmt {
+                    span: s.span,
+                    // `iter = regeneratorRuntime.values(right)`
+                    init: Some(VarDeclOrExpr::Expr(Box::new(Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        op: op!("="),
+                        left: PatOrExpr::Expr(Box::new(iter.clone())),
+                        right: Box::new(Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            callee: member_expr!(DUMMY_SP, regeneratorRuntime.values).as_callee(),
+                            args: vec![s.right.as_arg()],
+                            type_args: Default::default(),
+                        })),
+                    })))),
+                    // `!(normal = (step = iter.next()).done)`
+                    test: Some(Box::new(Expr::Unary(UnaryExpr {
+                        span: DUMMY_SP,
+                        op: op!("!"),
+                        arg: Box::new(Expr::Assign(AssignExpr {
+                            span: DUMMY_SP,
+                            op: op!("="),
+                            left: PatOrExpr::Expr(Box::new(normal_completion.clone())),
+                            right: Box::new(
+                                AssignExpr {
+                                    span: DUMMY_SP,
+                                    op: op!("="),
+                                    left: PatOrExpr::Expr(Box::new(step)),
+                                    right: Box::new(Expr::Call(CallExpr {
+                                        span: DUMMY_SP,
+                                        callee: iter
+                                            .clone()
+                                            .make_member(quote_ident!("next"))
+                                            .as_callee(),
+                                        args: vec![],
+                                        type_args: Default::default(),
+                                    })),
+                                }
+                                .make_member(quote_ident!("done")),
+                            ),
+                        })),
+                    }))),
+                    // `normal = true`
+                    update: Some(Box::new(Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        op: op!("="),
+                        left: PatOrExpr::Expr(Box::new(normal_completion.clone())),
+                        right: Box::new(Expr::Lit(Lit::Bool(Bool {
+                            span: DUMMY_SP,
+                            value: true,
+                        }))),
+                    }))),
+                    body: Box::new(body),
+                });
+
+                let for_stmt = match label {
+                    Some(label) => Stmt::Labeled(LabeledStmt {
+                        span: s.span,
+                        label: Ident::new(label, DUMMY_SP),
+                        body: Box::new(for_stmt),
+                    }),
+                    None => for_stmt,
+                };
+
+                let try_stmt = Stmt::Try(TryStmt {
+                    span: DUMMY_SP,
+                    block: BlockStmt {
+                        span: DUMMY_SP,
+                        stmts: vec![for_stmt],
+                    },
+                    handler: Some(CatchClause {
+                        span: DUMMY_SP,
+                        param: Some(Pat::Ident(quote_ident!("err"))),
+                        body: BlockStmt {
+                            span: DUMMY_SP,
+                            stmts: vec![
+                                AssignExpr {
+                                    span: DUMMY_SP,
+                                    op: op!("="),
+                                    left: PatOrExpr::Expr(Box::new(error_flag.clone())),
+                                    right: Box::new(Expr::Lit(Lit::Bool(Bool {
+                                        span: DUMMY_SP,
+                                        value: true,
+                                    }))),
+                                }
+                                .into_stmt(),
+                                AssignExpr {
+                                    span: DUMMY_SP,
+                                    op: op!("="),
+                                    left: PatOrExpr::Expr(Box::new(error.clone())),
+                                    right: Box::new(Expr::Ident(quote_ident!("err"))),
+                                }
+                                .into_stmt(),
+                            ],
+                        },
+                    }),
+                    finalizer: Some(BlockStmt {
+                        span: DUMMY_SP,
+                        stmts: vec![make_finally_block(
+                            Box::new(iter.make_member(quote_ident!("return"))),
+                            normal_completion,
+                            error_flag,
+                            error,
+                        )],
+                    }),
+                });
+
+                self.explode_stmt(try_stmt, None);
+            }
 
             Stmt::Decl(_) => self.emit(s),
         }
     }
+
+    /// Assigns `right` to the left hand side of a for-in statement.
+    fn emit_loop_left(&mut self, left: VarDeclOrPat, right: Box<Expr>) {
+        let stmt = loop_left_to_stmt(left, right);
+        self.explode_stmt(stmt, None);
+    }
+
+    /// Assigns `value` to `pat` one binding at a time, so that default values
+    /// and computed keys are evaluated in order and only when required.
+    fn explode_pat_assign(&mut self, pat: Pat, value: Expr) {
+        if !contains_leap(&pat) {
+            let left = match pat {
+                Pat::Expr(e) => PatOrExpr::Expr(e),
+                pat => PatOrExpr::Pat(Box::new(pat)),
+            };
+            self.emit(
+                AssignExpr {
+                    span: DUMMY_SP,
+                    op: op!("="),
+                    left,
+                    right: Box::new(value),
+                }
+                .into_stmt(),
+            );
+            return;
+        }
+
+        match pat {
+            // `[a[yield b]] = c`
+            Pat::Expr(e) => {
+                let target = self.explode_expr(*e, false);
+                self.emit_assign(target, value);
+            }
+
+            Pat::Assign(AssignPat { left, right, .. }) => {
+                let tmp = self.make_var();
+                self.emit_assign(tmp.clone(), value);
+
+                let after = self.loc();
+                self.jump_if_not(
+                    Box::new(Expr::Bin(BinExpr {
+                        span: DUMMY_SP,
+                        left: Box::new(tmp.clone()),
+                        op: op!("==="),
+                        right: undefined(DUMMY_SP),
+                    })),
+                    after,
+                );
+                let default = self.explode_expr(*right, false);
+                self.emit_assign(tmp.clone(), default);
+                self.mark(after);
+
+                self.explode_pat_assign(*left, tmp);
+            }
+
+            Pat::Array(ArrayPat { elems, .. }) => {
+                let has_rest = elems.iter().any(|elem| match elem {
+                    Some(Pat::Rest(..)) => true,
+                    _ => false,
+                });
+
+                let tmp = self.make_var();
+                self.emit_assign(
+                    tmp.clone(),
+                    Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: if has_rest {
+                            helper!(to_array, "toArray")
+                        } else {
+                            helper!(sliced_to_array, "slicedToArray")
+                        },
+                        args: if has_rest {
+                            vec![value.as_arg()]
+                        } else {
+                            vec![
+                                value.as_arg(),
+                                Lit::Num(Number {
+                                    span: DUMMY_SP,
+                                    value: elems.len() as _,
+                                })
+                                .as_arg(),
+                            ]
+                        },
+                        type_args: Default::default(),
+                    }),
+                );
+
+                for (i, elem) in elems.into_iter().enumerate() {
+                    let idx = Lit::Num(Number {
+                        span: DUMMY_SP,
+                        value: i as _,
+                    });
+
+                    match elem {
+                        // Hole
+                        None => {}
+                        Some(Pat::Rest(RestPat { arg, .. })) => {
+                            let rest = CallExpr {
+                                span: DUMMY_SP,
+                                callee: tmp.clone().make_member(quote_ident!("slice")).as_callee(),
+                                args: vec![idx.as_arg()],
+                                type_args: Default::default(),
+                            };
+                            self.explode_pat_assign(*arg, rest.into());
+                        }
+                        Some(elem) => {
+                            self.explode_pat_assign(elem, tmp.clone().computed_member(idx));
+                        }
+                    }
+                }
+            }
+
+            Pat::Object(ObjectPat { props, .. }) => {
+                let tmp = self.make_var();
+                self.emit_assign(tmp.clone(), value);
+
+                for prop in props {
+                    match prop {
+                        ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => {
+                            let value_expr = match key {
+                                PropName::Ident(key) => tmp.clone().make_member(key),
+                                // `{ [yield a]: b } = c`
+                                PropName::Computed(key) => {
+                                    let key = self.explode_expr(*key.expr, false);
+                                    let key_tmp = self.make_var();
+                                    self.emit_assign(key_tmp.clone(), key);
+
+                                    tmp.clone().computed_member(key_tmp)
+                                }
+                                key => tmp.clone().computed_member(prop_name_to_expr_value(key)),
+                            };
+
+                            self.explode_pat_assign(*value, value_expr);
+                        }
+
+                        ObjectPatProp::Assign(AssignPatProp { key, value, .. }) => {
+                            let value_expr = tmp.clone().make_member(key.clone());
+
+                            match value {
+                                // `{ a = yield b } = c`
+                                Some(default) => self.explode_pat_assign(
+                                    Pat::Assign(AssignPat {
+                                        span: DUMMY_SP,
+                                        left: Box::new(Pat::Ident(key)),
+                                        right: default,
+                                        type_ann: None,
+                                    }),
+                                    value_expr,
+                                ),
+                                None => self.emit_assign(Expr::Ident(key), value_expr),
+                            }
+                        }
+
+                        ObjectPatProp::Rest(rest) => report(
+                            rest.span,
+                            "object rest pattern should be lowered by the object rest spread pass \
+                             before the regenerator",
+                        ),
+                    }
+                }
+            }
+
+            _ => {
+                report(
+                    pat.span(),
+                    "yield in this pattern is not supported by the regenerator",
+                );
+                self.emit(
+                    AssignExpr {
+                        span: DUMMY_SP,
+                        op: op!("="),
+                        left: PatOrExpr::Pat(Box::new(pat)),
+                        right: Box::new(value),
+                    }
+                    .into_stmt(),
+                );
+            }
+        }
+    }
+}
+
+/// Assigns `right` to the left hand side of a for-in or a for-of statement.
+fn loop_left_to_stmt(left: VarDeclOrPat, right: Box<Expr>) -> Stmt {
+    match left {
+        VarDeclOrPat::VarDecl(var) => {
+            // `for (var a = b in c)`, which is only allowed in sloppy mode.
+            report(
+                var.span,
+                "initializer of a for-in statement is not supported in generator functions",
+            );
+            Stmt::Decl(Decl::Var(var))
+        }
+        VarDeclOrPat::Pat(pat) => AssignExpr {
+            span: DUMMY_SP,
+            op: op!("="),
+            left: PatOrExpr::Pat(Box::new(pat)),
+            right,
+        }
+        .into_stmt(),
+    }
+}
+
+fn report(span: Span, msg: &str) {
+    HANDLER.with(|handler| handler.struct_span_err(span, msg).emit());
 }
 
 struct LeapFinder {
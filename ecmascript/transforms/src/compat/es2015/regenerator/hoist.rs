@@ -133,7 +133,9 @@ impl Fold for Hoister {
 
             VarDeclOrPat::VarDecl(mut var) => {
                 if var.decls.len() == 1 && var.decls[0].init.is_none() {
-                    return var.decls.remove(0).name.into();
+                    let name = var.decls.remove(0).name;
+                    self.vars.extend(find_ids::<_, Ident>(&name));
+                    return name.into();
                 }
 
                 var.into()
@@ -118,7 +118,8 @@ pub(super) struct TryEntry {
 #[derive(Debug, Clone)]
 pub(super) struct CatchEntry {
     pub first_loc: Loc,
-    pub param_id: Id,
+    /// [None] if the parameter is omitted or a destructuring pattern.
+    pub param_id: Option<Id>,
 }
 
 #[derive(Debug, Clone)]
@@ -327,6 +327,13 @@ impl Regenerator {
             }));
 
         handler.explode_stmts(body.stmts);
+        self.outer_fn_vars
+            .extend(handler.vars.drain(..).map(|id| VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(id),
+                init: None,
+                definite: false,
+            }));
 
         let mut cases = vec![];
 
@@ -1,18 +1,38 @@
 use crate::util::{is_literal, prepend_stmts, ExprFactory, StmtLike};
+use serde::Deserialize;
 use std::{iter, mem};
 use swc_atoms::js_word;
-use swc_common::{BytePos, Mark, Spanned, SyntaxContext, DUMMY_SP};
+use swc_common::{BytePos, Mark, Span, Spanned, SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
 
 pub fn template_literal() -> impl Fold {
+    template_literal_with_config(Default::default())
+}
+
+pub fn template_literal_with_config(c: Config) -> impl Fold {
     TemplateLiteral {
+        c,
         added: Default::default(),
         str_ctxt: SyntaxContext::empty().apply_mark(Mark::fresh(Mark::root())),
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Concatenate with `+` instead of `.concat()`.
+    #[serde(default)]
+    pub ignore_to_primitive_hint: bool,
+
+    /// Use `_taggedTemplateLiteralLoose`, which does not freeze the template
+    /// object.
+    #[serde(default)]
+    pub mutable_template_object: bool,
+}
+
 struct TemplateLiteral {
+    c: Config,
     added: Vec<Stmt>,
     /// Applied to [Str] created by this pass.
     ///
@@ -20,6 +40,51 @@ struct TemplateLiteral {
     str_ctxt: SyntaxContext,
 }
 
+impl TemplateLiteral {
+    /// Creates `"a" + b + "c"` from `a${b}c`.
+    fn concat_with_plus(&self, span: Span, quasis: Vec<TplElement>, exprs: Vec<Box<Expr>>) -> Expr {
+        let mut quasis = quasis.into_iter();
+        let mut exprs = exprs.into_iter();
+
+        // The first string is kept even if it's empty, as it makes the result a
+        // string.
+        let mut obj = Box::new(Expr::Lit(Lit::Str(
+            self.str_from_quasi(quasis.next().unwrap()),
+        )));
+
+        for expr in &mut exprs {
+            obj = Box::new(Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                left: obj,
+                op: op!(bin, "+"),
+                right: expr,
+            }));
+
+            let s = self.str_from_quasi(quasis.next().unwrap());
+            if s.value.is_empty() {
+                continue;
+            }
+            obj = Box::new(Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                left: obj,
+                op: op!(bin, "+"),
+                right: Box::new(Expr::Lit(Lit::Str(s))),
+            }));
+        }
+
+        match *obj {
+            Expr::Bin(bin) => Expr::Bin(BinExpr { span, ..bin }),
+            obj => obj,
+        }
+    }
+
+    fn str_from_quasi(&self, quasi: TplElement) -> Str {
+        let mut s = quasi.cooked.unwrap_or(quasi.raw);
+        s.span.ctxt = self.str_ctxt;
+        s
+    }
+}
+
 impl Fold for TemplateLiteral {
     noop_fold_type!();
 
@@ -38,6 +103,10 @@ impl Fold for TemplateLiteral {
             }) => {
                 assert_eq!(quasis.len(), exprs.len() + 1);
 
+                if self.c.ignore_to_primitive_hint {
+                    return self.concat_with_plus(span, quasis, exprs);
+                }
+
                 // TODO: Optimize
 
                 // This makes result of addition string
@@ -218,10 +287,14 @@ impl Fold for TemplateLiteral {
                                 definite: false,
                                 init: Some(Box::new(Expr::Call(CallExpr {
                                     span: DUMMY_SP,
-                                    callee: helper!(
-                                        tagged_template_literal,
-                                        "taggedTemplateLiteral"
-                                    ),
+                                    callee: if self.c.mutable_template_object {
+                                        helper!(
+                                            tagged_template_literal_loose,
+                                            "taggedTemplateLiteralLoose"
+                                        )
+                                    } else {
+                                        helper!(tagged_template_literal, "taggedTemplateLiteral")
+                                    },
                                     args: {
                                         let has_escape = quasis.iter().any(|s| {
                                             s.cooked.as_ref().map(|s| s.has_escape).unwrap_or(true)
@@ -0,0 +1,290 @@
+use crate::{
+    compat::regexp::{
+        property_set, surrogate_pair, Alternative, Atom, Char, CharSet, Class, ClassItem,
+        Disjunction, GroupKind, Pattern, Term,
+    },
+    util::HANDLER,
+};
+use std::collections::BTreeMap;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
+
+/// Compile ES2015 unicode regex to an ES5 compatible regex.
+///
+/// Astral code points are replaced with surrogate pairs, and `.`, negated
+/// classes and classes with astral code points are expanded so that they
+/// match whole code points.
+///
+/// Regexes with the `i` flag are not modified, because case folding of `iu`
+/// differs from the one of `i` (e.g. `/\u017F/iu` matches `s`).
+///
+///# Example
+///## In
+///
+/// ```js
+/// /😀+/u;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// /(?:\uD83D\uDE00)+/;
+/// ```
+pub fn unicode_regex() -> impl 'static + Fold {
+    UnicodeRegex
+}
+
+#[derive(Clone, Copy)]
+struct UnicodeRegex;
+
+impl Fold for UnicodeRegex {
+    noop_fold_type!();
+
+    fn fold_expr(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children_with(self);
+
+        match e {
+            Expr::Lit(Lit::Regex(Regex { span, exp, flags }))
+                if flags.contains('u') && !flags.contains('i') =>
+            {
+                let mut pattern = match Pattern::parse(&exp, &flags) {
+                    Ok(pattern) => pattern,
+                    Err(..) => return Expr::Lit(Lit::Regex(Regex { span, exp, flags })),
+                };
+                let dot_all = flags.contains('s');
+
+                match lower_disjunction(&mut pattern.body, dot_all) {
+                    Some(()) => Expr::Lit(Lit::Regex(Regex {
+                        span,
+                        exp: pattern.print(false).into(),
+                        flags: flags.replace('u', "").into(),
+                    })),
+                    None => {
+                        HANDLER.with(|handler| {
+                            handler
+                                .struct_span_err(
+                                    span,
+                                    "unknown unicode property escape in a regular expression",
+                                )
+                                .emit()
+                        });
+
+                        Expr::Lit(Lit::Regex(Regex { span, exp, flags }))
+                    }
+                }
+            }
+            _ => e,
+        }
+    }
+}
+
+fn lower_disjunction(body: &mut Disjunction, dot_all: bool) -> Option<()> {
+    for alt in body {
+        let terms = std::mem::take(alt);
+        for term in terms {
+            lower_term(alt, term, dot_all)?;
+        }
+    }
+
+    Some(())
+}
+
+fn lower_term(buf: &mut Alternative, term: Term, dot_all: bool) -> Option<()> {
+    let Term {
+        mut atom,
+        quantifier,
+    } = term;
+
+    let set = match atom {
+        Atom::Char(ref mut c) if c.value > 0xffff => {
+            let (high, low) = surrogate_pair(c.value);
+            let pair = vec![
+                Term::from(Atom::Char(Char::new(high))),
+                Term::from(Atom::Char(Char::new(low))),
+            ];
+            if quantifier.is_none() {
+                buf.extend(pair);
+            } else {
+                buf.push(Term {
+                    atom: Atom::Group {
+                        kind: GroupKind::NonCapture,
+                        body: vec![pair],
+                    },
+                    quantifier,
+                });
+            }
+            return Some(());
+        }
+        Atom::Char(ref mut c) => {
+            // `\u{41}` is not valid without the flag.
+            if c.raw.as_ref().map_or(false, |raw| raw.starts_with("\\u{")) {
+                c.raw = None;
+            }
+            None
+        }
+        Atom::Dot if dot_all => Some(CharSet::from_ranges(vec![(0, 0x10ffff)])),
+        Atom::Dot => Some(
+            CharSet::from_ranges(vec![(0x0a, 0x0a), (0x0d, 0x0d), (0x2028, 0x2029)]).complement(),
+        ),
+        Atom::Builtin(c) if c.is_ascii_uppercase() => Some(CharSet::builtin(c)),
+        Atom::Property { negated, ref name } => Some(property_set(negated, name)?),
+        Atom::Class(ref class) => Some(class.to_set()?),
+        Atom::Group { ref mut body, .. } => {
+            lower_disjunction(body, dot_all)?;
+            None
+        }
+        _ => None,
+    };
+
+    if let Some(set) = set {
+        atom = set_to_atom(&set);
+    }
+    buf.push(Term { atom, quantifier });
+
+    Some(())
+}
+
+/// Creates an atom matching a code point in `set`, without the `u` flag.
+fn set_to_atom(set: &CharSet) -> Atom {
+    let (bmp, astral) = set.split_bmp();
+    if astral.is_empty() {
+        return Atom::Class(Class::from_set(&bmp));
+    }
+
+    // Low surrogates for each high surrogate.
+    let mut lows = BTreeMap::<u32, CharSet>::new();
+    for &(start, end) in astral.ranges() {
+        let (start_high, start_low) = surrogate_pair(start);
+        let (end_high, end_low) = surrogate_pair(end);
+        for high in start_high..=end_high {
+            let low_start = if high == start_high {
+                start_low
+            } else {
+                0xdc00
+            };
+            let low_end = if high == end_high { end_low } else { 0xdfff };
+            lows.entry(high).or_default().add(low_start, low_end);
+        }
+    }
+
+    // Merge high surrogates with the same low surrogates.
+    let mut branches: Vec<(u32, u32, CharSet)> = vec![];
+    for (high, low) in lows {
+        match branches.last_mut() {
+            Some((_, end, prev)) if *end + 1 == high && *prev == low => *end = high,
+            _ => branches.push((high, high, low)),
+        }
+    }
+
+    let mut body = branches
+        .into_iter()
+        .map(|(start, end, low)| {
+            let high = if start == end {
+                Atom::Char(Char::new(start))
+            } else {
+                Atom::Class(Class {
+                    negated: false,
+                    items: vec![ClassItem::Range(Char::new(start), Char::new(end))],
+                })
+            };
+            let low = match low.ranges() {
+                [(start, end)] if start == end => Atom::Char(Char::new(*start)),
+                _ => Atom::Class(Class::from_set(&low)),
+            };
+            vec![Term::from(high), Term::from(low)]
+        })
+        .collect::<Vec<_>>();
+
+    // Surrogate pairs should be matched before lone surrogates.
+    if !bmp.is_empty() {
+        body.push(vec![Term::from(Atom::Class(Class::from_set(&bmp)))]);
+    }
+
+    Atom::Group {
+        kind: GroupKind::NonCapture,
+        body,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_common::DUMMY_SP;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodeRegex,
+        astral_char,
+        r#"var a = /😀/u; var b = /\u{1F600}+/u; var c = /😀?/u;"#,
+        r#"var a = /\uD83D\uDE00/; var b = /(?:\uD83D\uDE00)+/; var c = /(?:\uD83D\uDE00)?/;"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodeRegex,
+        bmp_class,
+        r#"var a = /[a-z\d]\u{41}\w/gu;"#,
+        r#"var a = /[0-9a-z]A\w/g;"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodeRegex,
+        ignore_case,
+        r#"var a = /[a-z\d]\u{41}\w/giu; var b = /\u017F/iu;"#,
+        r#"var a = /[a-z\d]\u{41}\w/giu; var b = /\u017F/iu;"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodeRegex,
+        astral_class,
+        r#"var a = /[a😀-😂]/u;"#,
+        r#"var a = /(?:\uD83D[\uDE00-\uDE02]|[a])/;"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodeRegex,
+        dot,
+        r#"var a = /^.$/u;"#,
+        r#"var a = /^(?:[\uD800-\uDBFF][\uDC00-\uDFFF]|[\0-\t\x0B-\f\x0E-\u2027\u202A-\uFFFF])$/;"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodeRegex,
+        ignore_non_unicode,
+        r#"var a = /😀+/; var b = /(/u;"#,
+        r#"var a = /😀+/; var b = /(/u;"#
+    );
+
+    #[test]
+    fn unknown_property() {
+        let res = ::testing::run_test(false, |_, handler| {
+            HANDLER.set(handler, || {
+                Expr::Lit(Lit::Regex(Regex {
+                    span: DUMMY_SP,
+                    exp: "\\p{Unknown}".into(),
+                    flags: "u".into(),
+                }))
+                .fold_with(&mut UnicodeRegex);
+            });
+
+            if handler.has_errors() {
+                Err(())
+            } else {
+                Ok(())
+            }
+        });
+
+        let stderr = res
+            .expect_err("unknown property should be reported")
+            .to_string();
+        assert!(
+            stderr.contains("unknown unicode property escape"),
+            "{}",
+            stderr
+        );
+    }
+}
@@ -322,8 +322,8 @@ impl Fold for Actual {
 ///     }
 /// }
 /// ```
-struct MethodFolder {
-    vars: Vec<VarDeclarator>,
+pub(crate) struct MethodFolder {
+    pub vars: Vec<VarDeclarator>,
 }
 
 impl MethodFolder {
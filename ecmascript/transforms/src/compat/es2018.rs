@@ -1,12 +1,39 @@
 pub use self::{
-    object_rest_spread::object_rest_spread, optional_catch_binding::optional_catch_binding,
+    async_generator_functions::async_generator_functions,
+    dotall_regex::dotall_regex,
+    named_capturing_groups_regex::named_capturing_groups_regex,
+    object_rest_spread::{object_rest_spread, object_rest_spread_with_config},
+    optional_catch_binding::optional_catch_binding,
+    unicode_property_regex::unicode_property_regex,
 };
+use serde::Deserialize;
 use swc_common::chain;
 use swc_ecma_visit::Fold;
 
-mod object_rest_spread;
+mod async_generator_functions;
+mod dotall_regex;
+mod named_capturing_groups_regex;
+pub mod object_rest_spread;
 mod optional_catch_binding;
+mod unicode_property_regex;
 
 pub fn es2018() -> impl Fold {
-    chain!(object_rest_spread(), optional_catch_binding())
+    es2018_with_config(Default::default())
+}
+
+pub fn es2018_with_config(c: Config) -> impl Fold {
+    chain!(
+        async_generator_functions(),
+        object_rest_spread_with_config(c.object_rest_spread),
+        optional_catch_binding(),
+        dotall_regex(),
+        unicode_property_regex(),
+        named_capturing_groups_regex(),
+    )
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(flatten)]
+    pub object_rest_spread: object_rest_spread::Config,
 }
@@ -0,0 +1,532 @@
+use crate::{
+    compat::es2017::MethodFolder,
+    perf::Check,
+    util::{prepend, ExprFactory, StmtLike},
+};
+use std::mem::take;
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_transforms_macros::fast_path;
+use swc_ecma_visit::{noop_fold_type, noop_visit_type, Fold, FoldWith, Node, Visit, VisitWith};
+
+/// `@babel/plugin-proposal-async-generator-functions`
+///
+/// Async generators are converted to generators, and `for await` loops are
+/// converted to `for` loops using `await`. So `async_to_generator` and
+/// `regenerator` should be applied after this pass if required.
+///
+/// ## In
+///
+/// ```js
+/// async function* foo(stream) {
+///     for await (const chunk of stream) {
+///         yield await chunk.text();
+///     }
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function foo(stream) {
+///     return _wrapAsyncGenerator(function* () {
+///         var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+///         try {
+///             for (var _iterator = _asyncIterator(stream), _step, _value; _step = yield _awaitAsyncGenerator(_iterator.next()), _iteratorNormalCompletion = _step.done, _value = yield _awaitAsyncGenerator(_step.value), !_iteratorNormalCompletion; _iteratorNormalCompletion = true) {
+///                 const chunk = _value;
+///                 yield yield _awaitAsyncGenerator(chunk.text());
+///             }
+///         } catch (err) {
+///             ...
+///         }
+///     }).apply(this, arguments);
+/// }
+/// ```
+pub fn async_generator_functions() -> impl Fold {
+    AsyncGeneratorFunctions::default()
+}
+
+#[derive(Default)]
+struct AsyncGeneratorFunctions {
+    vars: Vec<VarDeclarator>,
+}
+
+impl AsyncGeneratorFunctions {
+    fn fold_stmt_like<T>(&mut self, stmts: Vec<T>) -> Vec<T>
+    where
+        T: FoldWith<Self> + StmtLike,
+    {
+        let mut buf = Vec::with_capacity(stmts.len() + 2);
+
+        for stmt in stmts {
+            let stmt = stmt.fold_with(self);
+
+            if !self.vars.is_empty() {
+                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    decls: take(&mut self.vars),
+                    declare: false,
+                }))));
+            }
+
+            buf.push(stmt);
+        }
+
+        buf
+    }
+
+    /// Converts a `for await` loop to a `for` loop using `await`.
+    fn fold_for_await(&mut self, label: Option<Ident>, s: ForOfStmt) -> Stmt {
+        let ForOfStmt {
+            span,
+            left,
+            right,
+            body,
+            ..
+        } = s.fold_children_with(self);
+
+        let iterator = private_ident!("_iterator");
+        let step = private_ident!("_step");
+        let value = private_ident!("_value");
+        let normal_completion = private_ident!("_iteratorNormalCompletion");
+        let did_error = private_ident!("_didIteratorError");
+        let error = private_ident!("_iteratorError");
+
+        self.vars.push(VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(normal_completion.clone()),
+            init: Some(Box::new(Expr::Lit(Lit::Bool(Bool {
+                span: DUMMY_SP,
+                value: true,
+            })))),
+            definite: false,
+        });
+        self.vars.push(VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(did_error.clone()),
+            init: Some(Box::new(Expr::Lit(Lit::Bool(Bool {
+                span: DUMMY_SP,
+                value: false,
+            })))),
+            definite: false,
+        });
+        self.vars.push(VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(error.clone()),
+            init: None,
+            definite: false,
+        });
+
+        let mut body = match *body {
+            Stmt::Block(block) => block,
+            body => BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![body],
+            },
+        };
+        prepend(
+            &mut body.stmts,
+            match left {
+                VarDeclOrPat::VarDecl(mut var) => {
+                    debug_assert_eq!(var.decls.len(), 1);
+                    Stmt::Decl(Decl::Var(VarDecl {
+                        decls: vec![VarDeclarator {
+                            init: Some(Box::new(Expr::Ident(value.clone()))),
+                            ..var.decls.pop().unwrap()
+                        }],
+                        ..var
+                    }))
+                }
+                VarDeclOrPat::Pat(pat) => AssignExpr {
+                    span: DUMMY_SP,
+                    left: PatOrExpr::Pat(Box::new(pat)),
+                    op: op!("="),
+                    right: Box::new(Expr::Ident(value.clone())),
+                }
+                .into_stmt(),
+            },
+        );
+
+        let assign = |left: &Ident, right: Expr| {
+            Box::new(Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                left: PatOrExpr::Pat(Box::new(Pat::Ident(left.clone()))),
+                op: op!("="),
+                right: Box::new(right),
+            }))
+        };
+        let await_expr = |arg: Expr| {
+            Expr::Await(AwaitExpr {
+                span: DUMMY_SP,
+                arg: Box::new(arg),
+            })
+        };
+        let bool_lit = |value: bool| {
+            Expr::Lit(Lit::Bool(Bool {
+                span: DUMMY_SP,
+                value,
+            }))
+        };
+
+        // _step = await _iterator.next(), _iteratorNormalCompletion = _step.done,
+        // _value = await _step.value, !_iteratorNormalCompletion
+        let test = Expr::Seq(SeqExpr {
+            span: DUMMY_SP,
+            exprs: vec![
+                assign(
+                    &step,
+                    await_expr(Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: iterator
+                            .clone()
+                            .make_member(quote_ident!("next"))
+                            .as_callee(),
+                        args: vec![],
+                        type_args: Default::default(),
+                    })),
+                ),
+                assign(
+                    &normal_completion,
+                    step.clone().make_member(quote_ident!("done")),
+                ),
+                assign(
+                    &value,
+                    await_expr(step.clone().make_member(quote_ident!("value"))),
+                ),
+                Box::new(Expr::Unary(UnaryExpr {
+                    span: DUMMY_SP,
+                    op: op!("!"),
+                    arg: Box::new(Expr::Ident(normal_completion.clone())),
+                })),
+            ],
+        });
+
+        let for_stmt = Stmt::For(ForStmt {
+            span,
+            init: Some(VarDeclOrExpr::VarDecl(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: vec![
+                    VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(iterator.clone()),
+                        init: Some(Box::new(Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            callee: helper!(async_iterator, "asyncIterator"),
+                            args: vec![right.as_arg()],
+                            type_args: Default::default(),
+                        }))),
+                        definite: false,
+                    },
+                    VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(step),
+                        init: None,
+                        definite: false,
+                    },
+                    VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(value),
+                        init: None,
+                        definite: false,
+                    },
+                ],
+            })),
+            test: Some(Box::new(test)),
+            update: Some(assign(&normal_completion, bool_lit(true))),
+            body: Box::new(Stmt::Block(body)),
+        });
+
+        let for_stmt = match label {
+            Some(label) => Stmt::Labeled(LabeledStmt {
+                span,
+                label,
+                body: Box::new(for_stmt),
+            }),
+            None => for_stmt,
+        };
+
+        let iterator_return = iterator.make_member(quote_ident!("return"));
+        let err = private_ident!("err");
+
+        // try {
+        //     if (!_iteratorNormalCompletion && _iterator.return != null) {
+        //         await _iterator.return();
+        //     }
+        // } finally {
+        //     if (_didIteratorError) {
+        //         throw _iteratorError;
+        //     }
+        // }
+        let finalizer = Stmt::Try(TryStmt {
+            span: DUMMY_SP,
+            block: BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![Stmt::If(IfStmt {
+                    span: DUMMY_SP,
+                    test: Box::new(Expr::Bin(BinExpr {
+                        span: DUMMY_SP,
+                        left: Box::new(Expr::Unary(UnaryExpr {
+                            span: DUMMY_SP,
+                            op: op!("!"),
+                            arg: Box::new(Expr::Ident(normal_completion)),
+                        })),
+                        op: op!("&&"),
+                        right: Box::new(
+                            iterator_return
+                                .clone()
+                                .make_bin(op!("!="), Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
+                        ),
+                    })),
+                    cons: Box::new(Stmt::Block(BlockStmt {
+                        span: DUMMY_SP,
+                        stmts: vec![await_expr(Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            callee: iterator_return.as_callee(),
+                            args: vec![],
+                            type_args: Default::default(),
+                        }))
+                        .into_stmt()],
+                    })),
+                    alt: None,
+                })],
+            },
+            handler: None,
+            finalizer: Some(BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![Stmt::If(IfStmt {
+                    span: DUMMY_SP,
+                    test: Box::new(Expr::Ident(did_error.clone())),
+                    cons: Box::new(Stmt::Block(BlockStmt {
+                        span: DUMMY_SP,
+                        stmts: vec![Stmt::Throw(ThrowStmt {
+                            span: DUMMY_SP,
+                            arg: Box::new(Expr::Ident(error.clone())),
+                        })],
+                    })),
+                    alt: None,
+                })],
+            }),
+        });
+
+        Stmt::Try(TryStmt {
+            span: DUMMY_SP,
+            block: BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![for_stmt],
+            },
+            handler: Some(CatchClause {
+                span: DUMMY_SP,
+                param: Some(Pat::Ident(err.clone())),
+                body: BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![
+                        assign(&did_error, bool_lit(true)).into_stmt(),
+                        assign(&error, Expr::Ident(err)).into_stmt(),
+                    ],
+                },
+            }),
+            finalizer: Some(BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![finalizer],
+            }),
+        })
+    }
+}
+
+#[fast_path(ShouldWork)]
+impl Fold for AsyncGeneratorFunctions {
+    noop_fold_type!();
+
+    fn fold_module_items(&mut self, n: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        self.fold_stmt_like(n)
+    }
+
+    fn fold_stmts(&mut self, n: Vec<Stmt>) -> Vec<Stmt> {
+        self.fold_stmt_like(n)
+    }
+
+    fn fold_stmt(&mut self, s: Stmt) -> Stmt {
+        match s {
+            Stmt::Labeled(LabeledStmt { span, label, body }) => match *body {
+                Stmt::ForOf(s) if s.await_token.is_some() => self.fold_for_await(Some(label), s),
+                body => Stmt::Labeled(LabeledStmt {
+                    span,
+                    label,
+                    body: Box::new(body.fold_with(self)),
+                }),
+            },
+            Stmt::ForOf(s) if s.await_token.is_some() => self.fold_for_await(None, s),
+            _ => s.fold_children_with(self),
+        }
+    }
+
+    /// Converts
+    ///
+    /// `async function* foo() {}`
+    ///
+    /// to
+    ///
+    /// `function foo() { return _wrapAsyncGenerator(function* ()
+    /// {}).apply(this, arguments); }`
+    ///
+    /// Parameters are kept in the outer function, as errors in parameters
+    /// should be thrown synchronously.
+    fn fold_function(&mut self, f: Function) -> Function {
+        let f = f.fold_children_with(self);
+        if !f.is_async || !f.is_generator || f.body.is_none() {
+            return f;
+        }
+
+        let mut super_folder = MethodFolder { vars: vec![] };
+        let body = f
+            .body
+            .fold_with(&mut AwaitToYield)
+            .fold_with(&mut super_folder);
+
+        let generator = Expr::Fn(FnExpr {
+            ident: None,
+            function: Function {
+                span: DUMMY_SP,
+                params: vec![],
+                decorators: vec![],
+                body,
+                is_generator: true,
+                is_async: false,
+                type_params: Default::default(),
+                return_type: Default::default(),
+            },
+        });
+
+        let mut stmts = vec![];
+        if !super_folder.vars.is_empty() {
+            stmts.push(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls: super_folder.vars,
+                declare: false,
+            })));
+        }
+        stmts.push(Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(Box::new(
+                Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: helper!(wrap_async_generator, "wrapAsyncGenerator"),
+                    args: vec![generator.as_arg()],
+                    type_args: Default::default(),
+                })
+                .apply(
+                    DUMMY_SP,
+                    Box::new(Expr::This(ThisExpr { span: DUMMY_SP })),
+                    vec![quote_ident!("arguments").as_arg()],
+                ),
+            )),
+        }));
+
+        Function {
+            is_async: false,
+            is_generator: false,
+            body: Some(BlockStmt {
+                span: DUMMY_SP,
+                stmts,
+            }),
+            ..f
+        }
+    }
+}
+
+/// Converts `await` and `yield*` in an async generator.
+struct AwaitToYield;
+
+impl Fold for AwaitToYield {
+    noop_fold_type!();
+
+    /// Don't recurse into other functions.
+    fn fold_function(&mut self, f: Function) -> Function {
+        f
+    }
+
+    /// Don't recurse into other functions.
+    fn fold_arrow_expr(&mut self, f: ArrowExpr) -> ArrowExpr {
+        f
+    }
+
+    fn fold_expr(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children_with(self);
+
+        match e {
+            // yield _awaitAsyncGenerator(arg)
+            Expr::Await(AwaitExpr { span, arg }) => Expr::Yield(YieldExpr {
+                span,
+                delegate: false,
+                arg: Some(Box::new(Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: helper!(await_async_generator, "awaitAsyncGenerator"),
+                    args: vec![arg.as_arg()],
+                    type_args: Default::default(),
+                }))),
+            }),
+
+            // yield* _asyncGeneratorDelegate(_asyncIterator(arg), _awaitAsyncGenerator)
+            Expr::Yield(YieldExpr {
+                span,
+                delegate: true,
+                arg: Some(arg),
+            }) => Expr::Yield(YieldExpr {
+                span,
+                delegate: true,
+                arg: Some(Box::new(Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: helper!(async_generator_delegate, "asyncGeneratorDelegate"),
+                    args: vec![
+                        Expr::Call(CallExpr {
+                            span: DUMMY_SP,
+                            callee: helper!(async_iterator, "asyncIterator"),
+                            args: vec![arg.as_arg()],
+                            type_args: Default::default(),
+                        })
+                        .as_arg(),
+                        helper_expr!(await_async_generator, "awaitAsyncGenerator").as_arg(),
+                    ],
+                    type_args: Default::default(),
+                }))),
+            }),
+
+            _ => e,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ShouldWork {
+    found: bool,
+}
+
+impl Visit for ShouldWork {
+    noop_visit_type!();
+
+    fn visit_function(&mut self, f: &Function, _: &dyn Node) {
+        if f.is_async && f.is_generator {
+            self.found = true;
+            return;
+        }
+        f.visit_children_with(self);
+    }
+
+    fn visit_for_of_stmt(&mut self, s: &ForOfStmt, _: &dyn Node) {
+        if s.await_token.is_some() {
+            self.found = true;
+            return;
+        }
+        s.visit_children_with(self);
+    }
+}
+
+impl Check for ShouldWork {
+    fn should_handle(&self) -> bool {
+        self.found
+    }
+}
@@ -0,0 +1,88 @@
+use crate::compat::regexp::{visit_terms_mut, Atom, Char, Class, ClassItem, Pattern};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
+
+/// Compile the `s` flag of regular expressions.
+///
+///# Example
+///## In
+///
+/// ```js
+/// /a.b/s;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// /a[\0-\uFFFF]b/;
+/// ```
+pub fn dotall_regex() -> impl 'static + Fold {
+    DotAllRegex
+}
+
+#[derive(Clone, Copy)]
+struct DotAllRegex;
+
+impl Fold for DotAllRegex {
+    noop_fold_type!();
+
+    fn fold_expr(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children_with(self);
+
+        match e {
+            Expr::Lit(Lit::Regex(Regex { span, exp, flags })) if flags.contains('s') => {
+                let mut pattern = match Pattern::parse(&exp, &flags) {
+                    Ok(pattern) => pattern,
+                    Err(..) => return Expr::Lit(Lit::Regex(Regex { span, exp, flags })),
+                };
+                let unicode = flags.contains('u');
+                let max = if unicode { 0x10ffff } else { 0xffff };
+
+                visit_terms_mut(&mut pattern.body, &mut |term| {
+                    if let Atom::Dot = term.atom {
+                        term.atom = Atom::Class(Class {
+                            negated: false,
+                            items: vec![ClassItem::Range(Char::new(0), Char::new(max))],
+                        });
+                    }
+                });
+
+                Expr::Lit(Lit::Regex(Regex {
+                    span,
+                    exp: pattern.print(unicode).into(),
+                    flags: flags.replace('s', "").into(),
+                }))
+            }
+            _ => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| DotAllRegex,
+        simple,
+        r#"var a = /a.b/s; var b = /./gis; var c = /[.](.)+/s;"#,
+        r#"var a = /a[\0-\uFFFF]b/; var b = /[\0-\uFFFF]/gi; var c = /[.]([\0-\uFFFF])+/;"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| DotAllRegex,
+        with_unicode_flag,
+        r#"var a = /^.$/su;"#,
+        r#"var a = /^[\0-\u{10FFFF}]$/u;"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| DotAllRegex,
+        ignore_without_flag,
+        r#"var a = /a.b/; var b = /\./gi;"#,
+        r#"var a = /a.b/; var b = /\./gi;"#
+    );
+}
@@ -0,0 +1,159 @@
+use crate::{
+    compat::regexp::{visit_terms_mut, Atom, Backref, Disjunction, GroupKind, Pattern},
+    util::ExprFactory,
+};
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
+
+/// Compile named capturing groups to numbered groups.
+///
+/// `.groups` of the match results and `$<name>` in replacement strings are
+/// supported by the `wrapRegExp` helper.
+///
+///# Example
+///## In
+///
+/// ```js
+/// /(?<year>\d{4})-\k<year>/;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// _wrapRegExp(/(\d{4})-\1/, { year: 1 });
+/// ```
+pub fn named_capturing_groups_regex() -> impl 'static + Fold {
+    NamedCapturingGroupsRegex
+}
+
+#[derive(Clone, Copy)]
+struct NamedCapturingGroupsRegex;
+
+impl Fold for NamedCapturingGroupsRegex {
+    noop_fold_type!();
+
+    fn fold_expr(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children_with(self);
+
+        match e {
+            Expr::Lit(Lit::Regex(Regex { span, exp, flags })) if exp.contains("(?<") => {
+                let mut pattern = match Pattern::parse(&exp, &flags) {
+                    Ok(pattern) => pattern,
+                    Err(..) => return Expr::Lit(Lit::Regex(Regex { span, exp, flags })),
+                };
+
+                let mut groups = vec![];
+                number_groups(&mut pattern.body, &mut 0, &mut groups);
+                if groups.is_empty() {
+                    return Expr::Lit(Lit::Regex(Regex { span, exp, flags }));
+                }
+
+                let mut unknown = false;
+                visit_terms_mut(&mut pattern.body, &mut |term| {
+                    if let Atom::Backref(Backref::Named(name)) = &term.atom {
+                        match groups.iter().find(|(n, _)| n == name) {
+                            Some(&(_, index)) => term.atom = Atom::Backref(Backref::Index(index)),
+                            None => unknown = true,
+                        }
+                    }
+                });
+                if unknown {
+                    return Expr::Lit(Lit::Regex(Regex { span, exp, flags }));
+                }
+
+                let regex = Expr::Lit(Lit::Regex(Regex {
+                    span: DUMMY_SP,
+                    exp: pattern.print(flags.contains('u')).into(),
+                    flags,
+                }));
+                let groups = Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: groups
+                        .into_iter()
+                        .map(|(name, index)| {
+                            PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                                key: PropName::Ident(Ident::new(name.into(), DUMMY_SP)),
+                                value: Box::new(Expr::Lit(Lit::Num(Number {
+                                    span: DUMMY_SP,
+                                    value: index as f64,
+                                }))),
+                            })))
+                        })
+                        .collect(),
+                });
+
+                Expr::Call(CallExpr {
+                    span,
+                    callee: helper!(wrap_reg_exp, "wrapRegExp"),
+                    args: vec![regex.as_arg(), groups.as_arg()],
+                    type_args: Default::default(),
+                })
+            }
+            _ => e,
+        }
+    }
+}
+
+/// Removes names of capturing groups and stores their indices in `groups`.
+fn number_groups(body: &mut Disjunction, count: &mut u32, groups: &mut Vec<(String, u32)>) {
+    for alt in body {
+        for term in alt {
+            if let Atom::Group { kind, body } = &mut term.atom {
+                if let GroupKind::Capture { name } = kind {
+                    *count += 1;
+                    if let Some(name) = name.take() {
+                        groups.push((name, *count));
+                    }
+                }
+                number_groups(body, count, groups);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| NamedCapturingGroupsRegex,
+        simple,
+        r#"var re = /(?<year>\d{4})-(?<month>\d{2})/u;"#,
+        r#"var re = _wrapRegExp(/(\d{4})-(\d{2})/u, { year: 1, month: 2 });"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| NamedCapturingGroupsRegex,
+        backreference,
+        r#"var re = /(a)(?:(?<x>b)|(c))\k<x>0\k<x>/;"#,
+        r#"var re = _wrapRegExp(/(a)(?:(b)|(c))(?:\2)0\2/, { x: 2 });"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| NamedCapturingGroupsRegex,
+        ignore_unnamed,
+        r#"var a = /(?<=a)(?<!b)(c)/; var b = /[(?<x>)]/;"#,
+        r#"var a = /(?<=a)(?<!b)(c)/; var b = /[(?<x>)]/;"#
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| NamedCapturingGroupsRegex,
+        exec_groups,
+        r#"
+var re = /(?<year>\d{4})-(?<month>\d{2})/;
+var result = re.exec("2020-11");
+expect(result.groups.year).toBe("2020");
+expect(result.groups.month).toBe("11");
+expect("2020-11".replace(re, "$<month>/$<year>")).toBe("11/2020");
+expect("2020-11".replace(re, function () {
+    var groups = arguments[arguments.length - 1];
+    return groups.month + "." + groups.year;
+})).toBe("11.2020");
+"#
+    );
+}
@@ -4,6 +4,7 @@ use crate::{
         alias_ident_for, alias_if_required, is_literal, var::VarCollector, ExprFactory, StmtLike,
     },
 };
+use serde::Deserialize;
 use std::{iter, mem};
 use swc_common::{chain, util::move_map::MoveMap, Mark, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
@@ -12,13 +13,32 @@ use swc_ecma_visit::{noop_fold_type, Fold, FoldWith, Node, Visit, VisitWith};
 
 /// `@babel/plugin-proposal-object-rest-spread`
 pub fn object_rest_spread() -> impl Fold {
-    chain!(ObjectRest, ObjectSpread)
+    object_rest_spread_with_config(Default::default())
 }
 
-struct ObjectRest;
+pub fn object_rest_spread_with_config(c: Config) -> impl Fold {
+    chain!(ObjectRest { c }, ObjectSpread { c })
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Use `_objectWithoutPropertiesLoose`, which ignores symbol keys.
+    #[serde(default)]
+    pub object_rest_no_symbols: bool,
+
+    /// Use `_extends` instead of `_objectSpread`.
+    #[serde(default)]
+    pub set_spread_properties: bool,
+}
+
+struct ObjectRest {
+    c: Config,
+}
 
 #[allow(clippy::vec_box)]
 struct RestFolder {
+    c: Config,
     /// Injected before the original statement.
     vars: Vec<VarDeclarator>,
     /// Variables which should be declared using `var`
@@ -428,6 +448,7 @@ impl ObjectRest {
 
         for stmt in stmts {
             let mut folder = RestFolder {
+                c: self.c,
                 vars: vec![],
                 mutable_vars: vec![],
                 exprs: vec![],
@@ -910,14 +931,18 @@ impl RestFolder {
                 span: DUMMY_SP,
                 left: PatOrExpr::Pat(last.arg),
                 op: op!("="),
-                right: Box::new(object_without_properties(obj, excluded_props)),
+                right: Box::new(object_without_properties(obj, excluded_props, self.c)),
             })));
         } else {
             // println!("Var: rest = objectWithoutProperties()",);
             self.push_var_if_not_empty(VarDeclarator {
                 span: DUMMY_SP,
                 name: *last.arg,
-                init: Some(Box::new(object_without_properties(obj, excluded_props))),
+                init: Some(Box::new(object_without_properties(
+                    obj,
+                    excluded_props,
+                    self.c,
+                ))),
                 definite: false,
             });
         }
@@ -931,7 +956,11 @@ impl RestFolder {
     }
 }
 
-fn object_without_properties(obj: Box<Expr>, excluded_props: Vec<Option<ExprOrSpread>>) -> Expr {
+fn object_without_properties(
+    obj: Box<Expr>,
+    excluded_props: Vec<Option<ExprOrSpread>>,
+    c: Config,
+) -> Expr {
     if excluded_props.is_empty() {
         return Expr::Call(CallExpr {
             span: DUMMY_SP,
@@ -967,7 +996,14 @@ fn object_without_properties(obj: Box<Expr>, excluded_props: Vec<Option<ExprOrSp
 
     Expr::Call(CallExpr {
         span: DUMMY_SP,
-        callee: helper!(object_without_properties, "objectWithoutProperties"),
+        callee: if c.object_rest_no_symbols {
+            helper!(
+                object_without_properties_loose,
+                "objectWithoutPropertiesLoose"
+            )
+        } else {
+            helper!(object_without_properties, "objectWithoutProperties")
+        },
         args: vec![
             obj.as_arg(),
             if is_literal(&excluded_props) {
@@ -1078,7 +1114,9 @@ fn simplify_pat(pat: Pat) -> Pat {
     pat.fold_with(&mut PatSimplifier)
 }
 
-struct ObjectSpread;
+struct ObjectSpread {
+    c: Config,
+}
 
 #[fast_path(SpreadVisitor)]
 impl Fold for ObjectSpread {
@@ -1137,7 +1175,11 @@ impl Fold for ObjectSpread {
 
                 Expr::Call(CallExpr {
                     span,
-                    callee: helper!(object_spread, "objectSpread"),
+                    callee: if self.c.set_spread_properties {
+                        helper!(extends, "extends")
+                    } else {
+                        helper!(object_spread, "objectSpread")
+                    },
                     args,
                     type_args: Default::default(),
                 })
@@ -0,0 +1,121 @@
+use crate::compat::regexp::{property_set, visit_terms_mut, Atom, Class, ClassItem, Pattern};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_fold_type, Fold, FoldWith};
+
+/// Compile unicode property escapes to character classes.
+///
+/// The `u` flag is preserved, so `unicode_regex` should be applied after this
+/// pass for es5 targets.
+///
+///# Example
+///## In
+///
+/// ```js
+/// /\p{ASCII_Hex_Digit}/u;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// /[0-9A-Fa-f]/u;
+/// ```
+pub fn unicode_property_regex() -> impl 'static + Fold {
+    UnicodePropertyRegex
+}
+
+#[derive(Clone, Copy)]
+struct UnicodePropertyRegex;
+
+impl Fold for UnicodePropertyRegex {
+    noop_fold_type!();
+
+    fn fold_expr(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children_with(self);
+
+        match e {
+            Expr::Lit(Lit::Regex(Regex { span, exp, flags }))
+                if flags.contains('u') && (exp.contains("\\p") || exp.contains("\\P")) =>
+            {
+                let mut pattern = match Pattern::parse(&exp, &flags) {
+                    Ok(pattern) => pattern,
+                    Err(..) => return Expr::Lit(Lit::Regex(Regex { span, exp, flags })),
+                };
+
+                let mut unknown = false;
+                visit_terms_mut(&mut pattern.body, &mut |term| match term.atom {
+                    Atom::Property { negated, ref name } => match property_set(negated, name) {
+                        Some(set) => term.atom = Atom::Class(Class::from_set(&set)),
+                        None => unknown = true,
+                    },
+                    Atom::Class(ref mut class) => {
+                        for item in std::mem::take(&mut class.items) {
+                            match item {
+                                ClassItem::Property { negated, ref name } => {
+                                    match property_set(negated, name) {
+                                        Some(set) => {
+                                            class.items.extend(Class::from_set(&set).items)
+                                        }
+                                        None => {
+                                            unknown = true;
+                                            class.items.push(item)
+                                        }
+                                    }
+                                }
+                                _ => class.items.push(item),
+                            }
+                        }
+                    }
+                    _ => {}
+                });
+
+                if unknown {
+                    return Expr::Lit(Lit::Regex(Regex { span, exp, flags }));
+                }
+
+                Expr::Lit(Lit::Regex(Regex {
+                    span,
+                    exp: pattern.print(true).into(),
+                    flags,
+                }))
+            }
+            _ => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodePropertyRegex,
+        binary_property,
+        r#"var a = /\p{ASCII_Hex_Digit}+/u;"#,
+        r#"var a = /[0-9A-Fa-f]+/u;"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodePropertyRegex,
+        in_class,
+        r#"var a = /[_\p{ASCII}\P{Any}]/u;"#,
+        r#"var a = /[_\0-\x7F]/u;"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodePropertyRegex,
+        negated,
+        r#"var a = /\P{ASCII}/u;"#,
+        r#"var a = /[\x80-\u{10FFFF}]/u;"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| UnicodePropertyRegex,
+        ignore,
+        r#"var a = /\p{NotAProperty}/u; var b = /\p{L}/;"#,
+        r#"var a = /\p{NotAProperty}/u; var b = /\p{L}/;"#
+    );
+}
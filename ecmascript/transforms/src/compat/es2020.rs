@@ -1,22 +1,41 @@
 pub use self::{
-    class_properties::{class_properties, typescript_class_properties},
+    class_properties::{
+        class_properties, class_properties_with_config, typescript_class_properties,
+    },
     export_namespace_from::export_namespace_from,
-    nullish_coalescing::nullish_coalescing,
-    opt_chaining::optional_chaining,
+    nullish_coalescing::{nullish_coalescing, nullish_coalescing_with_config},
+    opt_chaining::{optional_chaining, optional_chaining_with_config},
 };
+use serde::Deserialize;
 use swc_common::chain;
 use swc_ecma_visit::Fold;
 
-mod class_properties;
+pub mod class_properties;
 mod export_namespace_from;
-mod nullish_coalescing;
-mod opt_chaining;
+pub mod nullish_coalescing;
+pub mod opt_chaining;
 
 pub fn es2020() -> impl Fold {
+    es2020_with_config(Default::default())
+}
+
+pub fn es2020_with_config(c: Config) -> impl Fold {
     chain!(
-        nullish_coalescing(),
-        optional_chaining(),
-        class_properties(),
+        nullish_coalescing_with_config(c.nullish_coalescing),
+        optional_chaining_with_config(c.optional_chaining),
+        class_properties_with_config(c.class_properties),
         export_namespace_from(),
     )
 }
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(flatten)]
+    pub nullish_coalescing: nullish_coalescing::Config,
+
+    #[serde(flatten)]
+    pub optional_chaining: opt_chaining::Config,
+
+    #[serde(flatten)]
+    pub class_properties: class_properties::Config,
+}
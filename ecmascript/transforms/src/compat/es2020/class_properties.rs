@@ -12,7 +12,11 @@ use crate::{
         undefined, ExprFactory, ModuleItemLike, StmtLike,
     },
 };
-use std::{collections::HashSet, mem::take};
+use serde::Deserialize;
+use std::{
+    collections::HashSet,
+    mem::{replace, take},
+};
 use swc_atoms::JsWord;
 use swc_common::{util::move_map::MoveMap, Mark, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
@@ -32,7 +36,12 @@ mod used_name;
 ///
 /// We use custom helper to handle export defaul class
 pub fn class_properties() -> impl Fold {
+    class_properties_with_config(Default::default())
+}
+
+pub fn class_properties_with_config(c: Config) -> impl Fold {
     ClassProperties {
+        c,
         typescript: false,
         mark: Mark::root(),
     }
@@ -41,13 +50,23 @@ pub fn class_properties() -> impl Fold {
 /// Class properties pass for the typescript.
 pub fn typescript_class_properties() -> impl Fold {
     ClassProperties {
+        c: Default::default(),
         typescript: true,
         mark: Mark::root(),
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Assign public fields instead of using `_defineProperty`.
+    #[serde(default)]
+    pub set_public_class_fields: bool,
+}
+
 #[derive(Clone)]
 struct ClassProperties {
+    c: Config,
     typescript: bool,
     mark: Mark,
 }
@@ -312,12 +331,18 @@ impl ClassProperties {
         let mut used_names = vec![];
         let mut used_key_names = vec![];
         let mut statics = HashSet::default();
+        let mut private_methods = HashSet::default();
+        // Indices of lowered static blocks in `extra_stmts`.
+        let mut static_blocks = vec![];
 
         for member in class.body {
             match member {
-                ClassMember::PrivateMethod(..)
-                | ClassMember::Empty(..)
-                | ClassMember::TsIndexSignature(..) => members.push(member),
+                ClassMember::PrivateMethod(ref m) => {
+                    private_methods.insert(m.key.id.sym.clone());
+                    members.push(member)
+                }
+
+                ClassMember::Empty(..) | ClassMember::TsIndexSignature(..) => members.push(member),
 
                 ClassMember::Method(method) => {
                     // we handle computed key here to preserve the execution order
@@ -367,6 +392,12 @@ impl ClassProperties {
                         );
                     }
 
+                    // `b` of `this.b = value`
+                    let ident_key = match *prop.key {
+                        Expr::Ident(ref i) if !prop.computed => Some(i.clone()),
+                        _ => None,
+                    };
+
                     let key = if self.typescript {
                         // `b` in
                         //
@@ -426,6 +457,7 @@ impl ClassProperties {
                                 in_injected_define_property_call: false,
                                 in_nested_scope: false,
                                 this_alias_mark: None,
+                                constant_super_class: None,
                             })
                             .fold_with(&mut ThisInStaticFolder {
                                 ident: ident.clone(),
@@ -469,6 +501,29 @@ impl ClassProperties {
                                 right: value,
                             })));
                         }
+                    } else if self.c.set_public_class_fields {
+                        let obj = if prop.is_static {
+                            ident.clone().as_obj()
+                        } else {
+                            ThisExpr { span: DUMMY_SP }.as_obj()
+                        };
+                        let expr = Expr::Assign(AssignExpr {
+                            span: DUMMY_SP,
+                            left: PatOrExpr::Expr(Box::new(Expr::Member(MemberExpr {
+                                span: DUMMY_SP,
+                                obj,
+                                computed: ident_key.is_none(),
+                                prop: ident_key.map(Expr::Ident).map(Box::new).unwrap_or(key),
+                            }))),
+                            op: op!("="),
+                            right: value,
+                        });
+
+                        if prop.is_static {
+                            extra_stmts.push(expr.into_stmt());
+                        } else {
+                            constructor_exprs.push(Box::new(expr));
+                        }
                     } else {
                         let callee = helper!(define_property, "defineProperty");
 
@@ -590,6 +645,56 @@ impl ClassProperties {
                     })));
                 }
 
+                ClassMember::StaticBlock(block) => {
+                    // static { ... }
+                    //
+                    // is converted to
+                    //
+                    // (function () { ... })();
+                    //
+                    // which is invoked after the class is defined.
+                    let body = block
+                        .body
+                        .fold_with(&mut SuperFieldAccessFolder {
+                            class_name: &ident,
+                            vars: &mut vars,
+                            constructor_this_mark: None,
+                            is_static: true,
+                            folding_constructor: false,
+                            in_injected_define_property_call: false,
+                            in_nested_scope: false,
+                            this_alias_mark: None,
+                            constant_super_class: None,
+                        })
+                        .fold_with(&mut ThisInStaticFolder {
+                            ident: ident.clone(),
+                        });
+
+                    static_blocks.push(extra_stmts.len());
+                    extra_stmts.push(
+                        CallExpr {
+                            span: block.span,
+                            callee: FnExpr {
+                                ident: None,
+                                function: Function {
+                                    span: DUMMY_SP,
+                                    decorators: vec![],
+                                    is_async: false,
+                                    is_generator: false,
+                                    params: vec![],
+                                    body: Some(body),
+                                    type_params: Default::default(),
+                                    return_type: Default::default(),
+                                },
+                            }
+                            .as_callee(),
+                            args: vec![],
+                            type_args: Default::default(),
+                        }
+                        .into_stmt(),
+                    );
+                }
+
                 ClassMember::Constructor(mut c) => {
                     if self.typescript {
                         let store = |i: &Ident| {
@@ -665,11 +770,29 @@ impl ClassProperties {
         let members = members.fold_with(&mut FieldAccessFolder {
             mark: self.mark,
             statics: &statics,
+            private_methods: &private_methods,
             vars: vec![],
             class_name: &ident,
             in_assign_pat: false,
         });
 
+        // Private names used in static blocks can be resolved only after all
+        // members are processed.
+        for idx in static_blocks {
+            let stmt = replace(
+                &mut extra_stmts[idx],
+                Stmt::Empty(EmptyStmt { span: DUMMY_SP }),
+            );
+            extra_stmts[idx] = stmt.fold_with(&mut FieldAccessFolder {
+                mark: self.mark,
+                statics: &statics,
+                private_methods: &private_methods,
+                vars: vec![],
+                class_name: &ident,
+                in_assign_pat: false,
+            });
+        }
+
         (
             vars,
             Decl::Class(ClassDecl {
@@ -808,6 +931,10 @@ impl Visit for ShouldWork {
     fn visit_constructor(&mut self, _: &Constructor, _: &dyn Node) {
         self.found = true;
     }
+
+    fn visit_static_block(&mut self, _: &StaticBlock, _: &dyn Node) {
+        self.found = true;
+    }
 }
 
 impl Check for ShouldWork {
@@ -1,6 +1,6 @@
 use crate::{
     ext::{AsOptExpr, PatOrExprExt},
-    util::{alias_ident_for, alias_if_required, prepend, ExprFactory},
+    util::{alias_ident_for, alias_if_required, prepend, ExprFactory, HANDLER},
 };
 use std::{collections::HashSet, iter, mem};
 use swc_atoms::JsWord;
@@ -13,6 +13,9 @@ pub(super) struct FieldAccessFolder<'a> {
     pub class_name: &'a Ident,
     pub vars: Vec<VarDeclarator>,
     pub statics: &'a HashSet<JsWord>,
+    /// Private methods are not lowered, so brand checks for them can't be
+    /// either.
+    pub private_methods: &'a HashSet<JsWord>,
     pub in_assign_pat: bool,
 }
 
@@ -372,6 +375,54 @@ impl<'a> Fold for FieldAccessFolder<'a> {
                 }
             }
             Expr::Member(e) => self.fold_private_get(e, None).0,
+
+            // `#x in obj`
+            Expr::Bin(BinExpr {
+                span,
+                op: op!("in"),
+                left,
+                right,
+            }) if left.is_private_name() => {
+                let n = left.private_name().unwrap();
+                let right = right.fold_with(self);
+
+                if self.private_methods.contains(&n.id.sym) {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_err(
+                                span,
+                                "brand checks for private methods are not supported yet",
+                            )
+                            .emit()
+                    });
+
+                    Expr::Bin(BinExpr {
+                        span,
+                        op: op!("in"),
+                        left: Box::new(Expr::PrivateName(n)),
+                        right,
+                    })
+                } else if self.statics.contains(&n.id.sym) {
+                    Expr::Bin(BinExpr {
+                        span,
+                        left: right,
+                        op: op!("==="),
+                        right: Box::new(Expr::Ident(self.class_name.clone())),
+                    })
+                } else {
+                    let ident = Ident::new(
+                        format!("_{}", n.id.sym).into(),
+                        n.id.span.apply_mark(self.mark),
+                    );
+
+                    Expr::Call(CallExpr {
+                        span,
+                        callee: ident.make_member(quote_ident!("has")).as_callee(),
+                        args: vec![right.as_arg()],
+                        type_args: Default::default(),
+                    })
+                }
+            }
             _ => e.fold_children_with(self),
         }
     }
@@ -470,7 +521,19 @@ impl<'a> FieldAccessFolder<'a> {
                         .into(),
                         Some(Expr::This(this)),
                     ),
-                    _ => unimplemented!("destructuring set for object except this"),
+                    // `obj` is evaluated only once, as it's an assignment target.
+                    obj => (
+                        CallExpr {
+                            span: DUMMY_SP,
+                            callee: set,
+                            args: vec![obj.as_arg(), ident.as_arg()],
+
+                            type_args: Default::default(),
+                        }
+                        .make_member(quote_ident!("value"))
+                        .into(),
+                        None,
+                    ),
                 };
             }
 
@@ -2,6 +2,7 @@ use crate::{
     perf::Check,
     util::{alias_if_required, undefined, StmtLike},
 };
+use serde::Deserialize;
 use std::mem::replace;
 use swc_common::DUMMY_SP;
 use swc_ecma_ast::*;
@@ -12,11 +13,27 @@ use swc_ecma_visit::{noop_fold_type, noop_visit_type, Fold, FoldWith, Node, Visi
 mod tests;
 
 pub fn nullish_coalescing() -> impl Fold + 'static {
-    NullishCoalescing::default()
+    nullish_coalescing_with_config(Default::default())
+}
+
+pub fn nullish_coalescing_with_config(c: Config) -> impl Fold + 'static {
+    NullishCoalescing {
+        c,
+        ..Default::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Use `a != null` instead of `a !== null && a !== void 0`.
+    #[serde(default)]
+    pub no_document_all: bool,
 }
 
 #[derive(Debug, Default)]
 struct NullishCoalescing {
+    c: Config,
     vars: Vec<VarDeclarator>,
 }
 
@@ -52,12 +69,18 @@ impl Fold for NullishCoalescing {
 
     /// Prevents #1123
     fn fold_block_stmt(&mut self, s: BlockStmt) -> BlockStmt {
-        s.fold_children_with(&mut NullishCoalescing::default())
+        s.fold_children_with(&mut NullishCoalescing {
+            c: self.c,
+            ..Default::default()
+        })
     }
 
     /// Prevents #1123
     fn fold_switch_case(&mut self, s: SwitchCase) -> SwitchCase {
-        s.fold_children_with(&mut NullishCoalescing::default())
+        s.fold_children_with(&mut NullishCoalescing {
+            c: self.c,
+            ..Default::default()
+        })
     }
 
     fn fold_module_items(&mut self, n: Vec<ModuleItem>) -> Vec<ModuleItem> {
@@ -101,6 +124,21 @@ impl Fold for NullishCoalescing {
                     Expr::Ident(l.clone())
                 };
 
+                if self.c.no_document_all {
+                    // (ref = left) != null ? ref : right
+                    return Expr::Cond(CondExpr {
+                        span,
+                        test: Box::new(Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: Box::new(var_expr),
+                            op: op!("!="),
+                            right: Box::new(Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
+                        })),
+                        cons: Box::new(Expr::Ident(l)),
+                        alt: right,
+                    });
+                }
+
                 return Expr::Cond(CondExpr {
                     span,
                     test: Box::new(Expr::Bin(BinExpr {
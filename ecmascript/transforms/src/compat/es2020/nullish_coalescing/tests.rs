@@ -159,3 +159,24 @@ test!(
     console.log(submissions);
     "#
 );
+
+test!(
+    syntax(),
+    |_| nullish_coalescing_with_config(Config {
+        no_document_all: true,
+    }),
+    no_document_all,
+    r#"
+function foo(opts) {
+    var foo = opts.foo ?? "default";
+    var bar = baz ?? 1;
+}
+"#,
+    r#"
+function foo(opts) {
+    var _foo;
+    var foo = (_foo = opts.foo) != null ? _foo : "default";
+    var bar = baz != null ? baz : 1;
+}
+"#
+);
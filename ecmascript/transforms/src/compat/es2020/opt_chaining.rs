@@ -2,19 +2,36 @@ use crate::{
     perf::Check,
     util::{prepend, undefined, ExprFactory, StmtLike},
 };
+use serde::Deserialize;
 use std::{iter::once, mem};
-use swc_common::{Spanned, DUMMY_SP};
+use swc_common::{Span, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_transforms_macros::fast_path;
 use swc_ecma_utils::alias_if_required;
 use swc_ecma_visit::{noop_fold_type, Fold, FoldWith, Node, Visit};
 
 pub fn optional_chaining() -> impl Fold {
-    OptChaining::default()
+    optional_chaining_with_config(Default::default())
+}
+
+pub fn optional_chaining_with_config(c: Config) -> impl Fold {
+    OptChaining {
+        c,
+        ..Default::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Use `a == null` instead of `a === null || a === void 0`.
+    #[serde(default)]
+    pub no_document_all: bool,
 }
 
 #[derive(Default)]
 struct OptChaining {
+    c: Config,
     vars_without_init: Vec<VarDeclarator>,
     vars_with_init: Vec<VarDeclarator>,
 }
@@ -376,24 +393,7 @@ impl OptChaining {
                     }
                 };
 
-                let right = Box::new(Expr::Bin(BinExpr {
-                    span: DUMMY_SP,
-                    left: right,
-                    op: op!("==="),
-                    right: undefined(span),
-                }));
-
-                let test = Box::new(Expr::Bin(BinExpr {
-                    span,
-                    left: Box::new(Expr::Bin(BinExpr {
-                        span: obj_span,
-                        left,
-                        op: op!("==="),
-                        right: Box::new(Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
-                    })),
-                    op: op!("||"),
-                    right,
-                }));
+                let test = self.nullish_test(span, obj_span, left, right);
 
                 validate!(CondExpr {
                     span,
@@ -484,22 +484,7 @@ impl OptChaining {
                     }
                 };
 
-                let test = Box::new(Expr::Bin(BinExpr {
-                    span,
-                    left: Box::new(Expr::Bin(BinExpr {
-                        span: DUMMY_SP,
-                        left,
-                        op: op!("==="),
-                        right: Box::new(Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
-                    })),
-                    op: op!("||"),
-                    right: Box::new(Expr::Bin(BinExpr {
-                        span: DUMMY_SP,
-                        left: right,
-                        op: op!("==="),
-                        right: undefined(span),
-                    })),
-                }));
+                let test = self.nullish_test(span, DUMMY_SP, left, right);
 
                 validate!(CondExpr {
                     span: DUMMY_SP,
@@ -511,6 +496,43 @@ impl OptChaining {
             _ => unreachable!("TsOptChain.expr = {:?}", e.expr),
         }
     }
+
+    /// Creates `left === null || right === void 0`, where `right` is an alias
+    /// of `left`.
+    fn nullish_test(
+        &self,
+        span: Span,
+        left_span: Span,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    ) -> Box<Expr> {
+        if self.c.no_document_all {
+            // left == null
+            return Box::new(Expr::Bin(BinExpr {
+                span,
+                left,
+                op: op!("=="),
+                right: Box::new(Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
+            }));
+        }
+
+        Box::new(Expr::Bin(BinExpr {
+            span,
+            left: Box::new(Expr::Bin(BinExpr {
+                span: left_span,
+                left,
+                op: op!("==="),
+                right: Box::new(Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))),
+            })),
+            op: op!("||"),
+            right: Box::new(Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                left: right,
+                op: op!("==="),
+                right: undefined(span),
+            })),
+        }))
+    }
 }
 #[derive(Default)]
 struct ShouldWork {
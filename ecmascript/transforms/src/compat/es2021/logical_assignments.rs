@@ -0,0 +1,239 @@
+use crate::{
+    perf::Check,
+    util::{alias_ident_for, StmtLike},
+};
+use std::mem::take;
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_transforms_macros::fast_path;
+use swc_ecma_visit::{noop_fold_type, noop_visit_type, Fold, FoldWith, Node, Visit, VisitWith};
+
+/// Lowers `a ||= b`, `a &&= b` and `a ??= b`.
+///
+/// `a.b ||= c` becomes `(_a = a).b || (_a.b = c)`. Objects and computed keys
+/// of member expressions are evaluated only once.
+///
+/// `a ??= b` becomes `a ?? (a = b)`, so `nullish_coalescing` should be applied
+/// after this pass if required.
+pub fn logical_assignments() -> impl Fold + 'static {
+    LogicalAssignments::default()
+}
+
+#[derive(Debug, Default)]
+struct LogicalAssignments {
+    vars: Vec<VarDeclarator>,
+}
+
+impl LogicalAssignments {
+    fn fold_stmt_like<T>(&mut self, stmts: Vec<T>) -> Vec<T>
+    where
+        T: FoldWith<Self> + StmtLike,
+    {
+        let mut buf = Vec::with_capacity(stmts.len() + 2);
+
+        for stmt in stmts {
+            let stmt = stmt.fold_with(self);
+
+            if !self.vars.is_empty() {
+                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    decls: take(&mut self.vars),
+                    declare: false,
+                }))));
+            }
+
+            buf.push(stmt);
+        }
+
+        buf
+    }
+
+    /// Returns `(_a = a, _a)` if `e` should be evaluated only once.
+    fn memorize(&mut self, e: Box<Expr>, name: &str) -> (Box<Expr>, Box<Expr>) {
+        match *e {
+            Expr::Ident(..) | Expr::This(..) | Expr::Lit(..) => (e.clone(), e),
+            _ => {
+                let alias = alias_ident_for(&e, name);
+                self.vars.push(VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(alias.clone()),
+                    init: None,
+                    definite: false,
+                });
+
+                (
+                    Box::new(Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        op: op!("="),
+                        left: PatOrExpr::Pat(Box::new(Pat::Ident(alias.clone()))),
+                        right: e,
+                    })),
+                    Box::new(Expr::Ident(alias)),
+                )
+            }
+        }
+    }
+
+    /// Returns an expression to read the target and an expression to assign
+    /// to it.
+    fn split_target(&mut self, target: Expr) -> Result<(Expr, Expr), Expr> {
+        match target {
+            Expr::Ident(..) => Ok((target.clone(), target)),
+            Expr::Member(MemberExpr {
+                span,
+                obj,
+                prop,
+                computed,
+            }) => {
+                let (get_obj, set_obj) = match obj {
+                    ExprOrSuper::Super(..) => (obj.clone(), obj),
+                    ExprOrSuper::Expr(obj) => {
+                        let (get, set) = self.memorize(obj, "_ref");
+                        (ExprOrSuper::Expr(get), ExprOrSuper::Expr(set))
+                    }
+                };
+                let (get_prop, set_prop) = if computed {
+                    self.memorize(prop, "_prop")
+                } else {
+                    (prop.clone(), prop)
+                };
+
+                Ok((
+                    Expr::Member(MemberExpr {
+                        span,
+                        obj: get_obj,
+                        prop: get_prop,
+                        computed,
+                    }),
+                    Expr::Member(MemberExpr {
+                        span,
+                        obj: set_obj,
+                        prop: set_prop,
+                        computed,
+                    }),
+                ))
+            }
+            _ => Err(target),
+        }
+    }
+}
+
+#[fast_path(ShouldWork)]
+impl Fold for LogicalAssignments {
+    noop_fold_type!();
+
+    /// Prevents #1123
+    fn fold_block_stmt(&mut self, s: BlockStmt) -> BlockStmt {
+        s.fold_children_with(&mut LogicalAssignments::default())
+    }
+
+    /// Prevents #1123
+    fn fold_switch_case(&mut self, s: SwitchCase) -> SwitchCase {
+        s.fold_children_with(&mut LogicalAssignments::default())
+    }
+
+    fn fold_module_items(&mut self, n: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        self.fold_stmt_like(n)
+    }
+
+    fn fold_stmts(&mut self, n: Vec<Stmt>) -> Vec<Stmt> {
+        self.fold_stmt_like(n)
+    }
+
+    fn fold_expr(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children_with(self);
+
+        let AssignExpr {
+            span,
+            op,
+            left,
+            right,
+        } = match e {
+            Expr::Assign(e) => e,
+            _ => return e,
+        };
+
+        let bin_op = match op {
+            op!("||=") => op!("||"),
+            op!("&&=") => op!("&&"),
+            op!("??=") => op!("??"),
+            _ => {
+                return Expr::Assign(AssignExpr {
+                    span,
+                    op,
+                    left,
+                    right,
+                })
+            }
+        };
+
+        let target = match left {
+            PatOrExpr::Expr(e) => *e,
+            PatOrExpr::Pat(pat) => match *pat {
+                Pat::Ident(i) => Expr::Ident(i),
+                Pat::Expr(e) => *e,
+                pat => {
+                    return Expr::Assign(AssignExpr {
+                        span,
+                        op,
+                        left: PatOrExpr::Pat(Box::new(pat)),
+                        right,
+                    })
+                }
+            },
+        };
+
+        // The parser rejects other targets.
+        let (get, set) = match self.split_target(target) {
+            Ok(v) => v,
+            Err(target) => {
+                return Expr::Assign(AssignExpr {
+                    span,
+                    op,
+                    left: PatOrExpr::Expr(Box::new(target)),
+                    right,
+                })
+            }
+        };
+
+        let set = match set {
+            Expr::Ident(i) => PatOrExpr::Pat(Box::new(Pat::Ident(i))),
+            _ => PatOrExpr::Expr(Box::new(set)),
+        };
+
+        Expr::Bin(BinExpr {
+            span,
+            left: Box::new(get),
+            op: bin_op,
+            right: Box::new(Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                op: op!("="),
+                left: set,
+                right,
+            })),
+        })
+    }
+}
+
+#[derive(Default)]
+struct ShouldWork {
+    found: bool,
+}
+
+impl Visit for ShouldWork {
+    noop_visit_type!();
+
+    fn visit_assign_expr(&mut self, e: &AssignExpr, _: &dyn Node) {
+        match e.op {
+            op!("||=") | op!("&&=") | op!("??=") => self.found = true,
+            _ => e.visit_children_with(self),
+        }
+    }
+}
+
+impl Check for ShouldWork {
+    fn should_handle(&self) -> bool {
+        self.found
+    }
+}
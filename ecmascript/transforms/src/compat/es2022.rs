@@ -0,0 +1,20 @@
+pub use crate::compat::es2020::class_properties;
+use serde::Deserialize;
+use swc_ecma_visit::Fold;
+
+/// Class static blocks and private brand checks (`#x in obj`) are lowered by
+/// the `class_properties` pass, as they depend on the lowering of private
+/// fields.
+pub fn es2022() -> impl Fold {
+    es2022_with_config(Default::default())
+}
+
+pub fn es2022_with_config(c: Config) -> impl Fold {
+    class_properties::class_properties_with_config(c.class_properties)
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(flatten)]
+    pub class_properties: class_properties::Config,
+}
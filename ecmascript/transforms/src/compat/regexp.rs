@@ -0,0 +1,831 @@
+//! A parser and a printer for regular expression patterns, shared by the
+//! passes lowering regex syntax.
+//!
+//! Parts of a pattern which are not modified are printed as they were written.
+
+pub(crate) use self::char_set::CharSet;
+use std::fmt::Write;
+
+mod char_set;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Pattern {
+    pub body: Disjunction,
+}
+
+pub(crate) type Disjunction = Vec<Alternative>;
+
+pub(crate) type Alternative = Vec<Term>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Term {
+    pub atom: Atom,
+    /// Source text of the quantifier, like `+?` or `{1,3}`.
+    pub quantifier: Option<String>,
+}
+
+impl From<Atom> for Term {
+    fn from(atom: Atom) -> Self {
+        Term {
+            atom,
+            quantifier: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Atom {
+    Char(Char),
+    /// `.`
+    Dot,
+    /// `^`, `$`, `\b` or `\B`
+    Assertion(&'static str),
+    /// `\d`, `\D`, `\s`, `\S`, `\w` or `\W`
+    Builtin(char),
+    /// `\p{name}` or `\P{name}`
+    Property {
+        negated: bool,
+        name: String,
+    },
+    Class(Class),
+    Group {
+        kind: GroupKind,
+        body: Disjunction,
+    },
+    Backref(Backref),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Char {
+    pub value: u32,
+    /// Source text. [None] for generated characters.
+    pub raw: Option<String>,
+}
+
+impl Char {
+    pub fn new(value: u32) -> Self {
+        Char { value, raw: None }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Class {
+    pub negated: bool,
+    pub items: Vec<ClassItem>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ClassItem {
+    Char(Char),
+    Range(Char, Char),
+    Builtin(char),
+    Property { negated: bool, name: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum GroupKind {
+    Capture {
+        name: Option<String>,
+    },
+    /// `(?:`
+    NonCapture,
+    /// `(?=` or `(?!`
+    Lookahead {
+        negated: bool,
+    },
+    /// `(?<=` or `(?<!`
+    Lookbehind {
+        negated: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Backref {
+    Index(u32),
+    Named(String),
+}
+
+impl Class {
+    /// Returns the code points matched by the class, or [None] if it contains
+    /// an unknown property.
+    pub fn to_set(&self) -> Option<CharSet> {
+        let mut set = CharSet::default();
+        for item in &self.items {
+            match item {
+                ClassItem::Char(c) => set.add(c.value, c.value),
+                ClassItem::Range(start, end) => set.add(start.value, end.value),
+                ClassItem::Builtin(c) => set.union(&CharSet::builtin(*c)),
+                ClassItem::Property { negated, name } => set.union(&property_set(*negated, name)?),
+            }
+        }
+
+        if self.negated {
+            Some(set.complement())
+        } else {
+            Some(set)
+        }
+    }
+
+    pub fn from_set(set: &CharSet) -> Self {
+        Class {
+            negated: false,
+            items: set
+                .ranges()
+                .iter()
+                .map(|&(start, end)| {
+                    if start == end {
+                        ClassItem::Char(Char::new(start))
+                    } else {
+                        ClassItem::Range(Char::new(start), Char::new(end))
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+pub(crate) fn property_set(negated: bool, name: &str) -> Option<CharSet> {
+    let set = CharSet::property(name)?;
+    if negated {
+        Some(set.complement())
+    } else {
+        Some(set)
+    }
+}
+
+/// Calls `op` for all terms in `body`, including terms in groups.
+///
+/// Terms in a group are visited before the group itself.
+pub(crate) fn visit_terms_mut<F>(body: &mut Disjunction, op: &mut F)
+where
+    F: FnMut(&mut Term),
+{
+    for alt in body {
+        for term in alt {
+            if let Atom::Group { body, .. } = &mut term.atom {
+                visit_terms_mut(body, op);
+            }
+            op(term);
+        }
+    }
+}
+
+pub(crate) type ParseResult<T> = Result<T, String>;
+
+impl Pattern {
+    pub fn parse(src: &str, flags: &str) -> ParseResult<Self> {
+        let unicode = flags.contains('u');
+        let mut p = Parser {
+            chars: src.chars().collect(),
+            pos: 0,
+            unicode,
+            named_groups: unicode,
+            has_named_group: false,
+        };
+        let pattern = p.parse_pattern()?;
+
+        // `\k` is an identity escape if there's no named group.
+        if p.has_named_group && !p.named_groups {
+            p.pos = 0;
+            p.named_groups = true;
+            return p.parse_pattern();
+        }
+
+        Ok(pattern)
+    }
+
+    /// `unicode` should be true if the pattern is printed for a regex with the
+    /// `u` flag.
+    pub fn print(&self, unicode: bool) -> String {
+        let mut buf = String::new();
+        print_disjunction(&mut buf, &self.body, unicode);
+        buf
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    unicode: bool,
+    /// True if `\k<name>` is a backreference.
+    named_groups: bool,
+    has_named_group: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        let len = s.chars().count();
+        if self.chars[self.pos..]
+            .iter()
+            .take(len)
+            .copied()
+            .eq(s.chars())
+        {
+            self.pos += len;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next(&mut self) -> ParseResult<char> {
+        let c = self.peek().ok_or("unexpected end of pattern")?;
+        self.pos += 1;
+        Ok(c)
+    }
+
+    fn raw_since(&self, start: usize) -> String {
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    fn parse_pattern(&mut self) -> ParseResult<Pattern> {
+        let body = self.parse_disjunction()?;
+        if self.pos != self.chars.len() {
+            return Err("unmatched ')'".into());
+        }
+        Ok(Pattern { body })
+    }
+
+    fn parse_disjunction(&mut self) -> ParseResult<Disjunction> {
+        let mut body = vec![self.parse_alternative()?];
+        while self.eat('|') {
+            body.push(self.parse_alternative()?);
+        }
+        Ok(body)
+    }
+
+    fn parse_alternative(&mut self) -> ParseResult<Alternative> {
+        let mut terms = vec![];
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.parse_atom()?;
+            let quantifier = match atom {
+                Atom::Assertion(..) => None,
+                _ => self.parse_quantifier()?,
+            };
+            terms.push(Term { atom, quantifier });
+        }
+        Ok(terms)
+    }
+
+    fn parse_quantifier(&mut self) -> ParseResult<Option<String>> {
+        let start = self.pos;
+        match self.peek() {
+            Some('*') | Some('+') | Some('?') => self.pos += 1,
+            Some('{') => {
+                if !self.eat_braced_quantifier() {
+                    self.pos = start;
+                    if self.unicode {
+                        return Err("incomplete quantifier".into());
+                    }
+                    return Ok(None);
+                }
+            }
+            _ => return Ok(None),
+        }
+        self.eat('?');
+
+        Ok(Some(self.raw_since(start)))
+    }
+
+    /// Eats `{n}`, `{n,}` or `{n,m}`.
+    fn eat_braced_quantifier(&mut self) -> bool {
+        self.pos += 1;
+        if !self.eat_digits() {
+            return false;
+        }
+        if self.eat(',') {
+            self.eat_digits();
+        }
+        self.eat('}')
+    }
+
+    fn eat_digits(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek().map_or(false, |c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.pos != start
+    }
+
+    fn parse_atom(&mut self) -> ParseResult<Atom> {
+        let start = self.pos;
+        let c = self.next()?;
+        Ok(match c {
+            '^' => Atom::Assertion("^"),
+            '$' => Atom::Assertion("$"),
+            '.' => Atom::Dot,
+            '(' => self.parse_group()?,
+            '[' => Atom::Class(self.parse_class()?),
+            '\\' => self.parse_atom_escape(start)?,
+            '*' | '+' | '?' => return Err("nothing to repeat".into()),
+            '{' if self.unicode => return Err("lone quantifier brackets".into()),
+            ']' | '}' if self.unicode => return Err("lone quantifier brackets".into()),
+            _ => Atom::Char(Char {
+                value: c as u32,
+                raw: Some(c.to_string()),
+            }),
+        })
+    }
+
+    fn parse_group(&mut self) -> ParseResult<Atom> {
+        let kind = if self.eat_str("?:") {
+            GroupKind::NonCapture
+        } else if self.eat_str("?=") {
+            GroupKind::Lookahead { negated: false }
+        } else if self.eat_str("?!") {
+            GroupKind::Lookahead { negated: true }
+        } else if self.eat_str("?<=") {
+            GroupKind::Lookbehind { negated: false }
+        } else if self.eat_str("?<!") {
+            GroupKind::Lookbehind { negated: true }
+        } else if self.eat_str("?<") {
+            self.has_named_group = true;
+            GroupKind::Capture {
+                name: Some(self.parse_group_name()?),
+            }
+        } else if self.peek() == Some('?') {
+            return Err("invalid group".into());
+        } else {
+            GroupKind::Capture { name: None }
+        };
+
+        let body = self.parse_disjunction()?;
+        if !self.eat(')') {
+            return Err("unterminated group".into());
+        }
+
+        Ok(Atom::Group { kind, body })
+    }
+
+    /// Parses `name>`.
+    fn parse_group_name(&mut self) -> ParseResult<String> {
+        let mut name = String::new();
+        loop {
+            match self.next()? {
+                '>' => break,
+                c if c == '$' || c == '_' || c.is_alphanumeric() => name.push(c),
+                _ => return Err("invalid capture group name".into()),
+            }
+        }
+        if name.is_empty() {
+            return Err("empty capture group name".into());
+        }
+        Ok(name)
+    }
+
+    /// Parses an escape after `\`, outside of a character class.
+    fn parse_atom_escape(&mut self, start: usize) -> ParseResult<Atom> {
+        let c = self.next()?;
+        Ok(match c {
+            'b' => Atom::Assertion("\\b"),
+            'B' => Atom::Assertion("\\B"),
+            '1'..='9' => {
+                self.eat_digits();
+                let index = self.chars[start + 1..self.pos]
+                    .iter()
+                    .collect::<String>()
+                    .parse()
+                    .map_err(|_| "invalid backreference")?;
+                Atom::Backref(Backref::Index(index))
+            }
+            'k' if self.named_groups => {
+                if !self.eat('<') {
+                    return Err("invalid named reference".into());
+                }
+                Atom::Backref(Backref::Named(self.parse_group_name()?))
+            }
+            'd' | 'D' | 's' | 'S' | 'w' | 'W' => Atom::Builtin(c),
+            'p' | 'P' if self.unicode => Atom::Property {
+                negated: c == 'P',
+                name: self.parse_property_name()?,
+            },
+            _ => Atom::Char(self.parse_char_escape(start, c)?),
+        })
+    }
+
+    /// Parses `{name}` of `\p{name}`.
+    fn parse_property_name(&mut self) -> ParseResult<String> {
+        if !self.eat('{') {
+            return Err("invalid property name".into());
+        }
+        let mut name = String::new();
+        loop {
+            match self.next()? {
+                '}' => break,
+                c if c == '_' || c == '=' || c.is_ascii_alphanumeric() => name.push(c),
+                _ => return Err("invalid property name".into()),
+            }
+        }
+        Ok(name)
+    }
+
+    /// Parses a character escape. `c` is the character after `\`.
+    fn parse_char_escape(&mut self, start: usize, c: char) -> ParseResult<Char> {
+        let value = match c {
+            't' => 0x09,
+            'n' => 0x0a,
+            'v' => 0x0b,
+            'f' => 0x0c,
+            'r' => 0x0d,
+            'c' => match self.peek() {
+                Some(l) if l.is_ascii_alphabetic() => {
+                    self.pos += 1;
+                    l as u32 % 32
+                }
+                _ if self.unicode => return Err("invalid unicode escape".into()),
+                // `\c` is a backslash followed by `c`.
+                _ => {
+                    self.pos -= 1;
+                    return Ok(Char {
+                        value: '\\' as u32,
+                        raw: Some("\\".into()),
+                    });
+                }
+            },
+            '0' if !self.peek().map_or(false, |c| c.is_ascii_digit()) => 0,
+            '0'..='7' if !self.unicode => {
+                // Legacy octal escape
+                let mut value = c as u32 - '0' as u32;
+                while let Some(d) = self.peek().and_then(|c| c.to_digit(8)) {
+                    if value * 8 + d > 0o377 {
+                        break;
+                    }
+                    value = value * 8 + d;
+                    self.pos += 1;
+                }
+                value
+            }
+            'x' => match self.eat_hex(2) {
+                Some(v) => v,
+                None if self.unicode => return Err("invalid escape".into()),
+                None => 'x' as u32,
+            },
+            'u' => match self.parse_unicode_escape()? {
+                Some(v) => v,
+                None if self.unicode => return Err("invalid unicode escape".into()),
+                None => 'u' as u32,
+            },
+            _ => {
+                if self.unicode && !is_syntax_char(c) && c != '/' && c != '-' {
+                    return Err("invalid escape".into());
+                }
+                c as u32
+            }
+        };
+
+        Ok(Char {
+            value,
+            raw: Some(self.raw_since(start)),
+        })
+    }
+
+    /// Parses the part after `\u`.
+    fn parse_unicode_escape(&mut self) -> ParseResult<Option<u32>> {
+        if self.unicode && self.peek() == Some('{') {
+            let start = self.pos;
+            self.pos += 1;
+            let mut value = 0u32;
+            let mut empty = true;
+            while let Some(d) = self.peek().and_then(|c| c.to_digit(16)) {
+                value = value.saturating_mul(16).saturating_add(d);
+                empty = false;
+                self.pos += 1;
+            }
+            if empty || !self.eat('}') || value > char_set::MAX_CODE_POINT {
+                self.pos = start;
+                return Ok(None);
+            }
+            return Ok(Some(value));
+        }
+
+        let value = match self.eat_hex(4) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        // A surrogate pair is a single code point in unicode mode.
+        if self.unicode && (0xd800..=0xdbff).contains(&value) {
+            let start = self.pos;
+            if self.eat_str("\\u") {
+                if let Some(low) = self.eat_hex(4) {
+                    if (0xdc00..=0xdfff).contains(&low) {
+                        return Ok(Some(0x10000 + ((value - 0xd800) << 10) + (low - 0xdc00)));
+                    }
+                }
+            }
+            self.pos = start;
+        }
+
+        Ok(Some(value))
+    }
+
+    fn eat_hex(&mut self, len: usize) -> Option<u32> {
+        let mut value = 0;
+        for i in 0..len {
+            value = value * 16 + self.peek_at(i)?.to_digit(16)?;
+        }
+        self.pos += len;
+        Some(value)
+    }
+
+    /// Parses a character class after `[`.
+    fn parse_class(&mut self) -> ParseResult<Class> {
+        let negated = self.eat('^');
+        let mut items = vec![];
+        loop {
+            if self.eat(']') {
+                break;
+            }
+            let item = self.parse_class_atom()?;
+            if self.peek() == Some('-') && self.peek_at(1).map_or(false, |c| c != ']') {
+                let dash_pos = self.pos;
+                self.pos += 1;
+                let end = self.parse_class_atom()?;
+                match (item, end) {
+                    (ClassItem::Char(start), ClassItem::Char(end)) => {
+                        if start.value > end.value {
+                            return Err("range out of order in character class".into());
+                        }
+                        items.push(ClassItem::Range(start, end));
+                    }
+                    _ if self.unicode => return Err("invalid character class".into()),
+                    (item, end) => {
+                        items.push(item);
+                        items.push(ClassItem::Char(Char {
+                            value: '-' as u32,
+                            raw: Some(self.chars[dash_pos].to_string()),
+                        }));
+                        items.push(end);
+                    }
+                }
+            } else {
+                items.push(item);
+            }
+        }
+
+        Ok(Class { negated, items })
+    }
+
+    fn parse_class_atom(&mut self) -> ParseResult<ClassItem> {
+        let start = self.pos;
+        let c = self.next().map_err(|_| "unterminated character class")?;
+        if c != '\\' {
+            return Ok(ClassItem::Char(Char {
+                value: c as u32,
+                raw: Some(c.to_string()),
+            }));
+        }
+
+        let c = self.next()?;
+        Ok(match c {
+            'b' => ClassItem::Char(Char {
+                value: 0x08,
+                raw: Some(self.raw_since(start)),
+            }),
+            'd' | 'D' | 's' | 'S' | 'w' | 'W' => ClassItem::Builtin(c),
+            'p' | 'P' if self.unicode => ClassItem::Property {
+                negated: c == 'P',
+                name: self.parse_property_name()?,
+            },
+            _ => ClassItem::Char(self.parse_char_escape(start, c)?),
+        })
+    }
+}
+
+fn is_syntax_char(c: char) -> bool {
+    matches!(
+        c,
+        '^' | '$' | '\\' | '.' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|'
+    )
+}
+
+fn print_disjunction(buf: &mut String, body: &[Alternative], unicode: bool) {
+    for (i, alt) in body.iter().enumerate() {
+        if i != 0 {
+            buf.push('|');
+        }
+        print_alternative(buf, alt, unicode);
+    }
+}
+
+fn print_alternative(buf: &mut String, terms: &[Term], unicode: bool) {
+    for (i, term) in terms.iter().enumerate() {
+        // `\1` followed by `0` would be `\10`.
+        let wrap = match (&term.atom, &term.quantifier, terms.get(i + 1)) {
+            (Atom::Backref(..), None, Some(next)) => {
+                let mut next_buf = String::new();
+                print_atom(&mut next_buf, &next.atom, unicode);
+                next_buf.starts_with(|c: char| c.is_ascii_digit())
+            }
+            _ => false,
+        };
+
+        if wrap {
+            buf.push_str("(?:");
+        }
+        print_atom(buf, &term.atom, unicode);
+        if wrap {
+            buf.push(')');
+        }
+        if let Some(q) = &term.quantifier {
+            buf.push_str(q);
+        }
+    }
+}
+
+fn print_atom(buf: &mut String, atom: &Atom, unicode: bool) {
+    match atom {
+        Atom::Char(c) => print_char(buf, c, false, unicode),
+        Atom::Dot => buf.push('.'),
+        Atom::Assertion(s) => buf.push_str(s),
+        Atom::Builtin(c) => {
+            buf.push('\\');
+            buf.push(*c);
+        }
+        Atom::Property { negated, name } => {
+            let _ = write!(buf, "\\{}{{{}}}", if *negated { 'P' } else { 'p' }, name);
+        }
+        Atom::Class(class) => print_class(buf, class, unicode),
+        Atom::Group { kind, body } => {
+            buf.push('(');
+            match kind {
+                GroupKind::Capture { name: None } => {}
+                GroupKind::Capture { name: Some(name) } => {
+                    let _ = write!(buf, "?<{}>", name);
+                }
+                GroupKind::NonCapture => buf.push_str("?:"),
+                GroupKind::Lookahead { negated } => {
+                    buf.push_str(if *negated { "?!" } else { "?=" })
+                }
+                GroupKind::Lookbehind { negated } => {
+                    buf.push_str(if *negated { "?<!" } else { "?<=" })
+                }
+            }
+            print_disjunction(buf, body, unicode);
+            buf.push(')');
+        }
+        Atom::Backref(Backref::Index(index)) => {
+            let _ = write!(buf, "\\{}", index);
+        }
+        Atom::Backref(Backref::Named(name)) => {
+            let _ = write!(buf, "\\k<{}>", name);
+        }
+    }
+}
+
+fn print_class(buf: &mut String, class: &Class, unicode: bool) {
+    buf.push('[');
+    if class.negated {
+        buf.push('^');
+    }
+    for item in &class.items {
+        match item {
+            ClassItem::Char(c) => print_char(buf, c, true, unicode),
+            ClassItem::Range(start, end) => {
+                if start.raw.is_none() && start.value == 0 {
+                    buf.push_str("\\0");
+                } else {
+                    print_char(buf, start, true, unicode);
+                }
+                buf.push('-');
+                print_char(buf, end, true, unicode);
+            }
+            ClassItem::Builtin(c) => {
+                buf.push('\\');
+                buf.push(*c);
+            }
+            ClassItem::Property { negated, name } => {
+                let _ = write!(buf, "\\{}{{{}}}", if *negated { 'P' } else { 'p' }, name);
+            }
+        }
+    }
+    buf.push(']');
+}
+
+fn print_char(buf: &mut String, c: &Char, in_class: bool, unicode: bool) {
+    if let Some(raw) = &c.raw {
+        buf.push_str(raw);
+        return;
+    }
+
+    let value = c.value;
+    match std::char::from_u32(value) {
+        Some(ch)
+            if in_class && (ch == '\\' || ch == ']' || ch == '[' || ch == '^' || ch == '-') =>
+        {
+            buf.push('\\');
+            buf.push(ch);
+        }
+        Some(ch) if !in_class && (is_syntax_char(ch) || ch == '/') => {
+            buf.push('\\');
+            buf.push(ch);
+        }
+        Some('/') => buf.push_str("\\/"),
+        Some(ch) if (' '..='~').contains(&ch) => buf.push(ch),
+        Some('\t') => buf.push_str("\\t"),
+        Some('\n') => buf.push_str("\\n"),
+        Some('\u{c}') => buf.push_str("\\f"),
+        Some('\r') => buf.push_str("\\r"),
+        _ if value <= 0xff => {
+            let _ = write!(buf, "\\x{:02X}", value);
+        }
+        _ if value <= 0xffff => {
+            let _ = write!(buf, "\\u{:04X}", value);
+        }
+        _ if unicode => {
+            let _ = write!(buf, "\\u{{{:X}}}", value);
+        }
+        _ => {
+            let (high, low) = surrogate_pair(value);
+            let _ = write!(buf, "\\u{:04X}\\u{:04X}", high, low);
+        }
+    }
+}
+
+/// Splits an astral code point into a surrogate pair.
+pub(crate) fn surrogate_pair(value: u32) -> (u32, u32) {
+    debug_assert!(value > 0xffff);
+    let value = value - 0x10000;
+    (0xd800 + (value >> 10), 0xdc00 + (value & 0x3ff))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(src: &str, flags: &str) {
+        let pattern = Pattern::parse(src, flags).unwrap();
+        assert_eq!(pattern.print(flags.contains('u')), src);
+    }
+
+    #[test]
+    fn print_unmodified() {
+        roundtrip("a|b(c)*?[^\\d\\-x-z]{1,2}\\1\\k", "");
+        roundtrip("(?<year>\\d{4})-(?<month>\\d{2})\\k<year>", "");
+        roundtrip("(?:a)(?=b)(?!c)(?<=d)(?<!e)^$\\b\\B", "");
+        roundtrip("\\p{Script=Greek}[\\P{L}\\u{1F600}]\\uD83D\\uDE00", "u");
+        roundtrip("a{1,x}]\\c\\01\\8[\\b]", "");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Pattern::parse("(a", "").is_err());
+        assert!(Pattern::parse("a)", "").is_err());
+        assert!(Pattern::parse("[b-a]", "").is_err());
+        assert!(Pattern::parse("a{", "u").is_err());
+        assert!(Pattern::parse("\\p{L", "u").is_err());
+    }
+
+    #[test]
+    fn unicode_escapes() {
+        let pattern = Pattern::parse("\\uD83D\\uDE00\\u{1F600}", "u").unwrap();
+        let values = pattern.body[0]
+            .iter()
+            .map(|t| match &t.atom {
+                Atom::Char(c) => c.value,
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![0x1f600, 0x1f600]);
+    }
+
+    #[test]
+    fn char_set() {
+        let mut set = CharSet::from_ranges(vec![(10, 20), (30, 40)]);
+        set.add(21, 29);
+        assert_eq!(set.ranges(), &[(10, 40)]);
+        set.add(0, 5);
+        assert_eq!(set.ranges(), &[(0, 5), (10, 40)]);
+        assert_eq!(
+            set.complement().ranges(),
+            &[(6, 9), (41, char_set::MAX_CODE_POINT)]
+        );
+
+        let greek = CharSet::property("Script=Greek").unwrap();
+        assert!(greek
+            .ranges()
+            .iter()
+            .any(|&(s, e)| s <= 0x3b1 && 0x3b1 <= e));
+        assert!(CharSet::property("NotAProperty").is_none());
+    }
+}
@@ -0,0 +1,159 @@
+use regex_syntax::{
+    hir::{Class, HirKind},
+    ParserBuilder,
+};
+
+pub(crate) const MAX_CODE_POINT: u32 = 0x10ffff;
+
+/// A set of code points, stored as sorted and non-adjacent inclusive ranges.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct CharSet {
+    ranges: Vec<(u32, u32)>,
+}
+
+impl CharSet {
+    pub fn from_ranges(ranges: impl IntoIterator<Item = (u32, u32)>) -> Self {
+        let mut set = CharSet::default();
+        for (start, end) in ranges {
+            set.add(start, end);
+        }
+        set
+    }
+
+    pub fn ranges(&self) -> &[(u32, u32)] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn add(&mut self, start: u32, end: u32) {
+        debug_assert!(start <= end);
+
+        // Ranges are mostly added in order.
+        match self.ranges.last() {
+            Some(&(_, e)) if e + 1 < start => {
+                self.ranges.push((start, end));
+                return;
+            }
+            None => {
+                self.ranges.push((start, end));
+                return;
+            }
+            _ => {}
+        }
+
+        let idx = self
+            .ranges
+            .iter()
+            .position(|&(_, e)| e + 1 >= start)
+            .unwrap_or_else(|| self.ranges.len());
+        let mut start = start;
+        let mut end = end;
+        let mut last = idx;
+        while last < self.ranges.len() && self.ranges[last].0 <= end + 1 {
+            start = start.min(self.ranges[last].0);
+            end = end.max(self.ranges[last].1);
+            last += 1;
+        }
+        self.ranges.splice(idx..last, Some((start, end)));
+    }
+
+    pub fn union(&mut self, other: &CharSet) {
+        for &(start, end) in &other.ranges {
+            self.add(start, end);
+        }
+    }
+
+    pub fn complement(&self) -> CharSet {
+        let mut ranges = vec![];
+        let mut next = 0;
+        for &(start, end) in &self.ranges {
+            if start > next {
+                ranges.push((next, start - 1));
+            }
+            next = end + 1;
+        }
+        if next <= MAX_CODE_POINT {
+            ranges.push((next, MAX_CODE_POINT));
+        }
+        CharSet { ranges }
+    }
+
+    /// Splits the set into the code points in the basic multilingual plane
+    /// and the astral ones.
+    pub fn split_bmp(&self) -> (CharSet, CharSet) {
+        let mut bmp = vec![];
+        let mut astral = vec![];
+        for &(start, end) in &self.ranges {
+            if end <= 0xffff {
+                bmp.push((start, end));
+            } else if start > 0xffff {
+                astral.push((start, end));
+            } else {
+                bmp.push((start, 0xffff));
+                astral.push((0x10000, end));
+            }
+        }
+        (CharSet { ranges: bmp }, CharSet { ranges: astral })
+    }
+
+    /// Set for `\d`, `\s`, `\w` and their negations.
+    pub fn builtin(c: char) -> CharSet {
+        let set = match c.to_ascii_lowercase() {
+            'd' => CharSet::from_ranges(vec![(0x30, 0x39)]),
+            's' => CharSet::from_ranges(vec![
+                (0x09, 0x0d),
+                (0x20, 0x20),
+                (0xa0, 0xa0),
+                (0x1680, 0x1680),
+                (0x2000, 0x200a),
+                (0x2028, 0x2029),
+                (0x202f, 0x202f),
+                (0x205f, 0x205f),
+                (0x3000, 0x3000),
+                (0xfeff, 0xfeff),
+            ]),
+            'w' => {
+                CharSet::from_ranges(vec![(0x30, 0x39), (0x41, 0x5a), (0x5f, 0x5f), (0x61, 0x7a)])
+            }
+            _ => unreachable!("invalid character class escape: \\{}", c),
+        };
+
+        if c.is_ascii_uppercase() {
+            set.complement()
+        } else {
+            set
+        }
+    }
+
+    /// Set for `\p{name}`. Returns [None] for unknown properties.
+    pub fn property(name: &str) -> Option<CharSet> {
+        // Surrogates are not `char`s, so the tables below can't contain them.
+        match name {
+            "Any" => return Some(CharSet::from_ranges(vec![(0, MAX_CODE_POINT)])),
+            "Cs"
+            | "Surrogate"
+            | "gc=Cs"
+            | "gc=Surrogate"
+            | "General_Category=Cs"
+            | "General_Category=Surrogate" => {
+                return Some(CharSet::from_ranges(vec![(0xd800, 0xdfff)]))
+            }
+            _ => {}
+        }
+
+        let hir = ParserBuilder::new()
+            .build()
+            .parse(&format!("\\p{{{}}}", name))
+            .ok()?;
+
+        match hir.into_kind() {
+            HirKind::Class(Class::Unicode(class)) => Some(CharSet::from_ranges(
+                class.iter().map(|r| (r.start() as u32, r.end() as u32)),
+            )),
+            _ => None,
+        }
+    }
+}
@@ -79,7 +79,8 @@ impl Helpers {
         }
     }
 
-    pub(crate) const fn mark(&self) -> Mark {
+    /// Identifiers referencing helpers have this mark.
+    pub const fn mark(&self) -> Mark {
         self.mark.0
     }
     pub(crate) const fn external(&self) -> bool {
@@ -119,6 +120,31 @@ macro_rules! define_helpers {
         }
 
         impl Helpers {
+            /// Returns names of the used helpers, like `class_call_check`.
+            pub fn used(&self) -> Vec<&'static str> {
+                let mut buf = vec![];
+                $(
+                    if self.inner.$name.load(Ordering::SeqCst) {
+                        buf.push(stringify!($name));
+                    }
+                )*
+                buf
+            }
+
+            /// Marks a helper named like [Helpers::used] returns as used.
+            ///
+            /// Returns `false` if there's no such helper.
+            pub fn enable(&self, name: &str) -> bool {
+                match name {
+                    $(
+                        stringify!($name) => self.$name(),
+                    )*
+                    _ => return false,
+                }
+
+                true
+            }
+
             pub fn extend_from(&self, other: &Self) {
                 $(
                     if other.inner.$name.load(Ordering::SeqCst) {
@@ -234,6 +260,7 @@ define_helpers!(Helpers {
         set_prototype_of,
         is_native_function
     ),
+    wrap_reg_exp: (wrap_native_super, inherits),
 
     class_private_field_destructure: (),
 });
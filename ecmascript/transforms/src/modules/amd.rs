@@ -1,6 +1,7 @@
 use super::util::{
     self, define_es_module, define_property, has_use_strict, initialize_to_undefined,
-    local_name_for_src, make_descriptor, use_strict, Exports, ModulePass, Scope,
+    local_name_for_src, make_descriptor, report_top_level_await, use_strict, Exports, ModulePass,
+    Scope,
 };
 use crate::util::{prepend_stmts, var::VarCollector, DestructuringFinder, ExprFactory};
 use fxhash::FxHashSet;
@@ -47,6 +48,8 @@ impl Fold for Amd {
     }
 
     fn fold_module(&mut self, module: Module) -> Module {
+        report_top_level_await(&module, "AMD");
+
         let items = module.body;
         self.in_top_level = true;
 
@@ -2,7 +2,8 @@ use self::config::BuiltConfig;
 pub use self::config::Config;
 use super::util::{
     self, define_es_module, define_property, has_use_strict, initialize_to_undefined,
-    local_name_for_src, make_descriptor, make_require_call, use_strict, Exports, ModulePass, Scope,
+    local_name_for_src, make_descriptor, make_require_call, report_top_level_await, use_strict,
+    Exports, ModulePass, Scope,
 };
 use crate::util::{prepend_stmts, var::VarCollector, DestructuringFinder, ExprFactory};
 use fxhash::FxHashSet;
@@ -45,6 +46,8 @@ impl Fold for Umd {
     }
 
     fn fold_module(&mut self, module: Module) -> Module {
+        report_top_level_await(&module, "UMD");
+
         self.in_top_level = true;
 
         let filename = self.cm.span_to_filename(module.span);
@@ -1,4 +1,6 @@
-use crate::util::{undefined, DestructuringFinder, ExprFactory};
+use crate::util::{
+    find_top_level_await, undefined, DestructuringFinder, ExprFactory, TopLevelAwaitFinder, HANDLER,
+};
 use fxhash::FxHashSet;
 use indexmap::IndexMap;
 use inflector::Inflector;
@@ -764,6 +766,30 @@ pub(super) fn has_use_strict(stmts: &[ModuleItem]) -> bool {
     false
 }
 
+/// Reports top level await, as it cannot be used in the synchronous module
+/// body of `module_type` modules.
+pub(super) fn report_top_level_await<N>(node: &N, module_type: &str)
+where
+    N: VisitWith<TopLevelAwaitFinder>,
+{
+    if let Some(span) = find_top_level_await(node) {
+        if HANDLER.is_set() {
+            HANDLER.with(|handler| {
+                handler
+                    .struct_span_err(
+                        span,
+                        &format!(
+                            "top level await is not supported by {} modules. Use es6 modules or \
+                             move the await into an async function",
+                            module_type
+                        ),
+                    )
+                    .emit()
+            });
+        }
+    }
+}
+
 pub(super) fn use_strict() -> Stmt {
     Lit::Str(quote_str!("use strict")).into_stmt()
 }
@@ -1,6 +1,6 @@
 use crate::util::{
     alias_ident_for, constructor::inject_after_super, prop_name_to_expr_value, undefined,
-    ExprFactory, IdentExt,
+    ExprFactory, IdentExt, HANDLER,
 };
 use either::Either;
 use serde::Deserialize;
@@ -408,8 +408,17 @@ impl Decorators {
             .filter_map(|member| {
                 //
                 match member {
-                    ClassMember::Constructor(_) => unreachable!("multiple constructor?"),
-                    ClassMember::TsIndexSignature(_) => None,
+                    // Overload signatures of typescript.
+                    ClassMember::Constructor(Constructor { body: None, .. }) => None,
+                    ClassMember::Constructor(c) => {
+                        HANDLER.with(|handler| {
+                            handler
+                                .struct_span_err(c.span, "A class may only have one constructor")
+                                .emit()
+                        });
+                        None
+                    }
+                    ClassMember::TsIndexSignature(_) | ClassMember::Empty(_) => None,
                     ClassMember::Method(method) => {
                         let fn_name = match method.key {
                             PropName::Ident(ref i) => Some(i.clone()),
@@ -523,7 +532,18 @@ impl Decorators {
                             .as_arg(),
                         )
                     }
-                    _ => unimplemented!("ClassMember::{:?}", member,),
+                    ClassMember::PrivateProp(..) | ClassMember::StaticBlock(..) => {
+                        HANDLER.with(|handler| {
+                            handler
+                                .struct_span_err(
+                                    member.span(),
+                                    "this class member is not supported by the decorators \
+                                     transform yet",
+                                )
+                                .emit()
+                        });
+                        None
+                    }
                 }
             })
             .map(Some)
@@ -301,8 +301,29 @@ impl Legacy {
                 }
 
                 let name = match m.key {
-                    PropName::Computed(..) => {
-                        unimplemented!("decorators on methods with computed key")
+                    PropName::Computed(ref mut key) => {
+                        // The key is evaluated only once, while defining the class.
+                        //
+                        //  [_key = foo()]() {}
+                        let (i, aliased) = alias_if_required(&key.expr, "_key");
+                        if aliased {
+                            self.uninitialized_vars.push(VarDeclarator {
+                                span: DUMMY_SP,
+                                name: Pat::Ident(i.clone()),
+                                init: None,
+                                definite: false,
+                            });
+
+                            let expr = replace(&mut key.expr, undefined(DUMMY_SP));
+                            key.expr = Box::new(Expr::Assign(AssignExpr {
+                                span: DUMMY_SP,
+                                op: op!("="),
+                                left: PatOrExpr::Pat(Box::new(Pat::Ident(i.clone()))),
+                                right: expr,
+                            }));
+                        }
+
+                        Expr::Ident(i)
                     }
                     _ => prop_name_to_expr_value(m.key.clone()),
                 };
@@ -68,20 +68,20 @@ fn parse_option(cm: &SourceMap, name: &str, src: String) -> Box<Expr> {
         return expr.clone();
     }
 
-    let expr = Parser::new(Syntax::default(), StringInput::from(&*fm), None)
-        .parse_expr()
-        .map_err(|e| {
-            if HANDLER.is_set() {
-                HANDLER.with(|h| e.into_diagnostic(h).emit())
-            }
-        })
-        .map(drop_span)
-        .unwrap_or_else(|()| {
-            panic!(
-                "faield to parse jsx option {}: '{}' is not an expression",
-                name, fm.src,
-            )
-        });
+    let expr = match Parser::new(Syntax::default(), StringInput::from(&*fm), None).parse_expr() {
+        Ok(expr) => drop_span(expr),
+        Err(e) => {
+            HANDLER.with(|h| {
+                e.into_diagnostic(h)
+                    .note(&format!(
+                        "failed to parse jsx option {}: '{}' is not an expression",
+                        name, fm.src
+                    ))
+                    .emit()
+            });
+            return Box::new(Expr::Invalid(Invalid { span: DUMMY_SP }));
+        }
+    };
 
     CACHE.insert((*fm.src).clone(), expr.clone());
 
@@ -193,9 +193,11 @@ where
             }) => return None,
             JSXElementChild::JSXElement(el) => self.jsx_elem_to_expr(*el).as_arg(),
             JSXElementChild::JSXFragment(el) => self.jsx_frag_to_expr(el).as_arg(),
-            JSXElementChild::JSXSpreadChild(JSXSpreadChild { .. }) => {
-                unimplemented!("jsx sperad child")
-            }
+            // React.createElement("div", null, ...children)
+            JSXElementChild::JSXSpreadChild(JSXSpreadChild { span, expr }) => ExprOrSpread {
+                spread: Some(span),
+                expr,
+            },
         })
     }
 
@@ -1170,3 +1170,14 @@ test!(
     "let page = React.createElement('p', null, 'Click ', React.createElement('em', null, 'New \
      melody'), ' listen to a randomly generated melody');"
 );
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |t| tr(t, Default::default()),
+    spread_children,
+    r#"<div>{...children}</div>;"#,
+    r#"React.createElement("div", null, ...children);"#
+);
@@ -14,7 +14,7 @@ use swc_ecma_visit::{as_folder, Fold, Node, Visit, VisitMut, VisitMutWith, Visit
 /// Value does not contain TsLit::Bool
 type EnumValues = FxHashMap<Id, TsLit>;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 // TODO(nayeemrmn): The name should be `ImportsNotUsedAsValues`. Rename as a
 // breaking change.
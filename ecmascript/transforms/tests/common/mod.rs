@@ -15,7 +15,7 @@ use swc_common::{
 };
 use swc_ecma_ast::*;
 use swc_ecma_codegen::Emitter;
-use swc_ecma_parser::{error::Error, lexer::Lexer, Parser, StringInput, Syntax};
+use swc_ecma_parser::{error::Error, lexer::Lexer, JscTarget, Parser, StringInput, Syntax};
 use swc_ecma_transforms::helpers::{inject_helpers, HELPERS};
 use swc_ecma_utils::DropSpan;
 use swc_ecma_visit::{as_folder, Fold, FoldWith, VisitMut, VisitMutWith};
@@ -222,6 +222,45 @@ where
     });
 }
 
+/// Applies `tr` to `input` and returns the errors reported by it.
+pub fn test_transform_errors<F, P>(syntax: Syntax, target: JscTarget, tr: F, input: &str) -> String
+where
+    F: FnOnce(&mut Tester<'_>) -> P,
+    P: Fold,
+{
+    let res = ::testing::run_test(false, |cm, handler| {
+        swc_ecma_transforms::util::HANDLER.set(handler, || {
+            HELPERS.set(&Default::default(), || {
+                let mut tester = Tester {
+                    cm: cm.clone(),
+                    handler,
+                    comments: Default::default(),
+                };
+
+                let fm = cm.new_source_file(FileName::Real("input.js".into()), input.into());
+                let lexer = Lexer::new(syntax, target, StringInput::from(&*fm), None);
+                let module = Parser::new_from(lexer)
+                    .parse_module()
+                    .map_err(|e| e.into_diagnostic(handler).emit())?;
+
+                let mut tr = make_tr("actual", tr, &mut tester);
+                module.fold_with(&mut tr);
+
+                if handler.has_errors() {
+                    Err(())
+                } else {
+                    Ok(())
+                }
+            })
+        })
+    });
+
+    match res {
+        Ok(()) => panic!("expected an error"),
+        Err(stderr) => stderr.to_string(),
+    }
+}
+
 #[derive(PartialEq, Eq)]
 pub struct DebugUsingDisplay<'a>(pub &'a str);
 impl<'a> fmt::Debug for DebugUsingDisplay<'a> {
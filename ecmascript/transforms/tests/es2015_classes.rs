@@ -3,7 +3,7 @@
 use swc_common::chain;
 use swc_ecma_parser::{EsConfig, Syntax};
 use swc_ecma_transforms::{
-    compat::es2015::{arrow, block_scoping, classes, spread},
+    compat::es2015::{arrow, block_scoping, classes, classes_with_config, spread},
     react::jsx,
     resolver,
 };
@@ -6132,3 +6132,140 @@ const obj = new OtherClass();
 expect(obj.anotherMethod()).toBe(2);
 "#
 );
+
+test!(
+    syntax(),
+    |_| classes_with_config(classes::Config {
+        set_class_methods: true,
+        ..Default::default()
+    }),
+    set_class_methods,
+    r#"
+class Foo {
+  foo() {}
+  static bar() {}
+  get baz() {
+    return 1;
+  }
+  ["qux"]() {}
+}
+"#,
+    r#"
+let Foo = function () {
+  "use strict";
+
+  function Foo() {
+    _classCallCheck(this, Foo);
+  }
+
+  Foo.prototype.foo = function foo() {};
+
+  Foo.prototype["qux"] = function () {};
+
+  Foo.bar = function bar() {};
+
+  _createClass(Foo, [{
+    key: "baz",
+    get: function () {
+      return 1;
+    }
+  }]);
+
+  return Foo;
+}();
+"#
+);
+
+test!(
+    syntax(),
+    |_| classes_with_config(classes::Config {
+        no_class_calls: true,
+        constant_super: true,
+        ..Default::default()
+    }),
+    no_class_calls_constant_super,
+    r#"
+class Bar extends Foo {
+  constructor() {
+    super();
+    this.a = super.a;
+  }
+  foo() {
+    return super.foo(1);
+  }
+  static bar() {
+    return super.bar();
+  }
+}
+"#,
+    r#"
+let Bar = function (Foo) {
+  "use strict";
+
+  _inherits(Bar, Foo);
+
+  function Bar() {
+    var _this;
+
+    _this = _possibleConstructorReturn(this, _getPrototypeOf(Bar).call(this));
+    _this.a = Foo.prototype.a;
+    return _this;
+  }
+
+  _createClass(Bar, [{
+    key: "foo",
+    value: function foo() {
+      return Foo.prototype.foo.call(this, 1);
+    }
+  }], [{
+    key: "bar",
+    value: function bar() {
+      return Foo.bar.call(this);
+    }
+  }]);
+
+  return Bar;
+}(Foo);
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| classes_with_config(classes::Config {
+        no_class_calls: true,
+        constant_super: true,
+        set_class_methods: true,
+    }),
+    loose_exec,
+    r#"
+class Foo {
+  constructor(a) {
+    this.a = a;
+  }
+  foo() {
+    return this.a;
+  }
+  static bar() {
+    return "bar";
+  }
+  get baz() {
+    return this.a * 2;
+  }
+}
+
+class Bar extends Foo {
+  foo() {
+    return super.foo() + 1;
+  }
+  static bar() {
+    return super.bar() + "!";
+  }
+}
+
+const bar = new Bar(1);
+expect(bar.foo()).toBe(2);
+expect(bar.baz).toBe(2);
+expect(Bar.bar()).toBe("bar!");
+expect(Object.keys(Foo.prototype)).toEqual(["foo"]);
+"#
+);
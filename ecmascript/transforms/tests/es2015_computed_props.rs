@@ -1,6 +1,8 @@
 #![feature(test)]
 use swc_ecma_parser::Syntax;
-use swc_ecma_transforms::compat::es2015::computed_properties;
+use swc_ecma_transforms::compat::es2015::{
+    computed_properties, computed_properties_with_config, computed_props,
+};
 use swc_ecma_visit::Fold;
 
 #[macro_use]
@@ -528,3 +530,44 @@ expect(foo[k]).toBe(k)
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| computed_properties_with_config(computed_props::Config {
+        set_computed_properties: true,
+    }),
+    set_computed_properties,
+    r#"
+var a = { [b]: 1 };
+var obj = {
+  foo: "foo",
+  ["x" + foo]: "heh",
+  bar: "bar",
+};
+"#,
+    r#"
+var _obj;
+var a = (_obj = {}, _obj[b] = 1, _obj);
+var _obj1;
+var obj = (_obj1 = {
+  foo: "foo"
+}, _obj1["x" + foo] = "heh", _obj1["bar"] = "bar", _obj1);
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    spread,
+    r#"
+var obj = {
+  [a]: 1,
+  ...b,
+  c: 2,
+};
+"#,
+    r#"
+var _obj;
+var obj = (_obj = {}, _defineProperty(_obj, a, 1), _objectSpread(_obj, b), _defineProperty(_obj, "c", 2), _obj);
+"#
+);
@@ -0,0 +1,96 @@
+#![feature(test)]
+use swc_ecma_parser::Syntax;
+use swc_ecma_transforms::compat::es2015::instance_of;
+use swc_ecma_visit::Fold;
+
+#[macro_use]
+mod common;
+
+fn syntax() -> Syntax {
+    Default::default()
+}
+
+fn tr() -> impl Fold {
+    instance_of()
+}
+
+test!(
+    syntax(),
+    |_| tr(),
+    instanceof,
+    r#"
+foo instanceof Bar;
+"#,
+    r#"
+_instanceof(foo, Bar);
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    nested,
+    r#"
+if (a instanceof b === c instanceof (d instanceof E)) {
+    foo(function () {
+        return x instanceof y;
+    });
+}
+"#,
+    r#"
+if (_instanceof(a, b) === _instanceof(c, _instanceof(d, E))) {
+    foo(function () {
+        return _instanceof(x, y);
+    });
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    ignore_other_operators,
+    r#"
+a in b;
+typeof c === "object";
+"#,
+    r#"
+a in b;
+typeof c === "object";
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(),
+    exec,
+    r#"
+var foo = {
+    [Symbol.hasInstance]: function (value) {
+        return true;
+    },
+};
+var bar = {};
+
+expect(bar instanceof foo).toBe(true);
+expect(new String("foo") instanceof String).toBe(true);
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(),
+    exec_has_instance_receives_value,
+    r#"
+class Even {
+    static [Symbol.hasInstance](num) {
+        return num % 2 === 0;
+    }
+}
+
+expect(2 instanceof Even).toBe(true);
+expect(3 instanceof Even).toBe(false);
+expect([] instanceof Array).toBe(true);
+expect(null instanceof Object).toBe(false);
+"#
+);
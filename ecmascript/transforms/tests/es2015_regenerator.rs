@@ -1219,3 +1219,188 @@ test_exec!(
     expect(v.next()).toEqual({ done: true})
     "
 );
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    destructuring_default_value,
+    "
+    const x = function*() {
+        const { a = 1, b } = yield 'first';
+        yield a + b;
+    }
+    const v = x();
+    expect(v.next()).toEqual({ value: 'first', done: false })
+    expect(v.next({ b: 2 })).toEqual({ value: 3, done: false })
+    expect(v.next()).toEqual({ done: true })
+    "
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    compound_assignment,
+    "
+    const x = function*() {
+        let a = 1;
+        a += yield a;
+        yield a;
+    }
+    const v = x();
+    expect(v.next()).toEqual({ value: 1, done: false })
+    expect(v.next(2)).toEqual({ value: 3, done: false })
+    expect(v.next()).toEqual({ done: true })
+    "
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    catch_pattern,
+    "
+    const x = function*() {
+        try {
+            yield 1;
+            throw new Error('foo');
+        } catch ({ message }) {
+            yield message;
+            yield message + '!';
+        }
+    }
+    const v = x();
+    expect(v.next()).toEqual({ value: 1, done: false })
+    expect(v.next()).toEqual({ value: 'foo', done: false })
+    expect(v.next()).toEqual({ value: 'foo!', done: false })
+    expect(v.next()).toEqual({ done: true })
+    "
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    for_in_var,
+    "
+    const x = function*() {
+        for (var key in { a: 1, b: 2 }) {
+            yield key;
+        }
+        yield key;
+    }
+    const v = x();
+    expect(v.next()).toEqual({ value: 'a', done: false })
+    expect(v.next()).toEqual({ value: 'b', done: false })
+    expect(v.next()).toEqual({ value: 'b', done: false })
+    expect(v.next()).toEqual({ done: true })
+    "
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    for_of_pattern,
+    "
+    const x = function*() {
+        for (const [a, b = 2] of [[1], [3, 4]]) {
+            yield a + b;
+        }
+    }
+    const v = x();
+    expect(v.next()).toEqual({ value: 3, done: false })
+    expect(v.next()).toEqual({ value: 7, done: false })
+    expect(v.next()).toEqual({ done: true })
+    "
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    yield_in_pattern_default,
+    "
+    function* foo(obj) {
+        const { a = yield 1, b = yield 2, c = yield 3 } = obj;
+        return [a, b, c];
+    }
+
+    const v = foo({ b: 'b' });
+    expect(v.next()).toEqual({ value: 1, done: false });
+    expect(v.next('a')).toEqual({ value: 3, done: false });
+    expect(v.next('c')).toEqual({ value: ['a', 'b', 'c'], done: true });
+    "
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    yield_in_pattern_computed_key,
+    "
+    function* foo(obj) {
+        let a, b;
+        ({ [yield 'key']: a, b = yield 'default' } = obj);
+        [a, [b = yield 'nested']] = [a, []];
+        return [a, b];
+    }
+
+    const v = foo({ x: 1 });
+    expect(v.next()).toEqual({ value: 'key', done: false });
+    expect(v.next('x')).toEqual({ value: 'default', done: false });
+    expect(v.next(2)).toEqual({ value: 'nested', done: false });
+    expect(v.next(3)).toEqual({ value: [1, 3], done: true });
+    "
+);
+
+test_exec!(
+    syntax(),
+    |_| tr(Default::default()),
+    for_of_close_iterator,
+    "
+    function iterable(log) {
+        let i = 0;
+        return {
+            [Symbol.iterator]() {
+                return {
+                    next() {
+                        return { value: i++, done: i > 3 };
+                    },
+                    return() {
+                        log.push('return');
+                        return {};
+                    },
+                };
+            },
+        };
+    }
+
+    function* brk(log) {
+        for (const x of iterable(log)) {
+            yield x;
+            break;
+        }
+    }
+
+    function* thr(log) {
+        for (const x of iterable(log)) {
+            yield x;
+            throw new Error('thrown');
+        }
+    }
+
+    const log1 = [];
+    const v1 = brk(log1);
+    expect(v1.next()).toEqual({ value: 0, done: false });
+    expect(log1).toEqual([]);
+    expect(v1.next()).toEqual({ done: true });
+    expect(log1).toEqual(['return']);
+
+    const log2 = [];
+    const v2 = thr(log2);
+    expect(v2.next()).toEqual({ value: 0, done: false });
+    expect(() => v2.next()).toThrow('thrown');
+    expect(log2).toEqual(['return']);
+
+    const log3 = [];
+    const v3 = brk(log3);
+    expect(v3.next()).toEqual({ value: 0, done: false });
+    expect(v3.return(5)).toEqual({ value: 5, done: true });
+    expect(log3).toEqual(['return']);
+    "
+);
@@ -1,6 +1,6 @@
 #![feature(test)]
 use swc_ecma_parser::Syntax;
-use swc_ecma_transforms::compat::es2015::template_literal;
+use swc_ecma_transforms::compat::es2015::{template_literal, template_literal_with_config};
 use swc_ecma_visit::Fold;
 
 #[macro_use]
@@ -907,3 +907,47 @@ test!(
     r#""The ".concat(argumentName, " has unexpected type of \"") + matchType + "\". Expected argument to be an object with the following " + "keys: \"".concat(reducerKeys.join('", "'), "\"")"#,
     ok_if_code_eq
 );
+
+test!(
+    syntax(),
+    |_| template_literal_with_config(template_literal::Config {
+        ignore_to_primitive_hint: true,
+        ..Default::default()
+    }),
+    ignore_to_primitive_hint,
+    r#"
+var a = `foo${bar}baz${qux}`;
+var b = `${foo}`;
+var c = `${a + b}${c}`;
+"#,
+    r#"
+var a = "foo" + bar + "baz" + qux;
+var b = "" + foo;
+var c = "" + (a + b) + c;
+"#
+);
+
+test!(
+    syntax(),
+    |_| template_literal_with_config(template_literal::Config {
+        mutable_template_object: true,
+        ..Default::default()
+    }),
+    mutable_template_object,
+    r#"
+var a = tag`foo${bar}baz`;
+"#,
+    r#"
+function _templateObject() {
+  const data = _taggedTemplateLiteralLoose(["foo", "baz"]);
+
+  _templateObject = function () {
+    return data;
+  };
+
+  return data;
+}
+
+var a = tag(_templateObject(), bar);
+"#
+);
@@ -0,0 +1,275 @@
+#![feature(test)]
+use swc_common::{chain, Mark};
+use swc_ecma_parser::Syntax;
+use swc_ecma_transforms::compat::{
+    es2015::{es2015, regenerator},
+    es2017::async_to_generator,
+    es2018::async_generator_functions,
+};
+use swc_ecma_visit::Fold;
+
+#[macro_use]
+mod common;
+
+fn syntax() -> Syntax {
+    Syntax::default()
+}
+
+fn tr() -> impl Fold {
+    async_generator_functions()
+}
+
+test!(
+    syntax(),
+    |_| tr(),
+    fn_decl,
+    r#"
+async function* agf(a = 1) {
+    await a;
+    yield 1;
+    yield* other();
+}
+"#,
+    r#"
+function agf(a = 1) {
+    return _wrapAsyncGenerator(function* () {
+        yield _awaitAsyncGenerator(a);
+        yield 1;
+        yield* _asyncGeneratorDelegate(_asyncIterator(other()), _awaitAsyncGenerator);
+    }).apply(this, arguments);
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    nested_fn,
+    r#"
+const o = {
+    async *gen() {
+        const f = async () => await 1;
+        function g() {}
+        yield await f();
+    }
+};
+"#,
+    r#"
+const o = {
+    gen() {
+        return _wrapAsyncGenerator(function* () {
+            const f = async () => await 1;
+            function g() {}
+            yield yield _awaitAsyncGenerator(f());
+        }).apply(this, arguments);
+    }
+};
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    class_method_super,
+    r#"
+class A extends B {
+    async *gen() {
+        yield super.foo();
+    }
+}
+"#,
+    r#"
+class A extends B {
+    gen() {
+        var _super_foo = (..._args) => super.foo(..._args);
+        return _wrapAsyncGenerator(function* () {
+            yield _super_foo();
+        }).apply(this, arguments);
+    }
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    for_await_in_async_fn,
+    r#"
+async function f() {
+    for await (const x of y) {
+        g(x);
+    }
+}
+"#,
+    r#"
+async function f() {
+    var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+    try {
+        for (var _iterator = _asyncIterator(y), _step, _value; _step = await _iterator.next(), _iteratorNormalCompletion = _step.done, _value = await _step.value, !_iteratorNormalCompletion; _iteratorNormalCompletion = true) {
+            const x = _value;
+            g(x);
+        }
+    } catch (err) {
+        _didIteratorError = true;
+        _iteratorError = err;
+    } finally {
+        try {
+            if (!_iteratorNormalCompletion && _iterator.return != null) {
+                await _iterator.return();
+            }
+        } finally {
+            if (_didIteratorError) {
+                throw _iteratorError;
+            }
+        }
+    }
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    for_await_in_async_generator,
+    r#"
+async function* f() {
+    outer: for await (x of y) continue outer;
+}
+"#,
+    r#"
+function f() {
+    return _wrapAsyncGenerator(function* () {
+        var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+        try {
+            outer: for (var _iterator = _asyncIterator(y), _step, _value; (_step = yield _awaitAsyncGenerator(_iterator.next()), _iteratorNormalCompletion = _step.done, _value = yield _awaitAsyncGenerator(_step.value), !_iteratorNormalCompletion); _iteratorNormalCompletion = true) {
+                x = _value;
+                continue outer;
+            }
+        } catch (err) {
+            _didIteratorError = true;
+            _iteratorError = err;
+        } finally {
+            try {
+                if (!_iteratorNormalCompletion && _iterator.return != null) {
+                    yield _awaitAsyncGenerator(_iterator.return());
+                }
+            } finally {
+                if (_didIteratorError) {
+                    throw _iteratorError;
+                }
+            }
+        }
+    }).apply(this, arguments);
+}
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(tr(), async_to_generator()),
+    exec_async_generator,
+    r#"
+async function* numbers(count) {
+    for (let i = 0; i < count; i++) {
+        yield await Promise.resolve(i);
+    }
+}
+
+async function* doubled(source) {
+    for await (const n of source) {
+        yield n * 2;
+    }
+}
+
+async function* delegating() {
+    yield* doubled(numbers(2));
+    yield* [10, 11];
+}
+
+return (async () => {
+    const values = [];
+    for await (const n of delegating()) {
+        values.push(n);
+    }
+    expect(values).toEqual([0, 2, 10, 11]);
+
+    const sync = [];
+    for await (const v of [Promise.resolve(1), 2]) {
+        sync.push(v);
+    }
+    expect(sync).toEqual([1, 2]);
+})();
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(tr(), async_to_generator()),
+    exec_early_return,
+    r#"
+let finished = false;
+async function* gen() {
+    try {
+        yield 1;
+        yield 2;
+    } finally {
+        finished = true;
+    }
+}
+
+return (async () => {
+    for await (const x of gen()) {
+        expect(x).toBe(1);
+        break;
+    }
+    expect(finished).toBe(true);
+})();
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(
+        tr(),
+        async_to_generator(),
+        es2015(Mark::fresh(Mark::root()), Default::default()),
+    ),
+    exec_es5,
+    r#"
+async function* gen() {
+    yield 1;
+    yield await Promise.resolve(2);
+}
+
+return (async function () {
+    const values = [];
+    for await (const x of gen()) {
+        values.push(x);
+    }
+    expect(values).toEqual([1, 2]);
+})();
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(
+        tr(),
+        async_to_generator(),
+        regenerator(Mark::fresh(Mark::root()))
+    ),
+    exec_regenerator,
+    r#"
+async function* gen() {
+    yield* [1, 2];
+}
+
+return (async function () {
+    const values = [];
+    for await (const x of gen()) {
+        values.push(x);
+    }
+    expect(values).toEqual([1, 2]);
+})();
+"#
+);
@@ -4,7 +4,7 @@ use swc_ecma_parser::Syntax;
 use swc_ecma_transforms::{
     compat::{
         es2015::{destructuring, spread},
-        es2018::object_rest_spread,
+        es2018::{object_rest_spread, object_rest_spread_with_config},
     },
     modules::common_js::common_js,
     resolver,
@@ -2790,3 +2790,21 @@ const _ref2 = foo((_param)=>{
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| object_rest_spread_with_config(object_rest_spread::Config {
+        object_rest_no_symbols: true,
+        set_spread_properties: true,
+    }),
+    loose,
+    r#"
+const { a, ...b } = c;
+const d = { e, ...f, g };
+"#,
+    r#"
+const { a } = c,
+  b = _objectWithoutPropertiesLoose(c, ["a"]);
+const d = _extends({ e }, f, { g });
+"#
+);
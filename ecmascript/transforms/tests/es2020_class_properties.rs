@@ -6,7 +6,7 @@ use swc_ecma_transforms::{
         es2015::{arrow, block_scoping, classes, function_name},
         es2016::exponentation,
         es2017::async_to_generator,
-        es2020::{class_properties, typescript_class_properties},
+        es2020::{class_properties, class_properties_with_config, typescript_class_properties},
         es3::reserved_words,
     },
     proposals::decorators,
@@ -4962,3 +4962,84 @@ class Foo {
 Foo.identifier = 5;
   "
 );
+
+test!(
+    syntax(),
+    |_| chain!(
+        resolver(),
+        class_properties_with_config(class_properties::Config {
+            set_public_class_fields: true,
+        }),
+    ),
+    set_public_class_fields,
+    r#"
+class Foo extends Bar {
+  a = 1;
+  b;
+  [c] = 2;
+  "d-e" = 3;
+  static f = 4;
+  #g = 5;
+
+  constructor() {
+    super();
+  }
+}
+"#,
+    r#"
+var _c = c;
+
+class Foo extends Bar {
+  constructor() {
+    super();
+    this.a = 1;
+    this.b = void 0;
+    this[_c] = 2;
+    this["d-e"] = 3;
+
+    _g.set(this, {
+      writable: true,
+      value: 5
+    });
+  }
+}
+
+Foo.f = 4;
+
+var _g = new WeakMap();
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| class_properties(),
+    private_destructuring_other_object_exec,
+    r#"
+class Foo {
+  #x = 1;
+
+  static assign(other, props) {
+    ({ x: other.#x } = props);
+  }
+
+  static assignArray(other, values) {
+    [, other.#x] = values;
+  }
+
+  getX() {
+    return this.#x;
+  }
+}
+
+const a = new Foo();
+const b = new Foo();
+
+Foo.assign(b, { x: 2 });
+expect(a.getX()).toBe(1);
+expect(b.getX()).toBe(2);
+
+Foo.assignArray(a, [0, 3]);
+expect(a.getX()).toBe(3);
+expect(b.getX()).toBe(2);
+"#
+);
@@ -1,6 +1,8 @@
 #![feature(test)]
 use swc_ecma_parser::{Syntax, TsConfig};
-use swc_ecma_transforms::compat::es2020::optional_chaining;
+use swc_ecma_transforms::compat::es2020::{
+    opt_chaining, optional_chaining, optional_chaining_with_config,
+};
 use swc_ecma_visit::Fold;
 
 #[macro_use]
@@ -702,3 +704,20 @@ test!(
     const patch = (ref = _obj) === null || ref === void 0 ? void 0 : ref.call(_obj);
     "
 );
+
+test!(
+    syntax(),
+    |_| optional_chaining_with_config(opt_chaining::Config {
+        no_document_all: true,
+    }),
+    no_document_all,
+    r#"
+a?.b;
+foo.bar?.baz();
+"#,
+    r#"
+var ref;
+a == null ? void 0 : a.b;
+(ref = foo.bar) == null ? void 0 : ref.baz();
+"#
+);
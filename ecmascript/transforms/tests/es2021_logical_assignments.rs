@@ -0,0 +1,143 @@
+#![feature(test)]
+use swc_common::chain;
+use swc_ecma_parser::Syntax;
+use swc_ecma_transforms::compat::{es2020::nullish_coalescing, es2021::logical_assignments};
+use swc_ecma_visit::Fold;
+
+#[macro_use]
+mod common;
+
+fn tr() -> impl Fold {
+    logical_assignments()
+}
+
+fn syntax() -> Syntax {
+    Default::default()
+}
+
+test!(
+    syntax(),
+    |_| tr(),
+    ident,
+    r#"
+a ||= b;
+a &&= b;
+a ??= b;
+"#,
+    r#"
+a || (a = b);
+a && (a = b);
+a ?? (a = b);
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    member,
+    r#"
+obj.x ||= 1;
+this.x &&= 2;
+obj.a.b ??= 3;
+"#,
+    r#"
+obj.x || (obj.x = 1);
+this.x && (this.x = 2);
+var _a;
+(_a = obj.a).b ?? (_a.b = 3);
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    computed,
+    r#"
+obj[key] ||= 1;
+obj["lit"] &&= 2;
+getObj()[getKey()] ??= 3;
+"#,
+    r#"
+obj[key] || (obj[key] = 1);
+obj["lit"] && (obj["lit"] = 2);
+var _ref, _prop;
+(_ref = getObj())[_prop = getKey()] ?? (_ref[_prop] = 3);
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    super_prop,
+    r#"
+class A extends B {
+    foo() {
+        super.x ||= 1;
+        super[key()] &&= 2;
+    }
+}
+"#,
+    r#"
+class A extends B {
+    foo() {
+        super.x || (super.x = 1);
+        var _prop;
+        super[_prop = key()] && (super[_prop] = 2);
+    }
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    nested,
+    r#"
+function f() {
+    a.b ||= c.d &&= e;
+}
+"#,
+    r#"
+function f() {
+    a.b || (a.b = c.d && (c.d = e));
+}
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(tr(), nullish_coalescing()),
+    exec_evaluates_once,
+    r#"
+let objCount = 0;
+let keyCount = 0;
+const obj = { a: 0, b: 1, c: null };
+function getObj() {
+    objCount++;
+    return obj;
+}
+function getKey(k) {
+    keyCount++;
+    return k;
+}
+
+expect(getObj()[getKey("a")] ||= 5).toBe(5);
+expect(getObj()[getKey("b")] &&= 6).toBe(6);
+expect(getObj()[getKey("c")] ??= 7).toBe(7);
+expect(getObj()[getKey("c")] ??= 8).toBe(7);
+expect(objCount).toBe(4);
+expect(keyCount).toBe(4);
+expect(obj).toEqual({ a: 5, b: 6, c: 7 });
+
+let x = 1;
+let setterCalls = 0;
+const o = {
+    get y() { return 1; },
+    set y(v) { setterCalls++; },
+};
+x ||= 2;
+o.y ||= 2;
+expect(x).toBe(1);
+expect(setterCalls).toBe(0);
+"#
+);
@@ -0,0 +1,189 @@
+#![feature(test)]
+use swc_common::chain;
+use swc_ecma_parser::{EsConfig, JscTarget, Syntax};
+use swc_ecma_transforms::{
+    compat::{es2015::classes, es2022::es2022},
+    resolver,
+};
+use swc_ecma_visit::Fold;
+
+#[macro_use]
+mod common;
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        class_private_props: true,
+        class_props: true,
+        ..Default::default()
+    })
+}
+
+fn tr() -> impl Fold {
+    chain!(resolver(), es2022())
+}
+
+test!(
+    syntax(),
+    |_| tr(),
+    static_block,
+    r#"
+class Foo {
+    static bar = 1;
+    static {
+        var baz = this.bar;
+        this.qux = () => baz + this.bar;
+    }
+}
+"#,
+    r#"
+class Foo {
+}
+_defineProperty(Foo, "bar", 1);
+(function () {
+    var baz = Foo.bar;
+    Foo.qux = () => baz + Foo.bar;
+})();
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    static_block_private,
+    r#"
+class Foo {
+    static #bar = 1;
+    static {
+        this.bar = this.#bar;
+    }
+}
+"#,
+    r#"
+class Foo {
+}
+var _bar = {
+    writable: true,
+    value: 1
+};
+(function () {
+    Foo.bar = _classStaticPrivateFieldSpecGet(Foo, Foo, _bar);
+})();
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    static_block_super,
+    r#"
+class Foo extends Bar {
+    static {
+        this.baz = super.baz;
+    }
+}
+"#,
+    r#"
+class Foo extends Bar {
+}
+(function () {
+    Foo.baz = _get(_getPrototypeOf(Foo), "baz", Foo);
+})();
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    private_in_object,
+    r#"
+class Foo {
+    #bar = 1;
+    static #baz = 2;
+    test(obj) {
+        return #bar in obj && #baz in obj;
+    }
+}
+"#,
+    r#"
+class Foo {
+    test(obj) {
+        return _bar.has(obj) && obj === Foo;
+    }
+    constructor() {
+        _bar.set(this, {
+            writable: true,
+            value: 1
+        });
+    }
+}
+var _bar = new WeakMap();
+var _baz = {
+    writable: true,
+    value: 2
+};
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(tr(), classes()),
+    exec,
+    r#"
+var order = [];
+class Foo {
+    static a = order.push("a");
+    static {
+        order.push("block");
+        this.b = this.a + 1;
+    }
+    static c = order.push("c");
+}
+expect(order).toEqual(["a", "block", "c"]);
+expect(Foo.b).toBe(2);
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(tr(), classes()),
+    exec_private_in_object,
+    r#"
+class Foo {
+    #bar = 1;
+    static #baz;
+    static isFoo(obj) {
+        return #bar in obj;
+    }
+    static isClass(obj) {
+        return #baz in obj;
+    }
+}
+expect(Foo.isFoo(new Foo())).toBe(true);
+expect(Foo.isFoo({})).toBe(false);
+expect(Foo.isClass(Foo)).toBe(true);
+expect(Foo.isClass(new Foo())).toBe(false);
+"#
+);
+
+#[test]
+fn private_method_in_object() {
+    let errors = common::test_transform_errors(
+        syntax(),
+        JscTarget::Es2020,
+        |_| tr(),
+        "
+        class Foo {
+            #bar() {}
+            static isFoo(obj) {
+                return #bar in obj;
+            }
+        }
+        ",
+    );
+
+    assert!(
+        errors.contains("brand checks for private methods are not supported yet"),
+        "{}",
+        errors
+    );
+}
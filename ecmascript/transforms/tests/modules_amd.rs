@@ -1,6 +1,6 @@
 #![feature(test)]
 use swc_common::chain;
-use swc_ecma_parser::{EsConfig, Syntax};
+use swc_ecma_parser::{EsConfig, JscTarget, Syntax};
 use swc_ecma_transforms::{
     modules::{
         amd::{amd, Config},
@@ -1200,3 +1200,22 @@ test!(
       }
   });"
 );
+
+#[test]
+fn top_level_await() {
+    let errors = common::test_transform_errors(
+        Syntax::Es(EsConfig {
+            top_level_await: true,
+            ..Default::default()
+        }),
+        JscTarget::Es2017,
+        |_| tr(Default::default()),
+        "export const a = await foo();",
+    );
+
+    assert!(
+        errors.contains("top level await is not supported by AMD modules"),
+        "{}",
+        errors
+    );
+}
@@ -1,6 +1,6 @@
 #![feature(test)]
 use swc_common::{chain, Mark};
-use swc_ecma_parser::{EsConfig, Syntax, TsConfig};
+use swc_ecma_parser::{EsConfig, JscTarget, Syntax, TsConfig};
 use swc_ecma_transforms::{
     compat,
     compat::es2020::class_properties,
@@ -4343,3 +4343,22 @@ export * from './pipes';
     });
     "#
 );
+
+#[test]
+fn top_level_await() {
+    let errors = common::test_transform_errors(
+        Syntax::Es(EsConfig {
+            top_level_await: true,
+            ..Default::default()
+        }),
+        JscTarget::Es2017,
+        |_| tr(Default::default()),
+        "export const a = await foo();",
+    );
+
+    assert!(
+        errors.contains("top level await is not supported by CommonJS modules"),
+        "{}",
+        errors
+    );
+}
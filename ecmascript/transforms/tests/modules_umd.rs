@@ -1,7 +1,7 @@
 #![feature(test)]
 use common::Tester;
 use swc_common::{chain, Mark};
-use swc_ecma_parser::{EsConfig, Syntax};
+use swc_ecma_parser::{EsConfig, JscTarget, Syntax};
 use swc_ecma_transforms::{
     modules::{
         umd::{umd, Config},
@@ -1924,3 +1924,22 @@ test!(
     ",
     ok_if_code_eq
 );
+
+#[test]
+fn top_level_await() {
+    let errors = common::test_transform_errors(
+        Syntax::Es(EsConfig {
+            top_level_await: true,
+            ..Default::default()
+        }),
+        JscTarget::Es2017,
+        |tester| tr(tester, Default::default()),
+        "export const a = await foo();",
+    );
+
+    assert!(
+        errors.contains("top level await is not supported by UMD modules"),
+        "{}",
+        errors
+    );
+}
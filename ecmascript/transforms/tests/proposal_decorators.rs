@@ -5129,3 +5129,39 @@ let Sample = _class = _dec11(_class = _dec10(_class = _dec9(((_class = class Sam
 ], Object.getOwnPropertyDescriptor(_class.prototype, "assignments"), _class.prototype), _class)) || _class) || _class) || _class;"##,
     ok_if_code_eq
 );
+
+test_exec!(
+    syntax(false),
+    |_| chain!(
+        decorators(decorators::Config {
+            legacy: true,
+            ..Default::default()
+        }),
+        class_properties(),
+    ),
+    legacy_computed_key_exec,
+    r#"
+let calls = 0;
+function key() {
+  calls++;
+  return "method";
+}
+
+const names = [];
+function dec(target, name, descriptor) {
+  names.push(name);
+  return descriptor;
+}
+
+class Foo {
+  @dec
+  [key()]() {
+    return 1;
+  }
+}
+
+expect(calls).toBe(1);
+expect(names).toEqual(["method"]);
+expect(new Foo().method()).toBe(1);
+"#
+);
@@ -29,6 +29,7 @@ mod macros;
 pub mod constructor;
 mod factory;
 pub mod ident;
+pub mod scope;
 mod value;
 pub mod var;
 
@@ -77,6 +78,54 @@ where
     visitor.found
 }
 
+/// Returns the span of the first `await` expression or `for await` loop which
+/// is not nested in a function.
+pub fn find_top_level_await<N>(body: &N) -> Option<Span>
+where
+    N: VisitWith<TopLevelAwaitFinder>,
+{
+    let mut visitor = TopLevelAwaitFinder { found: None };
+    body.visit_with(&Invalid { span: DUMMY_SP } as _, &mut visitor);
+    visitor.found
+}
+
+pub struct TopLevelAwaitFinder {
+    found: Option<Span>,
+}
+
+impl Visit for TopLevelAwaitFinder {
+    noop_visit_type!();
+
+    /// Don't recurse into arrow functions
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr, _: &dyn Node) {}
+
+    fn visit_await_expr(&mut self, n: &AwaitExpr, _: &dyn Node) {
+        if self.found.is_none() {
+            self.found = Some(n.span);
+        }
+    }
+
+    /// Don't recurse into constructor
+    fn visit_constructor(&mut self, _: &Constructor, _: &dyn Node) {}
+
+    fn visit_for_of_stmt(&mut self, n: &ForOfStmt, _: &dyn Node) {
+        if self.found.is_none() && n.await_token.is_some() {
+            self.found = Some(n.span);
+        }
+
+        n.visit_children_with(self);
+    }
+
+    /// Don't recurse into fn
+    fn visit_function(&mut self, _: &Function, _: &dyn Node) {}
+
+    /// Don't recurse into getters
+    fn visit_getter_prop(&mut self, _: &GetterProp, _: &dyn Node) {}
+
+    /// Don't recurse into setters
+    fn visit_setter_prop(&mut self, _: &SetterProp, _: &dyn Node) {}
+}
+
 pub struct IdentFinder<'a> {
     ident: &'a Ident,
     found: bool,
@@ -0,0 +1,725 @@
+//! Scope analysis.
+//!
+//! Unlike `resolver`, this does not modify the ast and does not depend on
+//! marks. Identifiers are resolved using lexical scoping of ecmascript, and
+//! `eval` and `with` are not taken into account.
+//!
+//! Declarations in blocks, including function declarations, are block-scoped
+//! like in strict mode. TypeScript types are ignored.
+
+use std::ops::Index;
+use swc_atoms::{js_word, JsWord};
+use swc_common::Span;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{noop_visit_type, Node, Visit, VisitWith};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScopeId(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BindingId(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ReferenceId(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    Module,
+    Script,
+    /// Functions, methods, getters, setters and constructors.
+    Function,
+    Arrow,
+    Class,
+    /// Block statements, `for` statements and `switch` statements.
+    Block,
+    Catch,
+}
+
+impl ScopeKind {
+    /// Returns true if `var` declarations are hoisted to this scope.
+    pub fn is_var_scope(self) -> bool {
+        matches!(
+            self,
+            ScopeKind::Module | ScopeKind::Script | ScopeKind::Function | ScopeKind::Arrow
+        )
+    }
+
+    /// Returns true for scopes of functions, which may capture bindings of
+    /// outer scopes.
+    pub fn is_closure(self) -> bool {
+        matches!(self, ScopeKind::Function | ScopeKind::Arrow)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingKind {
+    Var,
+    Let,
+    Const,
+    Function,
+    Class,
+    Param,
+    CatchParam,
+    Import,
+    /// TypeScript `enum`.
+    Enum,
+    /// TypeScript `namespace`.
+    Namespace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceKind {
+    Read,
+    Write,
+    /// Compound assignments and update expressions.
+    ReadWrite,
+}
+
+impl ReferenceKind {
+    pub fn is_read(self) -> bool {
+        self != ReferenceKind::Write
+    }
+
+    pub fn is_write(self) -> bool {
+        self != ReferenceKind::Read
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    pub span: Span,
+    pub parent: Option<ScopeId>,
+    pub children: Vec<ScopeId>,
+    /// Bindings declared in this scope, in the order of declaration.
+    pub bindings: Vec<BindingId>,
+    /// References appearing directly in this scope.
+    pub references: Vec<ReferenceId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub sym: JsWord,
+    pub kind: BindingKind,
+    /// Span of the identifier declaring this binding.
+    pub span: Span,
+    pub scope: ScopeId,
+    pub references: Vec<ReferenceId>,
+    /// Closures referencing this binding, which is declared outside of them.
+    ///
+    /// If a closure is nested in another closure, both of them capture the
+    /// binding.
+    pub captured_by: Vec<ScopeId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Reference {
+    pub sym: JsWord,
+    pub kind: ReferenceKind,
+    pub span: Span,
+    pub scope: ScopeId,
+    /// `None` for globals and implicit `arguments`.
+    pub binding: Option<BindingId>,
+}
+
+/// Scopes, bindings and references of a [Program].
+#[derive(Debug, Clone)]
+pub struct ScopeAnalysis {
+    pub scopes: Vec<Scope>,
+    pub bindings: Vec<Binding>,
+    pub references: Vec<Reference>,
+    /// References which are not resolved to a binding.
+    pub unresolved: Vec<ReferenceId>,
+}
+
+impl ScopeAnalysis {
+    pub fn analyze(program: &Program) -> Self {
+        let (kind, span) = match program {
+            Program::Module(m) => (ScopeKind::Module, m.span),
+            Program::Script(s) => (ScopeKind::Script, s.span),
+        };
+
+        let mut a = Analyzer {
+            data: ScopeAnalysis {
+                scopes: vec![],
+                bindings: vec![],
+                references: vec![],
+                unresolved: vec![],
+            },
+            cur: ScopeId(0),
+        };
+        a.push_scope(kind, span);
+        program.visit_children_with(&mut a);
+
+        let mut data = a.data;
+        data.resolve();
+        data
+    }
+
+    /// The scope of the program.
+    pub fn root(&self) -> ScopeId {
+        ScopeId(0)
+    }
+
+    /// Finds a binding named `sym` visible from `scope`.
+    pub fn lookup(&self, mut scope: ScopeId, sym: &JsWord) -> Option<BindingId> {
+        loop {
+            let s = &self[scope];
+            // Later declarations of a same name in a scope are redeclarations.
+            if let Some(&id) = s.bindings.iter().find(|&&id| self[id].sym == *sym) {
+                return Some(id);
+            }
+            scope = s.parent?;
+        }
+    }
+
+    /// Names of unresolved references, sorted and deduplicated.
+    pub fn globals(&self) -> Vec<JsWord> {
+        let mut globals = self
+            .unresolved
+            .iter()
+            .map(|&id| self[id].sym.clone())
+            .collect::<Vec<_>>();
+        globals.sort();
+        globals.dedup();
+        globals
+    }
+
+    /// Returns true if `scope` is `ancestor` or a descendant of it.
+    pub fn is_inside(&self, mut scope: ScopeId, ancestor: ScopeId) -> bool {
+        loop {
+            if scope == ancestor {
+                return true;
+            }
+            scope = match self[scope].parent {
+                Some(v) => v,
+                None => return false,
+            };
+        }
+    }
+
+    fn resolve(&mut self) {
+        for idx in 0..self.references.len() {
+            let id = ReferenceId(idx as u32);
+            let scope = self.references[idx].scope;
+            let binding = self.lookup(scope, &self.references[idx].sym);
+            self.references[idx].binding = binding;
+
+            let binding = match binding {
+                Some(v) => v,
+                None => {
+                    if !self.is_implicit_arguments(id) {
+                        self.unresolved.push(id);
+                    }
+                    continue;
+                }
+            };
+
+            let decl_scope = self[binding].scope;
+            let mut closures = vec![];
+            let mut cur = scope;
+            while cur != decl_scope {
+                if self[cur].kind.is_closure() {
+                    closures.push(cur);
+                }
+                cur = self[cur]
+                    .parent
+                    .expect("binding is not visible from reference");
+            }
+
+            let binding = &mut self.bindings[binding.0 as usize];
+            binding.references.push(id);
+            for closure in closures {
+                if !binding.captured_by.contains(&closure) {
+                    binding.captured_by.push(closure);
+                }
+            }
+        }
+    }
+
+    fn is_implicit_arguments(&self, id: ReferenceId) -> bool {
+        if self[id].sym != js_word!("arguments") {
+            return false;
+        }
+
+        let mut scope = Some(self[id].scope);
+        while let Some(s) = scope {
+            if self[s].kind == ScopeKind::Function {
+                return true;
+            }
+            scope = self[s].parent;
+        }
+        false
+    }
+}
+
+impl Index<ScopeId> for ScopeAnalysis {
+    type Output = Scope;
+
+    fn index(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id.0 as usize]
+    }
+}
+
+impl Index<BindingId> for ScopeAnalysis {
+    type Output = Binding;
+
+    fn index(&self, id: BindingId) -> &Binding {
+        &self.bindings[id.0 as usize]
+    }
+}
+
+impl Index<ReferenceId> for ScopeAnalysis {
+    type Output = Reference;
+
+    fn index(&self, id: ReferenceId) -> &Reference {
+        &self.references[id.0 as usize]
+    }
+}
+
+#[derive(Clone, Copy)]
+enum PatMode {
+    Bind(BindingKind),
+    Write(ReferenceKind),
+}
+
+struct Analyzer {
+    data: ScopeAnalysis,
+    cur: ScopeId,
+}
+
+impl Analyzer {
+    fn push_scope(&mut self, kind: ScopeKind, span: Span) -> ScopeId {
+        let id = ScopeId(self.data.scopes.len() as u32);
+        let parent = if self.data.scopes.is_empty() {
+            None
+        } else {
+            self.data.scopes[self.cur.0 as usize].children.push(id);
+            Some(self.cur)
+        };
+
+        self.data.scopes.push(Scope {
+            kind,
+            span,
+            parent,
+            children: vec![],
+            bindings: vec![],
+            references: vec![],
+        });
+        self.cur = id;
+        id
+    }
+
+    fn with_scope<F>(&mut self, kind: ScopeKind, span: Span, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let old = self.cur;
+        self.push_scope(kind, span);
+        op(self);
+        self.cur = old;
+    }
+
+    fn declare(&mut self, i: &Ident, kind: BindingKind) {
+        let mut scope = self.cur;
+        if kind == BindingKind::Var {
+            while !self.data[scope].kind.is_var_scope() {
+                scope = self.data[scope].parent.unwrap();
+            }
+        }
+
+        let id = BindingId(self.data.bindings.len() as u32);
+        self.data.bindings.push(Binding {
+            sym: i.sym.clone(),
+            kind,
+            span: i.span,
+            scope,
+            references: vec![],
+            captured_by: vec![],
+        });
+        self.data.scopes[scope.0 as usize].bindings.push(id);
+    }
+
+    fn reference(&mut self, i: &Ident, kind: ReferenceKind) {
+        let id = ReferenceId(self.data.references.len() as u32);
+        self.data.references.push(Reference {
+            sym: i.sym.clone(),
+            kind,
+            span: i.span,
+            scope: self.cur,
+            binding: None,
+        });
+        self.data.scopes[self.cur.0 as usize].references.push(id);
+    }
+
+    fn visit_pat_with_mode(&mut self, p: &Pat, mode: PatMode) {
+        match p {
+            Pat::Ident(i) => match mode {
+                PatMode::Bind(kind) => self.declare(i, kind),
+                PatMode::Write(kind) => self.reference(i, kind),
+            },
+            Pat::Array(a) => {
+                for elem in a.elems.iter().flatten() {
+                    self.visit_pat_with_mode(elem, mode);
+                }
+            }
+            Pat::Rest(r) => self.visit_pat_with_mode(&r.arg, mode),
+            Pat::Object(o) => {
+                for prop in &o.props {
+                    match prop {
+                        ObjectPatProp::KeyValue(p) => {
+                            p.key.visit_with(p as _, self);
+                            self.visit_pat_with_mode(&p.value, mode);
+                        }
+                        ObjectPatProp::Assign(p) => {
+                            match mode {
+                                PatMode::Bind(kind) => self.declare(&p.key, kind),
+                                PatMode::Write(kind) => self.reference(&p.key, kind),
+                            }
+                            if let Some(value) = &p.value {
+                                value.visit_with(p as _, self);
+                            }
+                        }
+                        ObjectPatProp::Rest(r) => self.visit_pat_with_mode(&r.arg, mode),
+                    }
+                }
+            }
+            Pat::Assign(a) => {
+                self.visit_pat_with_mode(&a.left, mode);
+                a.right.visit_with(a as _, self);
+            }
+            Pat::Expr(e) => match (&**e, mode) {
+                (Expr::Ident(i), PatMode::Write(kind)) => self.reference(i, kind),
+                _ => e.visit_with(p as _, self),
+            },
+            Pat::Invalid(..) => {}
+        }
+    }
+
+    /// Visits an assignment target.
+    fn visit_target(&mut self, target: &PatOrExpr, kind: ReferenceKind) {
+        match target {
+            PatOrExpr::Expr(e) => match &**e {
+                Expr::Ident(i) => self.reference(i, kind),
+                _ => e.visit_with(target as _, self),
+            },
+            PatOrExpr::Pat(p) => self.visit_pat_with_mode(p, PatMode::Write(kind)),
+        }
+    }
+
+    /// Visits a function with an optional name, which is declared in the
+    /// scope of the function.
+    fn visit_fn(&mut self, name: Option<&Ident>, f: &Function) {
+        f.decorators.visit_with(f as _, self);
+
+        self.with_scope(ScopeKind::Function, f.span, |a| {
+            if let Some(name) = name {
+                a.declare(name, BindingKind::Function);
+            }
+            for param in &f.params {
+                param.decorators.visit_with(param as _, a);
+                a.visit_pat_with_mode(&param.pat, PatMode::Bind(BindingKind::Param));
+            }
+            if let Some(body) = &f.body {
+                body.stmts.visit_with(body as _, a);
+            }
+        });
+    }
+
+    fn visit_class_with_name(&mut self, name: Option<&Ident>, c: &Class) {
+        c.decorators.visit_with(c as _, self);
+
+        self.with_scope(ScopeKind::Class, c.span, |a| {
+            if let Some(name) = name {
+                a.declare(name, BindingKind::Class);
+            }
+            c.super_class.visit_with(c as _, a);
+            c.body.visit_with(c as _, a);
+        });
+    }
+}
+
+impl Visit for Analyzer {
+    noop_visit_type!();
+
+    /// Identifiers are handled by their parents, because most of them are not
+    /// references.
+    fn visit_ident(&mut self, _: &Ident, _: &dyn Node) {}
+
+    fn visit_expr(&mut self, e: &Expr, _: &dyn Node) {
+        match e {
+            Expr::Ident(i) => self.reference(i, ReferenceKind::Read),
+            _ => e.visit_children_with(self),
+        }
+    }
+
+    fn visit_member_expr(&mut self, e: &MemberExpr, _: &dyn Node) {
+        e.obj.visit_with(e as _, self);
+        if e.computed {
+            e.prop.visit_with(e as _, self);
+        }
+    }
+
+    fn visit_prop(&mut self, p: &Prop, _: &dyn Node) {
+        match p {
+            Prop::Shorthand(i) => self.reference(i, ReferenceKind::Read),
+            _ => p.visit_children_with(self),
+        }
+    }
+
+    fn visit_assign_expr(&mut self, e: &AssignExpr, _: &dyn Node) {
+        let kind = match e.op {
+            AssignOp::Assign => ReferenceKind::Write,
+            _ => ReferenceKind::ReadWrite,
+        };
+        self.visit_target(&e.left, kind);
+        e.right.visit_with(e as _, self);
+    }
+
+    fn visit_update_expr(&mut self, e: &UpdateExpr, _: &dyn Node) {
+        match &*e.arg {
+            Expr::Ident(i) => self.reference(i, ReferenceKind::ReadWrite),
+            _ => e.arg.visit_with(e as _, self),
+        }
+    }
+
+    fn visit_pat(&mut self, p: &Pat, _: &dyn Node) {
+        self.visit_pat_with_mode(p, PatMode::Write(ReferenceKind::Write));
+    }
+
+    fn visit_var_decl(&mut self, v: &VarDecl, _: &dyn Node) {
+        let kind = match v.kind {
+            VarDeclKind::Var => BindingKind::Var,
+            VarDeclKind::Let => BindingKind::Let,
+            VarDeclKind::Const => BindingKind::Const,
+        };
+
+        for decl in &v.decls {
+            self.visit_pat_with_mode(&decl.name, PatMode::Bind(kind));
+            decl.init.visit_with(decl as _, self);
+        }
+    }
+
+    fn visit_fn_decl(&mut self, f: &FnDecl, _: &dyn Node) {
+        self.declare(&f.ident, BindingKind::Function);
+        self.visit_fn(None, &f.function);
+    }
+
+    fn visit_fn_expr(&mut self, f: &FnExpr, _: &dyn Node) {
+        self.visit_fn(f.ident.as_ref(), &f.function);
+    }
+
+    fn visit_function(&mut self, f: &Function, _: &dyn Node) {
+        self.visit_fn(None, f);
+    }
+
+    fn visit_arrow_expr(&mut self, f: &ArrowExpr, _: &dyn Node) {
+        self.with_scope(ScopeKind::Arrow, f.span, |a| {
+            for param in &f.params {
+                a.visit_pat_with_mode(param, PatMode::Bind(BindingKind::Param));
+            }
+            match &f.body {
+                BlockStmtOrExpr::BlockStmt(body) => body.stmts.visit_with(body as _, a),
+                BlockStmtOrExpr::Expr(body) => body.visit_with(f as _, a),
+            }
+        });
+    }
+
+    fn visit_getter_prop(&mut self, p: &GetterProp, _: &dyn Node) {
+        p.key.visit_with(p as _, self);
+        self.with_scope(ScopeKind::Function, p.span, |a| {
+            if let Some(body) = &p.body {
+                body.stmts.visit_with(body as _, a);
+            }
+        });
+    }
+
+    fn visit_setter_prop(&mut self, p: &SetterProp, _: &dyn Node) {
+        p.key.visit_with(p as _, self);
+        self.with_scope(ScopeKind::Function, p.span, |a| {
+            a.visit_pat_with_mode(&p.param, PatMode::Bind(BindingKind::Param));
+            if let Some(body) = &p.body {
+                body.stmts.visit_with(body as _, a);
+            }
+        });
+    }
+
+    fn visit_constructor(&mut self, c: &Constructor, _: &dyn Node) {
+        self.with_scope(ScopeKind::Function, c.span, |a| {
+            for param in &c.params {
+                match param {
+                    ParamOrTsParamProp::Param(p) => {
+                        p.decorators.visit_with(p as _, a);
+                        a.visit_pat_with_mode(&p.pat, PatMode::Bind(BindingKind::Param));
+                    }
+                    ParamOrTsParamProp::TsParamProp(p) => {
+                        p.decorators.visit_with(p as _, a);
+                        match &p.param {
+                            TsParamPropParam::Ident(i) => a.declare(i, BindingKind::Param),
+                            TsParamPropParam::Assign(pat) => {
+                                a.visit_pat_with_mode(&pat.left, PatMode::Bind(BindingKind::Param));
+                                pat.right.visit_with(pat as _, a);
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(body) = &c.body {
+                body.stmts.visit_with(body as _, a);
+            }
+        });
+    }
+
+    fn visit_class_prop(&mut self, p: &ClassProp, _: &dyn Node) {
+        p.decorators.visit_with(p as _, self);
+        if p.computed {
+            p.key.visit_with(p as _, self);
+        }
+        p.value.visit_with(p as _, self);
+    }
+
+    fn visit_class_decl(&mut self, c: &ClassDecl, _: &dyn Node) {
+        self.declare(&c.ident, BindingKind::Class);
+        self.visit_class_with_name(None, &c.class);
+    }
+
+    fn visit_class_expr(&mut self, c: &ClassExpr, _: &dyn Node) {
+        self.visit_class_with_name(c.ident.as_ref(), &c.class);
+    }
+
+    fn visit_class(&mut self, c: &Class, _: &dyn Node) {
+        self.visit_class_with_name(None, c);
+    }
+
+    fn visit_block_stmt(&mut self, b: &BlockStmt, _: &dyn Node) {
+        self.with_scope(ScopeKind::Block, b.span, |a| {
+            b.stmts.visit_with(b as _, a);
+        });
+    }
+
+    fn visit_for_stmt(&mut self, s: &ForStmt, _: &dyn Node) {
+        self.with_scope(ScopeKind::Block, s.span, |a| s.visit_children_with(a));
+    }
+
+    fn visit_for_in_stmt(&mut self, s: &ForInStmt, _: &dyn Node) {
+        self.with_scope(ScopeKind::Block, s.span, |a| s.visit_children_with(a));
+    }
+
+    fn visit_for_of_stmt(&mut self, s: &ForOfStmt, _: &dyn Node) {
+        self.with_scope(ScopeKind::Block, s.span, |a| s.visit_children_with(a));
+    }
+
+    fn visit_switch_stmt(&mut self, s: &SwitchStmt, _: &dyn Node) {
+        s.discriminant.visit_with(s as _, self);
+        self.with_scope(ScopeKind::Block, s.span, |a| {
+            s.cases.visit_with(s as _, a);
+        });
+    }
+
+    fn visit_catch_clause(&mut self, c: &CatchClause, _: &dyn Node) {
+        self.with_scope(ScopeKind::Catch, c.span, |a| {
+            if let Some(param) = &c.param {
+                a.visit_pat_with_mode(param, PatMode::Bind(BindingKind::CatchParam));
+            }
+            c.body.visit_with(c as _, a);
+        });
+    }
+
+    fn visit_export_default_decl(&mut self, e: &ExportDefaultDecl, _: &dyn Node) {
+        // Names of default exports are declared in the module scope.
+        match &e.decl {
+            DefaultDecl::Class(c) => {
+                if let Some(i) = &c.ident {
+                    self.declare(i, BindingKind::Class);
+                }
+                self.visit_class_with_name(None, &c.class);
+            }
+            DefaultDecl::Fn(f) => {
+                if let Some(i) = &f.ident {
+                    self.declare(i, BindingKind::Function);
+                }
+                self.visit_fn(None, &f.function);
+            }
+            DefaultDecl::TsInterfaceDecl(..) => {}
+        }
+    }
+
+    fn visit_import_specifier(&mut self, s: &ImportSpecifier, _: &dyn Node) {
+        let local = match s {
+            ImportSpecifier::Named(s) => &s.local,
+            ImportSpecifier::Default(s) => &s.local,
+            ImportSpecifier::Namespace(s) => &s.local,
+        };
+        self.declare(local, BindingKind::Import);
+    }
+
+    fn visit_named_export(&mut self, e: &NamedExport, _: &dyn Node) {
+        // Re-exports don't reference local bindings.
+        if e.src.is_some() {
+            return;
+        }
+
+        for s in &e.specifiers {
+            if let ExportSpecifier::Named(s) = s {
+                self.reference(&s.orig, ReferenceKind::Read);
+            }
+        }
+    }
+
+    fn visit_jsx_element_name(&mut self, n: &JSXElementName, _: &dyn Node) {
+        match n {
+            // Lowercase names are intrinsic elements.
+            JSXElementName::Ident(i) if !i.sym.starts_with(|c: char| c.is_ascii_lowercase()) => {
+                self.reference(i, ReferenceKind::Read)
+            }
+            JSXElementName::JSXMemberExpr(e) => e.visit_with(n as _, self),
+            _ => {}
+        }
+    }
+
+    fn visit_jsx_object(&mut self, o: &JSXObject, _: &dyn Node) {
+        match o {
+            JSXObject::Ident(i) => self.reference(i, ReferenceKind::Read),
+            JSXObject::JSXMemberExpr(e) => e.obj.visit_with(o as _, self),
+        }
+    }
+
+    fn visit_jsx_member_expr(&mut self, e: &JSXMemberExpr, _: &dyn Node) {
+        e.obj.visit_with(e as _, self);
+    }
+
+    fn visit_decl(&mut self, d: &Decl, _: &dyn Node) {
+        match d {
+            Decl::TsEnum(e) => {
+                self.declare(&e.id, BindingKind::Enum);
+                for member in &e.members {
+                    member.init.visit_with(member as _, self);
+                }
+            }
+            Decl::TsModule(m) => {
+                if let TsModuleName::Ident(i) = &m.id {
+                    self.declare(i, BindingKind::Namespace);
+                }
+            }
+            _ => d.visit_children_with(self),
+        }
+    }
+
+    fn visit_module_decl(&mut self, d: &ModuleDecl, _: &dyn Node) {
+        match d {
+            ModuleDecl::TsImportEquals(d) => {
+                self.declare(&d.id, BindingKind::Import);
+                if let TsModuleRef::TsEntityName(name) = &d.module_ref {
+                    let mut name = name;
+                    while let TsEntityName::TsQualifiedName(q) = name {
+                        name = &q.left;
+                    }
+                    if let TsEntityName::Ident(i) = name {
+                        self.reference(i, ReferenceKind::Read);
+                    }
+                }
+            }
+            _ => d.visit_children_with(self),
+        }
+    }
+}
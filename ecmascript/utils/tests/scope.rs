@@ -0,0 +1,233 @@
+use swc_atoms::JsWord;
+use swc_common::FileName;
+use swc_ecma_ast::Program;
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+use swc_ecma_utils::scope::{BindingKind, ReferenceKind, ScopeAnalysis, ScopeKind};
+
+fn analyze(syntax: Syntax, src: &str) -> ScopeAnalysis {
+    testing::run_test(false, |cm, handler| {
+        let fm = cm.new_source_file(FileName::Anon, src.into());
+        let lexer = Lexer::new(syntax, Default::default(), StringInput::from(&*fm), None);
+        let mut parser = Parser::new_from(lexer);
+        let module = parser
+            .parse_module()
+            .map_err(|err| err.into_diagnostic(handler).emit())?;
+
+        Ok(ScopeAnalysis::analyze(&Program::Module(module)))
+    })
+    .unwrap()
+}
+
+fn js(src: &str) -> ScopeAnalysis {
+    analyze(Default::default(), src)
+}
+
+/// Returns `(kind, scope kind)` of the binding named `sym`.
+fn binding(a: &ScopeAnalysis, sym: &str) -> (BindingKind, ScopeKind) {
+    let b = a
+        .bindings
+        .iter()
+        .find(|b| &*b.sym == sym)
+        .unwrap_or_else(|| panic!("binding `{}` not found", sym));
+    (b.kind, a[b.scope].kind)
+}
+
+fn refs(a: &ScopeAnalysis, sym: &str) -> Vec<ReferenceKind> {
+    let b = a.bindings.iter().find(|b| &*b.sym == sym).unwrap();
+    b.references.iter().map(|&r| a[r].kind).collect()
+}
+
+fn globals(a: &ScopeAnalysis) -> Vec<JsWord> {
+    a.globals()
+}
+
+#[test]
+fn declarations() {
+    let a = js("import def, { named as local } from 'a';
+        var v = 1;
+        let l = 2;
+        function f(p, { q, r: [s] }, ...rest) {
+            var inner;
+            if (p) { let block; var hoisted; }
+        }
+        class C {}
+        try {} catch ({ message }) {}
+        for (let i = 0; i < 1; i++) {}
+        export default function named() {}");
+
+    assert_eq!(binding(&a, "def"), (BindingKind::Import, ScopeKind::Module));
+    assert_eq!(
+        binding(&a, "local"),
+        (BindingKind::Import, ScopeKind::Module)
+    );
+    assert_eq!(binding(&a, "v"), (BindingKind::Var, ScopeKind::Module));
+    assert_eq!(binding(&a, "l"), (BindingKind::Let, ScopeKind::Module));
+    assert_eq!(binding(&a, "f"), (BindingKind::Function, ScopeKind::Module));
+    assert_eq!(binding(&a, "p"), (BindingKind::Param, ScopeKind::Function));
+    assert_eq!(binding(&a, "q"), (BindingKind::Param, ScopeKind::Function));
+    assert_eq!(binding(&a, "s"), (BindingKind::Param, ScopeKind::Function));
+    assert_eq!(
+        binding(&a, "rest"),
+        (BindingKind::Param, ScopeKind::Function)
+    );
+    assert_eq!(
+        binding(&a, "inner"),
+        (BindingKind::Var, ScopeKind::Function)
+    );
+    assert_eq!(binding(&a, "block"), (BindingKind::Let, ScopeKind::Block));
+    assert_eq!(
+        binding(&a, "hoisted"),
+        (BindingKind::Var, ScopeKind::Function)
+    );
+    assert_eq!(binding(&a, "C"), (BindingKind::Class, ScopeKind::Module));
+    assert_eq!(
+        binding(&a, "message"),
+        (BindingKind::CatchParam, ScopeKind::Catch)
+    );
+    assert_eq!(binding(&a, "i"), (BindingKind::Let, ScopeKind::Block));
+    assert_eq!(
+        binding(&a, "named"),
+        (BindingKind::Function, ScopeKind::Module)
+    );
+    assert!(a.bindings.iter().all(|b| &*b.sym != "r"));
+}
+
+#[test]
+fn references() {
+    let a = js("let a = 1, b = 2, c = 3;
+        a = b;
+        c += 1;
+        b++;
+        [a, { b: c }] = [];
+        ({ a, [b]: c });
+        a.b.c;
+        obj[c];
+        export { a as renamed };");
+
+    assert_eq!(
+        refs(&a, "a"),
+        vec![
+            ReferenceKind::Write,
+            ReferenceKind::Write,
+            ReferenceKind::Read,
+            ReferenceKind::Read,
+            ReferenceKind::Read
+        ]
+    );
+    assert_eq!(
+        refs(&a, "b"),
+        vec![
+            ReferenceKind::Read,
+            ReferenceKind::ReadWrite,
+            ReferenceKind::Read
+        ]
+    );
+    assert_eq!(
+        refs(&a, "c"),
+        vec![
+            ReferenceKind::ReadWrite,
+            ReferenceKind::Write,
+            ReferenceKind::Read,
+            ReferenceKind::Read
+        ]
+    );
+    assert_eq!(globals(&a), vec![JsWord::from("obj")]);
+}
+
+#[test]
+fn shadowing_and_hoisting() {
+    let a = js("use(x, later);
+        let x = 1;
+        function later() {
+            let x = 2;
+            return x;
+        }");
+
+    let outer = a
+        .bindings
+        .iter()
+        .position(|b| &*b.sym == "x" && a[b.scope].kind == ScopeKind::Module)
+        .unwrap();
+    let inner = a
+        .bindings
+        .iter()
+        .position(|b| &*b.sym == "x" && a[b.scope].kind == ScopeKind::Function)
+        .unwrap();
+    assert_eq!(a.bindings[outer].references.len(), 1);
+    assert_eq!(a.bindings[inner].references.len(), 1);
+    assert_eq!(refs(&a, "later").len(), 1);
+    assert_eq!(globals(&a), vec![JsWord::from("use")]);
+}
+
+#[test]
+fn unresolved_globals() {
+    let a = js("console.log(window.document, foo);
+        function f() { return arguments.length; }
+        const g = () => arguments;
+        label: for (;;) { break label; }
+        const o = { key: 1, method() {} };
+        class K { prop = value; method() { return this.prop; } }");
+
+    assert_eq!(
+        globals(&a),
+        vec![
+            JsWord::from("arguments"),
+            JsWord::from("console"),
+            JsWord::from("foo"),
+            JsWord::from("value"),
+            JsWord::from("window"),
+        ]
+    );
+}
+
+#[test]
+fn closures() {
+    let a = js("let count = 0;
+        let unused = 0;
+        function outer() {
+            count;
+            return () => {
+                const local = 1;
+                return () => count + local;
+            };
+        }");
+
+    let count = a.bindings.iter().find(|b| &*b.sym == "count").unwrap();
+    let kinds = count
+        .captured_by
+        .iter()
+        .map(|&s| a[s].kind)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![ScopeKind::Function, ScopeKind::Arrow, ScopeKind::Arrow]
+    );
+
+    let local = a.bindings.iter().find(|b| &*b.sym == "local").unwrap();
+    assert_eq!(local.captured_by.len(), 1);
+    assert!(a.is_inside(local.captured_by[0], local.scope));
+
+    let unused = a.bindings.iter().find(|b| &*b.sym == "unused").unwrap();
+    assert!(unused.captured_by.is_empty());
+    assert!(unused.references.is_empty());
+}
+
+#[test]
+fn jsx_and_typescript() {
+    let a = analyze(
+        Syntax::Typescript(TsConfig {
+            tsx: true,
+            ..Default::default()
+        }),
+        "import React from 'react';
+        import { Comp } from './comp';
+        enum E { A = 1 }
+        const value: SomeType = E.A;
+        const el = <div><Comp.Item value={value} /></div>;",
+    );
+
+    assert_eq!(refs(&a, "Comp"), vec![ReferenceKind::Read]);
+    assert_eq!(refs(&a, "E"), vec![ReferenceKind::Read]);
+    assert_eq!(refs(&a, "value"), vec![ReferenceKind::Read]);
+    assert!(globals(&a).is_empty(), "{:?}", globals(&a));
+}
@@ -437,6 +437,7 @@ define!({
         PrivateProp(PrivateProp),
         TsIndexSignature(TsIndexSignature),
         Empty(EmptyStmt),
+        StaticBlock(StaticBlock),
     }
 
     pub struct ClassProp {
@@ -496,6 +497,10 @@ define!({
         pub accessibility: Option<Accessibility>,
         pub is_optional: bool,
     }
+    pub struct StaticBlock {
+        pub span: Span,
+        pub body: BlockStmt,
+    }
     pub struct Decorator {
         pub span: Span,
         pub expr: Box<Expr>,
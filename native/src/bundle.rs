@@ -2,18 +2,18 @@ use crate::{
     get_compiler,
     util::{CtxtExt, MapErr},
 };
-use anyhow::{bail, Error};
+use anyhow::Error;
 use fxhash::FxHashMap;
 use napi::{CallContext, Env, JsObject, Status, Task};
 use serde::Deserialize;
-use spack::resolvers::NodeResolver;
+use spack::{emitter::Emitter, resolvers::NodeResolver};
 use std::{
     panic::{catch_unwind, AssertUnwindSafe},
     sync::Arc,
 };
-use swc::{config::SourceMapsConfig, Compiler, TransformOutput};
+use swc::{Compiler, TransformOutput};
 use swc_atoms::js_word;
-use swc_bundler::{BundleKind, Bundler, Load, ModuleRecord, Resolve};
+use swc_bundler::{Bundler, Load, ModuleRecord, Resolve};
 use swc_common::Span;
 use swc_ecma_ast::{
     Bool, Expr, ExprOrSuper, Ident, KeyValueProp, Lit, MemberExpr, MetaPropExpr, PropName, Str,
@@ -99,41 +99,21 @@ impl Task for BundleTask {
                 .bundle(self.config.static_items.config.entry.clone().into())
                 .convert_err()?;
 
-            let result = result
+            let emitter = Emitter::new(&self.swc, &self.config.static_items.config);
+            let result = emitter
+                .print(result)
+                .convert_err()?
                 .into_iter()
-                .map(|bundle| match bundle.kind {
-                    BundleKind::Named { name } | BundleKind::Lib { name } => {
-                        Ok((name, bundle.module))
-                    }
-                    BundleKind::Dynamic => bail!("unimplemented: dynamic code splitting"),
+                .map(|bundle| {
+                    (
+                        bundle.name,
+                        TransformOutput {
+                            code: bundle.code,
+                            map: bundle.map,
+                        },
+                    )
                 })
-                .map(|res| {
-                    res.and_then(|(k, m)| {
-                        // TODO: Source map
-                        let minify = self
-                            .config
-                            .static_items
-                            .config
-                            .options
-                            .as_ref()
-                            .map(|v| {
-                                v.config
-                                    .as_ref()
-                                    .map(|v| v.minify)
-                                    .flatten()
-                                    .unwrap_or(false)
-                            })
-                            .unwrap_or(false);
-
-                        let output =
-                            self.swc
-                                .print(&m, SourceMapsConfig::Bool(true), None, minify)?;
-
-                        Ok((k, output))
-                    })
-                })
-                .collect::<Result<_, _>>()
-                .convert_err()?;
+                .collect();
 
             Ok(result)
         }));
@@ -162,7 +142,7 @@ impl Task for BundleTask {
 }
 
 #[js_function(1)]
-pub(crate) fn bundle(cx: CallContext) -> napi::Result<JsObject> {
+pub(crate) fn bundle(cx: CallContext<JsObject>) -> napi::Result<JsObject> {
     let c: Arc<Compiler> = get_compiler(&cx);
 
     let static_items: StaticConfigItem = cx.get_deserialized(0)?;
@@ -179,11 +159,16 @@ pub(crate) fn bundle(cx: CallContext) -> napi::Result<JsObject> {
             }),
     ));
 
+    let resolver = match &static_items.config.resolve {
+        Some(resolve) => NodeResolver::from_config(resolve),
+        None => NodeResolver::new(),
+    };
+
     cx.env.spawn(BundleTask {
         swc: c.clone(),
         config: ConfigItem {
             loader,
-            resolver: Box::new(NodeResolver::new()) as Box<_>,
+            resolver: Box::new(resolver) as Box<_>,
             static_items,
         },
     })
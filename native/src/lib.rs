@@ -13,13 +13,14 @@ static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
 #[global_allocator]
 static ALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
+use crate::util::WarningEmitter;
 use backtrace::Backtrace;
-use napi::{CallContext, Env, JsFunction, JsObject, JsUndefined, Module};
+use napi::{CallContext, Env, JsFunction, JsObject, JsUndefined, Module, Property};
 use std::{env, panic::set_hook, sync::Arc};
 use swc::{Compiler, TransformOutput};
 use swc_common::{
     self,
-    errors::{ColorConfig, Handler},
+    errors::{ColorConfig, EmitterWriter, Handler},
     sync::Lazy,
     FilePathMapping, SourceMap,
 };
@@ -34,17 +35,25 @@ mod util;
 // #[global_allocator]
 // static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
 
-static COMPILER: Lazy<Arc<Compiler>> = Lazy::new(|| {
+/// Used when a binding function is not called on an instance of `Compiler`.
+static COMPILER: Lazy<Arc<Compiler>> = Lazy::new(new_compiler);
+
+/// Creates a compiler with its own [SourceMap] and comment storage.
+fn new_compiler() -> Arc<Compiler> {
     let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
-    let handler = Arc::new(Handler::with_tty_emitter(
-        ColorConfig::Always,
+    let handler = Arc::new(Handler::with_emitter(
         true,
         false,
-        Some(cm.clone()),
+        Box::new(WarningEmitter(EmitterWriter::stderr(
+            ColorConfig::Auto,
+            Some(cm.clone()),
+            false,
+            false,
+        ))),
     ));
 
     Arc::new(Compiler::new(cm.clone(), handler))
-});
+}
 
 register_module!(swc, init);
 
@@ -62,6 +71,7 @@ fn init(m: &mut Module) -> napi::Result<()> {
     m.create_named_method("transformSync", transform::transform_sync)?;
     m.create_named_method("transformFile", transform::transform_file)?;
     m.create_named_method("transformFileSync", transform::transform_file_sync)?;
+    m.create_named_method("transformFiles", transform::transform_files)?;
 
     m.create_named_method("parse", parse::parse)?;
     m.create_named_method("parseSync", parse::parse_sync)?;
@@ -76,19 +86,56 @@ fn init(m: &mut Module) -> napi::Result<()> {
     Ok(())
 }
 
-fn get_compiler(_ctx: &CallContext) -> Arc<Compiler> {
-    COMPILER.clone()
+/// Returns the compiler wrapped by `this`, or the global compiler if `this` is
+/// not an instance of `Compiler`.
+fn get_compiler(ctx: &CallContext<JsObject>) -> Arc<Compiler> {
+    match ctx.env.unwrap::<ArcCompiler>(&ctx.this) {
+        Ok(c) => c.clone(),
+        Err(..) => COMPILER.clone(),
+    }
 }
 
 #[js_function]
 fn define_compiler_class(ctx: CallContext) -> napi::Result<JsFunction> {
-    ctx.env
-        .define_class("Compiler", construct_compiler, &vec![])
+    let env = ctx.env;
+
+    ctx.env.define_class(
+        "Compiler",
+        construct_compiler,
+        &[
+            Property::new(env, "transform")?.with_method(transform::transform),
+            Property::new(env, "transformSync")?.with_method(transform::transform_sync),
+            Property::new(env, "transformFile")?.with_method(transform::transform_file),
+            Property::new(env, "transformFileSync")?.with_method(transform::transform_file_sync),
+            Property::new(env, "transformFiles")?.with_method(transform::transform_files),
+            Property::new(env, "parse")?.with_method(parse::parse),
+            Property::new(env, "parseSync")?.with_method(parse::parse_sync),
+            Property::new(env, "parseFile")?.with_method(parse::parse_file),
+            Property::new(env, "parseFileSync")?.with_method(parse::parse_file_sync),
+            Property::new(env, "print")?.with_method(print::print),
+            Property::new(env, "printSync")?.with_method(print::print_sync),
+            Property::new(env, "bundle")?.with_method(bundle::bundle),
+            Property::new(env, "reset")?.with_method(reset_compiler),
+        ],
+    )
 }
 
 #[js_function]
-fn construct_compiler(ctx: CallContext<JsObject>) -> napi::Result<JsUndefined> {
-    // TODO: Assign swc::Compiler
+fn construct_compiler(mut ctx: CallContext<JsObject>) -> napi::Result<JsUndefined> {
+    ctx.env.wrap(&mut ctx.this, new_compiler())?;
+
+    ctx.env.get_undefined()
+}
+
+/// Replaces the compiler of `this` with a fresh one.
+///
+/// The old [SourceMap] and comments are freed once all pending tasks using
+/// them are done.
+#[js_function]
+fn reset_compiler(ctx: CallContext<JsObject>) -> napi::Result<JsUndefined> {
+    let c = ctx.env.unwrap::<ArcCompiler>(&ctx.this)?;
+    *c = new_compiler();
+
     ctx.env.get_undefined()
 }
 
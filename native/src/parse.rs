@@ -26,6 +26,21 @@ pub struct ParseFileTask {
     pub options: ParseOptions,
 }
 
+/// Comments can't be returned to js with the program, so they are released
+/// instead of being stored until the program is transformed.
+fn parse_js(c: &Compiler, fm: Arc<SourceFile>, options: &ParseOptions) -> napi::Result<Program> {
+    let res = c.parse_js(
+        fm.clone(),
+        options.target,
+        options.syntax,
+        options.is_module,
+        options.comments,
+    );
+    c.comments().release(&fm);
+
+    res.convert_err()
+}
+
 pub fn complete_parse<'a>(env: &Env, program: Program, _c: &Compiler) -> napi::Result<JsString> {
     let s = serde_json::to_string(&program)
         .context("failed to serialize Program")
@@ -38,18 +53,7 @@ impl Task for ParseTask {
     type JsValue = JsString;
 
     fn compute(&mut self) -> napi::Result<Self::Output> {
-        let program = self
-            .c
-            .parse_js(
-                self.fm.clone(),
-                self.options.target,
-                self.options.syntax,
-                self.options.is_module,
-                self.options.comments,
-            )
-            .convert_err()?;
-
-        Ok(program)
+        parse_js(&self.c, self.fm.clone(), &self.options)
     }
 
     fn resolve(&self, env: &mut Env, result: Self::Output) -> napi::Result<Self::JsValue> {
@@ -70,15 +74,7 @@ impl Task for ParseFileTask {
                 .context("failed to read module")
                 .convert_err()?;
 
-            self.c
-                .parse_js(
-                    fm,
-                    self.options.target,
-                    self.options.syntax,
-                    self.options.is_module,
-                    self.options.comments,
-                )
-                .convert_err()
+            parse_js(&self.c, fm, &self.options)
         })
     }
 
@@ -88,7 +84,7 @@ impl Task for ParseFileTask {
 }
 
 #[js_function(2)]
-pub fn parse(ctx: CallContext) -> napi::Result<JsObject> {
+pub fn parse(ctx: CallContext<JsObject>) -> napi::Result<JsObject> {
     let c = get_compiler(&ctx);
     let src = ctx.get::<JsString>(0)?;
     let options: ParseOptions = ctx.get_deserialized(1)?;
@@ -104,31 +100,22 @@ pub fn parse(ctx: CallContext) -> napi::Result<JsObject> {
 }
 
 #[js_function(2)]
-pub fn parse_sync(cx: CallContext) -> napi::Result<JsString> {
+pub fn parse_sync(cx: CallContext<JsObject>) -> napi::Result<JsString> {
     let c = get_compiler(&cx);
 
     c.run(|| {
         let src = cx.get::<JsString>(0)?.as_str()?.to_string();
         let options: ParseOptions = cx.get_deserialized(1)?;
 
-        let program = {
-            let fm = c.cm.new_source_file(FileName::Anon, src);
-            c.parse_js(
-                fm,
-                options.target,
-                options.syntax,
-                options.is_module,
-                options.comments,
-            )
-        }
-        .convert_err()?;
+        let fm = c.cm.new_source_file(FileName::Anon, src);
+        let program = parse_js(&c, fm, &options)?;
 
         complete_parse(&cx.env, program, &c)
     })
 }
 
 #[js_function(2)]
-pub fn parse_file_sync(cx: CallContext) -> napi::Result<JsString> {
+pub fn parse_file_sync(cx: CallContext<JsObject>) -> napi::Result<JsString> {
     let c = get_compiler(&cx);
     let path = cx.get::<JsString>(0)?;
     let options: ParseOptions = cx.get_deserialized(1)?;
@@ -138,21 +125,14 @@ pub fn parse_file_sync(cx: CallContext) -> napi::Result<JsString> {
             c.cm.load_file(Path::new(path.as_str()?))
                 .expect("failed to read program file");
 
-        c.parse_js(
-            fm,
-            options.target,
-            options.syntax,
-            options.is_module,
-            options.comments,
-        )
-    }
-    .convert_err()?;
+        parse_js(&c, fm, &options)?
+    };
 
     complete_parse(cx.env, program, &c)
 }
 
 #[js_function(2)]
-pub fn parse_file(cx: CallContext) -> napi::Result<JsObject> {
+pub fn parse_file(cx: CallContext<JsObject>) -> napi::Result<JsObject> {
     let c = get_compiler(&cx);
     let path = PathBuf::from(cx.get::<JsString>(0)?.as_str()?);
     let options: ParseOptions = cx.get_deserialized(1)?;
@@ -47,7 +47,7 @@ impl Task for PrintTask {
 }
 
 #[js_function(2)]
-pub fn print(cx: CallContext) -> napi::Result<JsObject> {
+pub fn print(cx: CallContext<JsObject>) -> napi::Result<JsObject> {
     let c = get_compiler(&cx);
     let program = cx.get::<JsString>(0)?;
     let program: Program =
@@ -63,7 +63,7 @@ pub fn print(cx: CallContext) -> napi::Result<JsObject> {
 }
 
 #[js_function(2)]
-pub fn print_sync(cx: CallContext) -> napi::Result<JsObject> {
+pub fn print_sync(cx: CallContext<JsObject>) -> napi::Result<JsObject> {
     let c = get_compiler(&cx);
 
     let program = cx.get::<JsString>(0)?;
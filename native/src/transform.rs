@@ -3,7 +3,7 @@ use crate::{
     util::{CtxtExt, MapErr},
 };
 use anyhow::{Context as _, Error};
-use napi::{CallContext, Env, JsBoolean, JsObject, JsString, Task};
+use napi::{CallContext, Env, JsBoolean, JsObject, JsString, JsUnknown, Task};
 use path_clean::clean;
 use std::{
     path::{Path, PathBuf},
@@ -59,8 +59,45 @@ impl Task for TransformTask {
     }
 }
 
+/// Transforms many files with one call, using the thread pool of swc.
+pub struct TransformFilesTask {
+    pub c: Arc<Compiler>,
+    pub files: Vec<PathBuf>,
+    pub options: Options,
+}
+
+impl Task for TransformFilesTask {
+    /// Failed files are stored as errors, so that other files are not lost.
+    type Output = Vec<napi::Result<TransformOutput>>;
+    type JsValue = JsObject;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        // Diagnostics are taken by the thread which processed the file.
+        Ok(self
+            .c
+            .transform_many_with(&self.files, &self.options, |_, res| res.convert_err()))
+    }
+
+    fn resolve(&self, env: &mut Env, result: Self::Output) -> napi::Result<Self::JsValue> {
+        let mut arr = env.create_array_with_length(result.len())?;
+        for (i, res) in result.into_iter().enumerate() {
+            let v: JsUnknown = match res {
+                Ok(output) => complete_output(env, output)?.into_unknown()?,
+                Err(err) => {
+                    let mut obj = env.create_object()?;
+                    obj.set_named_property("error", env.create_string(&err.reason)?)?;
+                    obj.into_unknown()?
+                }
+            };
+            arr.set_element(i as _, v)?;
+        }
+
+        Ok(arr)
+    }
+}
+
 /// returns `compiler, (src / path), options, plugin, callback`
-pub fn schedule_transform<F>(cx: CallContext, op: F) -> napi::Result<JsObject>
+pub fn schedule_transform<F>(cx: CallContext<JsObject>, op: F) -> napi::Result<JsObject>
 where
     F: FnOnce(&Arc<Compiler>, String, bool, Options) -> TransformTask,
 {
@@ -75,7 +112,7 @@ where
     cx.env.spawn(task)
 }
 
-pub fn exec_transform<F>(cx: CallContext, op: F) -> napi::Result<JsObject>
+pub fn exec_transform<F>(cx: CallContext<JsObject>, op: F) -> napi::Result<JsObject>
 where
     F: FnOnce(&Compiler, String, &Options) -> Result<Arc<SourceFile>, Error>,
 {
@@ -100,7 +137,7 @@ where
 }
 
 #[js_function(4)]
-pub fn transform(cx: CallContext) -> napi::Result<JsObject> {
+pub fn transform(cx: CallContext<JsObject>) -> napi::Result<JsObject> {
     schedule_transform(cx, |c, src, is_module, options| {
         let input = if is_module {
             Input::Program(src)
@@ -124,7 +161,7 @@ pub fn transform(cx: CallContext) -> napi::Result<JsObject> {
 }
 
 #[js_function(4)]
-pub fn transform_sync(cx: CallContext) -> napi::Result<JsObject> {
+pub fn transform_sync(cx: CallContext<JsObject>) -> napi::Result<JsObject> {
     exec_transform(cx, |c, src, options| {
         Ok(c.cm.new_source_file(
             if options.filename.is_empty() {
@@ -138,7 +175,7 @@ pub fn transform_sync(cx: CallContext) -> napi::Result<JsObject> {
 }
 
 #[js_function(4)]
-pub fn transform_file(cx: CallContext) -> napi::Result<JsObject> {
+pub fn transform_file(cx: CallContext<JsObject>) -> napi::Result<JsObject> {
     schedule_transform(cx, |c, path, _, options| {
         let path = clean(&path);
 
@@ -151,10 +188,26 @@ pub fn transform_file(cx: CallContext) -> napi::Result<JsObject> {
 }
 
 #[js_function(4)]
-pub fn transform_file_sync(cx: CallContext) -> napi::Result<JsObject> {
+pub fn transform_file_sync(cx: CallContext<JsObject>) -> napi::Result<JsObject> {
     exec_transform(cx, |c, path, _| {
         Ok(c.cm
             .load_file(Path::new(&path))
             .expect("failed to load file"))
     })
 }
+
+#[js_function(2)]
+pub fn transform_files(cx: CallContext<JsObject>) -> napi::Result<JsObject> {
+    let c = get_compiler(&cx);
+
+    let files: Vec<String> = cx.get_deserialized(0)?;
+    let options: Options = cx.get_deserialized(1)?;
+
+    let task = TransformFilesTask {
+        c,
+        files: files.into_iter().map(|path| clean(&path).into()).collect(),
+        options,
+    };
+
+    cx.env.spawn(task)
+}
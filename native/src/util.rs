@@ -1,11 +1,49 @@
 use anyhow::Context;
 use napi::{CallContext, JsBuffer, NapiValue, Status};
 use serde::de::DeserializeOwned;
+use swc::Diagnostics;
+use swc_common::errors::{json::JsonDiagnostic, DiagnosticBuilder, Emitter, EmitterWriter};
+
+/// Prints warnings to stderr.
+///
+/// Errors are not printed, because [MapErr::convert_err] reports them to js
+/// using the [Diagnostics] of the failed call.
+pub struct WarningEmitter(pub EmitterWriter);
+
+impl Emitter for WarningEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        if !db.is_error() {
+            self.0.emit(db)
+        }
+    }
+}
 
 pub trait MapErr<T>: Into<Result<T, anyhow::Error>> {
+    /// On error, the reason is a json array of the diagnostics emitted by the
+    /// failed call, followed by `err`.
     fn convert_err(self) -> napi::Result<T> {
-        self.into()
-            .map_err(|err| napi::Error::new(Status::GenericFailure, format!("{:?}", err)))
+        self.into().map_err(|err| {
+            let mut diagnostics = match err.downcast_ref::<Diagnostics>() {
+                Some(d) => d
+                    .diagnostics
+                    .iter()
+                    .map(|diagnostic| JsonDiagnostic::from_diagnostic(diagnostic, Some(&*d.cm)))
+                    .collect(),
+                None => vec![],
+            };
+            diagnostics.push(JsonDiagnostic {
+                message: format!("{:?}", err),
+                code: None,
+                level: "error".into(),
+                spans: vec![],
+                children: vec![],
+            });
+
+            let reason = serde_json::to_string(&diagnostics)
+                .unwrap_or_else(|err| format!("failed to serialize diagnostics: {}", err));
+
+            napi::Error::new(Status::GenericFailure, reason)
+        })
     }
 }
 
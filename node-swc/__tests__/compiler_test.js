@@ -0,0 +1,19 @@
+const swc = require("../../");
+
+it("should work with separate compiler instances", () => {
+  const a = new swc.Compiler();
+  const b = new swc.Compiler();
+
+  expect(a.transformSync("const foo = 1;").code).toContain("var foo = 1;");
+  expect(b.transformSync("const bar = 1;").code).toContain("var bar = 1;");
+});
+
+it("should work after reset", async () => {
+  const compiler = new swc.Compiler();
+
+  const before = await compiler.transform("class Foo {}");
+  compiler.reset();
+  const after = await compiler.transform("class Foo {}");
+
+  expect(after.code).toBe(before.code);
+});
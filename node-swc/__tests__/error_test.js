@@ -1,7 +1,27 @@
 const swc = require("../../");
 
 it("should work", () => {
-  expect(() => {
+  expect.assertions(2);
+
+  try {
     swc.transformFileSync(__dirname + "/../tests/error/simple.js");
-  }).toThrow("jsc");
+  } catch (diagnostics) {
+    expect(Array.isArray(diagnostics)).toBe(true);
+    expect(diagnostics[diagnostics.length - 1].message).toContain("jsc");
+  }
+});
+
+it("should reject with spanned diagnostics", async () => {
+  expect.assertions(4);
+
+  try {
+    await swc.transform("let a = ;", { filename: "input.js" });
+  } catch (diagnostics) {
+    const [first] = diagnostics;
+
+    expect(first.level).toBe("error");
+    expect(first.spans[0].fileName).toBe("input.js");
+    expect(first.spans[0].lineStart).toBe(1);
+    expect(first.spans[0].columnStart).toBe(9);
+  }
 });
@@ -0,0 +1,18 @@
+const swc = require("../../../");
+const path = require("path");
+
+const dir = path.join(__dirname, "..", "..", "..", "tests", "projects", "swcrc-extends", "src");
+
+it("should transform files in parallel", async () => {
+  const res = await swc.transformFiles([
+    path.join(dir, "input.js"),
+    path.join(dir, "modern", "input.js"),
+    path.join(dir, "not-found.js"),
+  ]);
+
+  expect(res).toHaveLength(3);
+  expect(res[0].code).toContain("exports.add");
+  expect(res[0].code).not.toContain("=>");
+  expect(res[1].code).toContain("=>");
+  expect(res[2].error).toBeInstanceOf(Array);
+});
@@ -1,8 +1,10 @@
 import {
+  Diagnostic,
   Plugin,
   ParseOptions,
   Module,
   Output,
+  FileOutput,
   Options,
   Script,
   Program,
@@ -12,6 +14,7 @@ import { BundleInput, compileBundleOptions } from "./spack";
 import { loadBinding } from "@node-rs/helper";
 
 const bindings = loadBinding(__dirname, "swc", "@swc/core")
+const NativeCompiler = bindings.define();
 
 /**
  * Version of the swc binding.
@@ -29,6 +32,21 @@ export function plugins(ps: Plugin[]): Plugin {
 }
 
 export class Compiler {
+  /**
+   * Each instance owns a native compiler, so source files and comments
+   * are not shared with other instances.
+   */
+  private native = withDiagnostics(new NativeCompiler());
+
+  /**
+   * Drops every source file and comment stored by this compiler.
+   *
+   * Pending operations are not affected.
+   */
+  reset(): void {
+    this.native.reset();
+  }
+
   parse(
     src: string,
     options: ParseOptions & { isModule: false }
@@ -38,7 +56,7 @@ export class Compiler {
     options = options || { syntax: "ecmascript" };
     options.syntax = options.syntax || "ecmascript";
 
-    const res = await bindings.parse(src, toBuffer(options));
+    const res = await this.native.parse(src, toBuffer(options));
     return JSON.parse(res);
   }
 
@@ -48,7 +66,7 @@ export class Compiler {
     options = options || { syntax: "ecmascript" };
     options.syntax = options.syntax || "ecmascript";
 
-    return JSON.parse(bindings.parseSync(src, toBuffer(options)));
+    return JSON.parse(this.native.parseSync(src, toBuffer(options)));
   }
 
   parseFile(
@@ -60,7 +78,7 @@ export class Compiler {
     options = options || { syntax: "ecmascript" };
     options.syntax = options.syntax || "ecmascript";
 
-    const res = bindings.parseFile(path, toBuffer(options));
+    const res = this.native.parseFile(path, toBuffer(options));
 
     return JSON.parse(res);
   }
@@ -74,7 +92,7 @@ export class Compiler {
     options = options || { syntax: "ecmascript" };
     options.syntax = options.syntax || "ecmascript";
 
-    return JSON.parse(bindings.parseFileSync(path, toBuffer(options)));
+    return JSON.parse(this.native.parseFileSync(path, toBuffer(options)));
   }
 
   /**
@@ -84,7 +102,7 @@ export class Compiler {
   async print(m: Program, options?: Options): Promise<Output> {
     options = options || {};
 
-    return bindings.print(JSON.stringify(m), toBuffer(options))
+    return this.native.print(JSON.stringify(m), toBuffer(options))
   }
 
   /**
@@ -94,7 +112,7 @@ export class Compiler {
   printSync(m: Program, options?: Options): Output {
     options = options || {};
 
-    return bindings.printSync(JSON.stringify(m), toBuffer(options));
+    return this.native.printSync(JSON.stringify(m), toBuffer(options));
   }
 
   async transform(src: string | Program, options?: Options): Promise<Output> {
@@ -117,7 +135,7 @@ export class Compiler {
       return this.transform(plugin(m), options);
     }
 
-    return bindings.transform(isModule ? JSON.stringify(src) : src, isModule, toBuffer(options))
+    return this.native.transform(isModule ? JSON.stringify(src) : src, isModule, toBuffer(options))
   }
 
   transformSync(src: string | Program, options?: Options): Output {
@@ -138,7 +156,7 @@ export class Compiler {
       return this.transformSync(plugin(m), options);
     }
 
-    return bindings.transformSync(
+    return this.native.transformSync(
       isModule ? JSON.stringify(src) : src,
       isModule,
       toBuffer(options),
@@ -161,7 +179,7 @@ export class Compiler {
       return this.transform(plugin(m), options);
     }
 
-    return bindings.transformFile(path, false, toBuffer(options))
+    return this.native.transformFile(path, false, toBuffer(options))
   }
 
   transformFileSync(path: string, options?: Options): Output {
@@ -180,7 +198,28 @@ export class Compiler {
       return this.transformSync(plugin(m), options);
     }
 
-    return bindings.transformFileSync(path, /* isModule */ false, toBuffer(options));
+    return this.native.transformFileSync(path, /* isModule */ false, toBuffer(options));
+  }
+
+  /**
+   * Transforms `paths` in parallel with a single native call.
+   *
+   * `.swcrc` files are loaded once per directory. A file which failed to
+   * transform does not affect other files. Plugins are not supported.
+   */
+  async transformFiles(paths: string[], options?: Options): Promise<FileOutput[]> {
+    options = options || {};
+
+    if (options?.jsc?.parser) {
+      options.jsc.parser.syntax = options.jsc.parser.syntax ?? 'ecmascript';
+    }
+
+    if (options.plugin) {
+      throw new Error("transformFiles does not support plugins");
+    }
+
+    const res = await this.native.transformFiles(toBuffer(paths), toBuffer(options));
+    return res.map((r: any) => r.error === undefined ? r : { error: JSON.parse(r.error) });
   }
 
 
@@ -201,7 +240,7 @@ export class Compiler {
       return obj;
     }
 
-    return bindings.bundle(toBuffer({
+    return this.native.bundle(toBuffer({
       ...opts,
     }));
   }
@@ -284,6 +323,13 @@ export function transformFileSync(path: string, options?: Options): Output {
   return compiler.transformFileSync(path, options);
 }
 
+export function transformFiles(
+  paths: string[],
+  options?: Options
+): Promise<FileOutput[]> {
+  return compiler.transformFiles(paths, options);
+}
+
 export function bundle(
   options?: BundleInput | string
 ): Promise<{ [name: string]: Output }> {
@@ -300,6 +346,43 @@ export const DEFAULT_EXTENSIONS = Object.freeze([
   ".tsx"
 ]);
 
+/**
+ * Native errors have a json array of diagnostics as their message.
+ * This makes methods of `native` throw (or reject with) the array itself.
+ */
+function withDiagnostics(native: any): any {
+  const toDiagnostics = (e: any): Diagnostic[] | any => {
+    try {
+      return JSON.parse(e.message);
+    } catch (_) {
+      return e;
+    }
+  };
+
+  return new Proxy(native, {
+    get(target, prop) {
+      const f = target[prop];
+      if (typeof f !== "function") {
+        return f;
+      }
+
+      return (...args: any[]) => {
+        try {
+          const res = f.apply(target, args);
+          if (res instanceof Promise) {
+            return res.catch(e => {
+              throw toDiagnostics(e);
+            });
+          }
+          return res;
+        } catch (e) {
+          throw toDiagnostics(e);
+        }
+      };
+    }
+  });
+}
+
 function toBuffer(t: any): Buffer {
   return Buffer.from(JSON.stringify(t))
 }
\ No newline at end of file
@@ -67,10 +67,62 @@ export interface SpackConfig {
 
     module: ModuleConfig
 
+    resolve?: ResolveConfig
+
     options?: Options
 }
 
+/**
+ * See https://webpack.js.org/configuration/resolve/
+ */
+export interface ResolveConfig {
+    /**
+     * `foo$` matches only `foo`, while `foo` also matches `foo/bar`.
+     */
+    alias?: { [key: string]: string }
+
+    /**
+     * e.g. `["browser"]`
+     */
+    aliasFields?: string[]
+
+    /**
+     * Conditions for `exports` and `imports` of `package.json`.
+     * `default` always matches.
+     *
+     * @default ["import", "node"], or `browser` instead of `node` if it's in `aliasFields` or `mainFields`.
+     */
+    conditionNames?: string[]
+
+    /**
+     * @default ["package.json"]
+     */
+    descriptionFiles?: string[]
+
+    enforceExtension?: boolean
+
+    /**
+     * @default [".wasm", ".mjs", ".js", ".json", ".ts", ".tsc"]
+     */
+    extensions?: string[]
+
+    /**
+     * @default ["swc-main", "esnext", "module", "main"]
+     */
+    mainFields?: string[]
+
+    /**
+     * @default true
+     */
+    symlinks?: boolean
+}
+
 export interface OutputConfig {
+    /**
+     * File name of bundles. `[name]` is replaced with the name of a bundle.
+     *
+     * @default "[name]"
+     */
     name: string
     path: string
 }
@@ -129,6 +129,25 @@ export interface Options extends Config {
    */
   swcrc?: boolean;
 
+  /**
+   * Path to a `tsconfig.json` file, relative to `cwd`.
+   *
+   * If specified, `target`, `experimentalDecorators`, `emitDecoratorMetadata`,
+   * `jsxFactory`, `jsxFragmentFactory` and `importsNotUsedAsValues` of it
+   * override the values in `.swcrc`. `extends` is supported. Files are parsed
+   * as typescript, and as tsx if `jsx` is set.
+   */
+  tsconfig?: string;
+
+  /**
+   * Directory to store transformed files, relative to `cwd`.
+   *
+   * A file is transformed again only if its content, the resolved config or
+   * the version of swc changes. Used by `bundle`, too, unless `sourceMaps` is
+   * enabled.
+   */
+  cacheDir?: string;
+
   /**
    * By default, Babel will only search for .babelrc files within the "root" package
    *  because otherwise Babel cannot know if a given .babelrc is meant to be loaded,
@@ -206,17 +225,34 @@ export interface Config {
   /**
    * Note: The type is string beacuse it follow rust's regex syntax.
    */
-  test?: string | string[];
+  test?: FileMatcher;
   /**
    * Note: The type is string beacuse it follow rust's regex syntax.
    */
-  exclude?: string | string[];
+  exclude?: FileMatcher;
   env?: EnvConfig;
   jsc?: JscConfig;
   module?: ModuleConfig;
   minify?: boolean;
+  /**
+   * Path to a `.swcrc` file to extend, relative to the file declaring it.
+   */
+  extends?: string;
+  /**
+   * Configs merged if the key is equal to `envName`.
+   */
+  envs?: { [envName: string]: Config };
+  /**
+   * Configs merged if `test` and `exclude` of them match the file.
+   */
+  overrides?: Config[];
 }
 
+/**
+ * A regex, a glob pattern like `{ glob: "src/**\/*.ts" }`, or a list of them.
+ */
+export type FileMatcher = string | { glob: string } | FileMatcher[];
+
 /**
  * Configuration ported from babel-preset-env
  */
@@ -227,6 +263,11 @@ export interface EnvConfig {
 
   loose?: boolean;
 
+  /**
+   * Ignored if `loose` is true, as it implies all assumptions.
+   */
+  assumptions?: Assumptions;
+
   /// Skipped es features.
   ///
   /// e.g.)
@@ -253,9 +294,34 @@ export interface EnvConfig {
   forceAllTransforms?: boolean;
 }
 
+/**
+ * Assumptions about the input code, which allow compat passes to emit smaller
+ * and faster output.
+ *
+ * See https://babeljs.io/docs/en/assumptions
+ */
+export interface Assumptions {
+  noClassCalls?: boolean;
+  constantSuper?: boolean;
+  setClassMethods?: boolean;
+  setPublicClassFields?: boolean;
+  setComputedProperties?: boolean;
+  ignoreToPrimitiveHint?: boolean;
+  mutableTemplateObject?: boolean;
+  setSpreadProperties?: boolean;
+  objectRestNoSymbols?: boolean;
+  noDocumentAll?: boolean;
+  iterableIsArray?: boolean;
+}
+
 export interface JscConfig {
   loose?: boolean;
 
+  /**
+   * Ignored if `loose` is true, as it implies all assumptions.
+   */
+  assumptions?: Assumptions;
+
   /**
    * Defaults to EsParserConfig
    */
@@ -279,7 +345,10 @@ export type JscTarget =
   | "es2016"
   | "es2017"
   | "es2018"
-  | "es2019";
+  | "es2019"
+  | "es2020"
+  | "es2021"
+  | "es2022";
 
 export type ParserConfig = TsParserConfig | EsParserConfig;
 export interface TsParserConfig {
@@ -385,6 +454,11 @@ export interface TransformConfig {
    * https://swc.rs/docs/configuring-swc.html#jsctransformdecoratormetadata
    */
   decoratorMetadata?: boolean
+
+  /**
+   * Defaults to `remove`.
+   */
+  importNotUsedAsValues?: "remove" | "preserve"
 }
 
 export interface ReactConfig {
@@ -558,6 +632,55 @@ export interface Output {
   map?: string;
 }
 
+/**
+ * Result of a file passed to `transformFiles`.
+ */
+export type FileOutput = Output | { error: Diagnostic[] };
+
+/**
+ * Thrown (as an array) when an operation fails.
+ */
+export interface Diagnostic {
+  message: string;
+  code?: string;
+  /**
+   * "error", "warning", "note" or "help".
+   */
+  level: string;
+  spans: DiagnosticSpan[];
+  /**
+   * Notes and suggestions attached to this diagnostic.
+   */
+  children: Diagnostic[];
+}
+
+export interface DiagnosticSpan {
+  fileName: string;
+  /**
+   * 0-based, relative to the start of the file.
+   */
+  byteStart: number;
+  byteEnd: number;
+  /**
+   * 1-based.
+   */
+  lineStart: number;
+  lineEnd: number;
+  /**
+   * 1-based.
+   */
+  columnStart: number;
+  columnEnd: number;
+  isPrimary: boolean;
+  label?: string;
+  suggestedReplacement?: string;
+  suggestionApplicability?:
+    | "MachineApplicable"
+    | "HasPlaceholders"
+    | "MaybeIncorrect"
+    | "Unspecified";
+}
+
 export interface MatchPattern { }
 
 // -------------------------------
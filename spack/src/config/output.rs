@@ -6,6 +6,9 @@ use std::path::PathBuf;
 pub struct OutputConfig {
     pub path: PathBuf,
 
+    /// File name of bundles. `[name]` is replaced with the name of a bundle.
+    ///
+    /// Defaults to `[name]`, and `.js` is appended if there's no extension.
     #[serde(default)]
     pub name: String,
 }
@@ -12,6 +12,10 @@ pub struct ResolveConfig {
     #[serde(default)]
     pub alias_fields: Vec<JsWord>,
 
+    /// Conditions for `exports` and `imports` of `package.json`.
+    #[serde(default)]
+    pub condition_names: Vec<JsWord>,
+
     #[serde(default)]
     pub description_files: Vec<JsWord>,
 
@@ -0,0 +1,384 @@
+//! Printing and writing of bundles.
+
+use crate::{config::Config, resolvers::NodeResolver};
+use anyhow::{Context, Error};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fs::{create_dir_all, write},
+    path::{Component, Path, PathBuf},
+};
+use swc::config::SourceMapsConfig;
+use swc_bundler::{Bundle, BundleKind, Resolve};
+use swc_common::{FileName, SourceMap};
+use swc_ecma_ast::{
+    CallExpr, ExportAll, Expr, ExprOrSpread, ExprOrSuper, ImportDecl, Lit, NamedExport, Str,
+};
+use swc_ecma_visit::{noop_visit_mut_type, VisitMut, VisitMutWith};
+
+/// Prints bundles and writes them to the output directory.
+pub struct Emitter<'a> {
+    compiler: &'a swc::Compiler,
+    minify: bool,
+    source_maps: Option<SourceMapsConfig>,
+    /// `[name]` is replaced with the name of a bundle.
+    file_name: String,
+    /// Used to find chunks imported by `import()`.
+    resolver: NodeResolver,
+}
+
+/// A printed bundle.
+#[derive(Debug)]
+pub struct EmittedBundle {
+    /// Name of the entry or chunk.
+    pub name: String,
+    /// Path relative to the output directory.
+    pub file_name: String,
+    pub code: String,
+    pub map: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmitSummary {
+    pub files: Vec<EmittedFile>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmittedFile {
+    pub path: PathBuf,
+    /// Size of the file in bytes.
+    pub size: usize,
+    /// Size of the source map file, if it's written.
+    pub map_size: Option<usize>,
+}
+
+impl EmitSummary {
+    /// Total size of files, including source maps.
+    pub fn total_size(&self) -> usize {
+        self.files
+            .iter()
+            .map(|f| f.size + f.map_size.unwrap_or(0))
+            .sum()
+    }
+}
+
+impl<'a> Emitter<'a> {
+    /// Uses `minify` and `sourceMaps` of `config.options`, `name` of
+    /// `config.output`, and `config.resolve`.
+    pub fn new(compiler: &'a swc::Compiler, config: &Config) -> Self {
+        let options = config.options.as_ref();
+        let file_name = config
+            .output
+            .as_ref()
+            .map(|output| output.name.clone())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "[name]".into());
+
+        Emitter {
+            compiler,
+            minify: options
+                .and_then(|options| options.config.as_ref())
+                .and_then(|config| config.minify)
+                .unwrap_or(false),
+            source_maps: options.and_then(|options| options.source_maps.clone()),
+            file_name,
+            resolver: match &config.resolve {
+                Some(resolve) => NodeResolver::from_config(resolve),
+                None => NodeResolver::new(),
+            },
+        }
+    }
+
+    fn file_name(&self, bundle: &Bundle) -> (String, String) {
+        let name = match &bundle.kind {
+            BundleKind::Named { name } => name.clone(),
+            BundleKind::Lib { name } => Path::new(name)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| name.clone()),
+            BundleKind::Dynamic => format!("dynamic.{}.js", bundle.id),
+        };
+
+        let stem = name.strip_suffix(".js").unwrap_or(&name);
+        let mut file_name = self.file_name.replace("[name]", stem);
+        if Path::new(&file_name).extension().is_none() {
+            file_name.push_str(".js");
+        }
+
+        (name, file_name)
+    }
+
+    /// Imports between bundles are changed to point the printed files.
+    pub fn print(&self, bundles: Vec<Bundle>) -> Result<Vec<EmittedBundle>, Error> {
+        let names = bundles
+            .iter()
+            .map(|bundle| self.file_name(bundle))
+            .collect::<Vec<_>>();
+        let mut chunks = HashMap::new();
+        for (bundle, (_, file_name)) in bundles.iter().zip(&names) {
+            let path = match &bundle.file_name {
+                FileName::Real(path) => path,
+                _ => continue,
+            };
+
+            // `import()` refers to the source file of a chunk.
+            chunks.insert(normalize(path), file_name.clone());
+            // Chunks renamed by finalize are placed next to their source files.
+            if let BundleKind::Named { name } = &bundle.kind {
+                if let Some(dir) = path.parent() {
+                    chunks.insert(normalize(&dir.join(name)), file_name.clone());
+                }
+            }
+        }
+
+        bundles
+            .into_iter()
+            .zip(names)
+            .map(|(mut bundle, (name, file_name))| {
+                bundle.module.visit_mut_with(&mut ImportRenamer {
+                    cm: &self.compiler.cm,
+                    resolver: &self.resolver,
+                    base: &bundle.file_name,
+                    file_name: &file_name,
+                    chunks: &chunks,
+                });
+
+                let output = self
+                    .compiler
+                    .print(
+                        &bundle.module,
+                        self.source_maps
+                            .clone()
+                            .unwrap_or(SourceMapsConfig::Bool(false)),
+                        None,
+                        self.minify,
+                    )
+                    .with_context(|| format!("failed to print bundle `{}`", name))?;
+
+                Ok(EmittedBundle {
+                    name,
+                    file_name,
+                    code: output.code,
+                    map: output.map,
+                })
+            })
+            .collect()
+    }
+
+    /// Writes `bundles` under `dir`. Source maps are written to `.map` files
+    /// next to the bundles.
+    pub fn write(&self, dir: &Path, bundles: Vec<EmittedBundle>) -> Result<EmitSummary, Error> {
+        let mut summary = EmitSummary::default();
+
+        for bundle in bundles {
+            let path = dir.join(&bundle.file_name);
+            if let Some(parent) = path.parent() {
+                create_dir_all(parent)
+                    .with_context(|| format!("failed to create {}", parent.display()))?;
+            }
+
+            let mut code = bundle.code;
+            let map_size = match bundle.map {
+                Some(map) => {
+                    let mut map_path = path.clone().into_os_string();
+                    map_path.push(".map");
+                    let map_path = PathBuf::from(map_path);
+
+                    write(&map_path, &map)
+                        .with_context(|| format!("failed to write {}", map_path.display()))?;
+                    code.push_str(&format!(
+                        "\n//# sourceMappingURL={}",
+                        map_path.file_name().unwrap().to_string_lossy()
+                    ));
+
+                    Some(map.len())
+                }
+                None => None,
+            };
+
+            write(&path, &code).with_context(|| format!("failed to write {}", path.display()))?;
+            summary.files.push(EmittedFile {
+                path,
+                size: code.len(),
+                map_size,
+            });
+        }
+
+        Ok(summary)
+    }
+
+    /// Prints `bundles` and writes them to `output.path` of `config`, which is
+    /// relative to the working directory.
+    pub fn emit(&self, config: &Config, bundles: Vec<Bundle>) -> Result<EmitSummary, Error> {
+        let output = config
+            .output
+            .as_ref()
+            .context("`output` is required to write bundles")?;
+        let dir = config.working_dir.join(&output.path);
+
+        let bundles = self.print(bundles)?;
+        self.write(&dir, bundles)
+    }
+}
+
+/// Changes imports of chunks to paths relative to the importing bundle.
+struct ImportRenamer<'a> {
+    cm: &'a SourceMap,
+    resolver: &'a NodeResolver,
+    /// Source file of the importing bundle.
+    base: &'a FileName,
+    /// The importing bundle.
+    file_name: &'a str,
+    /// Full path of a chunk to its file name.
+    chunks: &'a HashMap<PathBuf, String>,
+}
+
+impl ImportRenamer<'_> {
+    /// Imports of chunks are changed by finalize to paths relative to the
+    /// source file of the importing bundle.
+    fn rename(&self, src: &mut Str) {
+        if !src.value.starts_with('.') {
+            return;
+        }
+
+        let dir = match self.base {
+            FileName::Real(path) => path.parent().unwrap_or_else(|| Path::new("")),
+            _ => return,
+        };
+        let path = dir.join(&*src.value);
+        self.rename_to(src, &path);
+    }
+
+    /// `import()` is not changed by finalize, so the specifier is resolved
+    /// from the module containing it.
+    fn rename_dynamic(&self, src: &mut Str) {
+        let base = if src.span.is_dummy() {
+            self.base.clone()
+        } else {
+            self.cm.lookup_char_pos(src.span.lo).file.name.clone()
+        };
+
+        if let Ok(FileName::Real(path)) = self.resolver.resolve(&base, &src.value) {
+            self.rename_to(src, &path);
+        }
+    }
+
+    fn rename_to(&self, src: &mut Str, path: &Path) {
+        let target = match self.chunks.get(&normalize(path)) {
+            Some(v) => v,
+            None => return,
+        };
+
+        let from = Path::new(self.file_name)
+            .parent()
+            .unwrap_or_else(|| Path::new(""));
+        src.value = relative(from, Path::new(target)).into();
+    }
+}
+
+impl VisitMut for ImportRenamer<'_> {
+    noop_visit_mut_type!();
+
+    fn visit_mut_import_decl(&mut self, import: &mut ImportDecl) {
+        self.rename(&mut import.src);
+    }
+
+    fn visit_mut_export_all(&mut self, export: &mut ExportAll) {
+        self.rename(&mut export.src);
+    }
+
+    fn visit_mut_named_export(&mut self, export: &mut NamedExport) {
+        if let Some(src) = &mut export.src {
+            self.rename(src);
+        }
+    }
+
+    fn visit_mut_call_expr(&mut self, call: &mut CallExpr) {
+        call.visit_mut_children_with(self);
+
+        match &call.callee {
+            ExprOrSuper::Expr(callee) => match &**callee {
+                Expr::Ident(ident) if &*ident.sym == "import" => {}
+                _ => return,
+            },
+            _ => return,
+        }
+
+        if let Some(ExprOrSpread { expr, .. }) = call.args.first_mut() {
+            if let Expr::Lit(Lit::Str(src)) = &mut **expr {
+                self.rename_dynamic(src);
+            }
+        }
+    }
+}
+
+/// Removes `.` and `..` from `path` without accessing the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut buf = PathBuf::new();
+    for c in path.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                buf.pop();
+            }
+            c => buf.push(c),
+        }
+    }
+    buf
+}
+
+/// Both of `from` and `to` are relative to the output directory.
+fn relative(from: &Path, to: &Path) -> String {
+    let from = from
+        .components()
+        .filter(|c| *c != Component::CurDir)
+        .collect::<Vec<_>>();
+    let to = to
+        .components()
+        .filter(|c| *c != Component::CurDir)
+        .collect::<Vec<_>>();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut parts = vec![];
+    if common == from.len() {
+        parts.push(".".to_string());
+    }
+    parts.extend(from[common..].iter().map(|_| "..".to_string()));
+    parts.extend(
+        to[common..]
+            .iter()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned()),
+    );
+
+    parts.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize, relative};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn relative_path() {
+        let r = |from: &str, to: &str| relative(Path::new(from), Path::new(to));
+
+        assert_eq!(r("", "common.js"), "./common.js");
+        assert_eq!(r("pages", "common.js"), "../common.js");
+        assert_eq!(r("pages", "pages/common.js"), "./common.js");
+        assert_eq!(r("a/b", "a/c/common.js"), "../c/common.js");
+    }
+
+    #[test]
+    fn normalize_path() {
+        assert_eq!(
+            normalize(Path::new("/src/pages/../common-1234.js")),
+            PathBuf::from("/src/common-1234.js")
+        );
+        assert_eq!(
+            normalize(Path::new("/src/./common.js")),
+            PathBuf::from("/src/common.js")
+        );
+    }
+}
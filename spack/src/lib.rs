@@ -4,5 +4,6 @@
 extern crate test;
 
 pub mod config;
+pub mod emitter;
 pub mod loaders;
 pub mod resolvers;
@@ -1,12 +1,16 @@
 use anyhow::{bail, Context, Error};
 use helpers::Helpers;
+use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, env, sync::Arc};
-use swc::config::{InputSourceMap, JscConfig, TransformConfig};
+use swc::{
+    cache::{key_part, Cache, CacheKey},
+    config::{InputSourceMap, JscConfig, SourceMapsConfig, TransformConfig},
+};
 use swc_atoms::JsWord;
 use swc_bundler::{Load, ModuleData};
-use swc_common::{FileName, DUMMY_SP};
-use swc_ecma_ast::{Expr, Lit, Program, Str};
-use swc_ecma_parser::JscTarget;
+use swc_common::{FileName, SourceFile, DUMMY_SP};
+use swc_ecma_ast::{Expr, Ident, Lit, Program, Str};
+use swc_ecma_parser::{EsConfig, JscTarget, Syntax};
 use swc_ecma_transforms::{
     helpers,
     optimization::{
@@ -14,7 +18,11 @@ use swc_ecma_transforms::{
         simplify::{dead_branch_remover, expr_simplifier},
     },
 };
-use swc_ecma_visit::FoldWith;
+use swc_ecma_visit::{noop_visit_mut_type, FoldWith, VisitMut, VisitMutWith};
+
+/// Prepended to the names of helpers in cached modules, so that they can be
+/// distinguished from other identifiers.
+const HELPER_PREFIX: &str = "__swcHelper$";
 
 /// JavaScript loader
 pub struct SwcLoader {
@@ -22,6 +30,16 @@ pub struct SwcLoader {
     options: swc::config::Options,
 }
 
+/// A module stored in the cache.
+///
+/// Marks are valid only in a process, so the module is stored as code.
+#[derive(Serialize, Deserialize)]
+struct CachedModule {
+    code: String,
+    /// Names of the used helpers.
+    helpers: Vec<String>,
+}
+
 impl SwcLoader {
     pub fn new(compiler: Arc<swc::Compiler>, mut options: swc::config::Options) -> Self {
         if options.config.is_none() {
@@ -30,6 +48,153 @@ impl SwcLoader {
 
         SwcLoader { compiler, options }
     }
+
+    /// Cached modules are parsed from the transformed code, so source maps
+    /// would point into it. Caching is disabled if source maps are enabled.
+    fn cache(&self) -> Option<Cache> {
+        if self
+            .options
+            .source_maps
+            .as_ref()
+            .map_or(false, |v| v.enabled())
+        {
+            return None;
+        }
+
+        self.options
+            .cache_dir
+            .as_ref()
+            .map(|dir| Cache::new(self.options.cwd.join(dir)))
+    }
+
+    /// Options used to transform files which are not in `node_modules`.
+    fn options(&self) -> swc::config::Options {
+        swc::config::Options {
+            config: {
+                if let Some(c) = &self.options.config {
+                    Some(swc::config::Config {
+                        jsc: JscConfig {
+                            transform: {
+                                if let Some(c) = &c.jsc.transform {
+                                    Some(TransformConfig {
+                                        react: c.react.clone(),
+                                        const_modules: c.const_modules.clone(),
+                                        optimizer: None,
+                                        legacy_decorator: c.legacy_decorator,
+                                        decorator_metadata: c.decorator_metadata,
+                                        import_not_used_as_values: c.import_not_used_as_values,
+                                        hidden: Default::default(),
+                                    })
+                                } else {
+                                    None
+                                }
+                            },
+                            external_helpers: Some(true),
+                            ..c.jsc
+                        },
+                        module: None,
+                        minify: Some(false),
+                        ..c.clone()
+                    })
+                } else {
+                    None
+                }
+            },
+            skip_helper_injection: true,
+            disable_hygiene: false,
+            disable_fixer: true,
+            global_mark: self.options.global_mark,
+            cwd: self.options.cwd.clone(),
+            caller: None,
+            filename: String::new(),
+            config_file: None,
+            root: None,
+            root_mode: Default::default(),
+            swcrc: true,
+            tsconfig: self.options.tsconfig.clone(),
+            cache_dir: None,
+            swcrc_roots: Default::default(),
+            env_name: {
+                let s = env::var("NODE_ENV").unwrap_or_else(|_| "development".into());
+                s
+            },
+            input_source_map: InputSourceMap::Bool(false),
+            source_maps: None,
+            source_file_name: None,
+            source_root: None,
+            is_module: true,
+        }
+    }
+
+    fn load_cached(&self, fm: &SourceFile, cached: CachedModule) -> Result<ModuleData, Error> {
+        let fm = self
+            .compiler
+            .cm
+            .new_source_file(fm.name.clone(), cached.code);
+        let program = self.compiler.parse_js(
+            fm.clone(),
+            JscTarget::Es2020,
+            Syntax::Es(EsConfig {
+                jsx: true,
+                num_sep: true,
+                class_private_props: true,
+                class_private_methods: true,
+                class_props: true,
+                fn_bind: true,
+                decorators: true,
+                decorators_before_export: true,
+                export_default_from: true,
+                export_namespace_from: true,
+                dynamic_import: true,
+                nullish_coalescing: true,
+                optional_chaining: true,
+                import_meta: true,
+                top_level_await: true,
+                import_assertions: true,
+            }),
+            true,
+            true,
+        )?;
+
+        let helpers = Helpers::new(false);
+        for name in &cached.helpers {
+            if !helpers.enable(name) {
+                bail!("unknown helper in cache: {}", name)
+            }
+        }
+
+        let mut module = match program {
+            Program::Module(module) => module,
+            _ => unreachable!(),
+        };
+        module.visit_mut_with(&mut HelperRestorer { helpers: &helpers });
+
+        Ok(ModuleData {
+            fm,
+            module,
+            helpers,
+        })
+    }
+
+    fn store(&self, cache: &Cache, key: &CacheKey, data: &ModuleData) -> Result<(), Error> {
+        let mut module = data.module.clone();
+        module.visit_mut_with(&mut HelperRenamer {
+            helpers: &data.helpers,
+        });
+
+        let code = self
+            .compiler
+            .print(&module, SourceMapsConfig::Bool(false), None, false)?
+            .code;
+
+        cache.put(
+            key,
+            &CachedModule {
+                code,
+                helpers: data.helpers.used().into_iter().map(String::from).collect(),
+            },
+        )
+    }
 }
 
 impl Load for SwcLoader {
@@ -48,7 +213,29 @@ impl Load for SwcLoader {
 
         log::trace!("JsLoader.load: loaded");
 
-        let program = if fm.name.to_string().contains("node_modules") {
+        let node_env = node_env();
+        let cache = self.cache();
+
+        let (program, cache) = if fm.name.to_string().contains("node_modules") {
+            let cache = cache.map(|cache| {
+                let key = cache.key(
+                    "spack-loader",
+                    &[
+                        b"node_modules",
+                        name.to_string().as_bytes(),
+                        fm.src.as_bytes(),
+                        node_env.as_bytes(),
+                    ],
+                );
+                (cache, key)
+            });
+            if let Some((cache, key)) = &cache {
+                if let Some(cached) = cache.get(key) {
+                    log::trace!("JsLoader.load: cache hit");
+                    return self.load_cached(&fm, cached);
+                }
+            }
+
             let program = self.compiler.parse_js(
                 fm.clone(),
                 JscTarget::Es2020,
@@ -67,63 +254,46 @@ impl Load for SwcLoader {
                 })
             });
 
-            program
+            (program, cache)
         } else {
-            let mut config = self.compiler.config_for_file(
-                &swc::config::Options {
-                    config: {
-                        if let Some(c) = &self.options.config {
-                            Some(swc::config::Config {
-                                jsc: JscConfig {
-                                    transform: {
-                                        if let Some(c) = &c.jsc.transform {
-                                            Some(TransformConfig {
-                                                react: c.react.clone(),
-                                                const_modules: c.const_modules.clone(),
-                                                optimizer: None,
-                                                legacy_decorator: c.legacy_decorator,
-                                                decorator_metadata: c.decorator_metadata,
-                                                hidden: Default::default(),
-                                            })
-                                        } else {
-                                            None
-                                        }
-                                    },
-                                    external_helpers: true,
-                                    ..c.jsc
-                                },
-                                module: None,
-                                minify: Some(false),
-                                ..c.clone()
-                            })
-                        } else {
-                            None
-                        }
-                    },
-                    skip_helper_injection: true,
-                    disable_hygiene: false,
-                    disable_fixer: true,
-                    global_mark: self.options.global_mark,
-                    cwd: self.options.cwd.clone(),
-                    caller: None,
-                    filename: String::new(),
-                    config_file: None,
-                    root: None,
-                    root_mode: Default::default(),
-                    swcrc: true,
-                    swcrc_roots: Default::default(),
-                    env_name: {
-                        let s = env::var("NODE_ENV").unwrap_or_else(|_| "development".into());
-                        s
-                    },
-                    input_source_map: InputSourceMap::Bool(false),
-                    source_maps: None,
-                    source_file_name: None,
-                    source_root: None,
-                    is_module: true,
-                },
-                &fm.name,
-            )?;
+            let options = self.options();
+            let config = self
+                .compiler
+                .read_config(&options, &fm.name)
+                .with_context(|| format!("failed to load config for file '{:?}'", fm.name))?;
+
+            let cache = match cache {
+                Some(cache) => {
+                    let key = cache.key(
+                        "spack-loader",
+                        &[
+                            name.to_string().as_bytes(),
+                            fm.src.as_bytes(),
+                            key_part(&config)?.as_bytes(),
+                            key_part(&options.config)?.as_bytes(),
+                            node_env.as_bytes(),
+                        ],
+                    );
+                    Some((cache, key))
+                }
+                None => None,
+            };
+            if let Some((cache, key)) = &cache {
+                if let Some(cached) = cache.get(key) {
+                    log::trace!("JsLoader.load: cache hit");
+                    return self.load_cached(&fm, cached);
+                }
+            }
+
+            let mut config = self.compiler.run(|| {
+                options.build(
+                    &self.compiler.cm,
+                    &self.compiler.handler,
+                    options.is_module,
+                    Some(config),
+                    Some(self.compiler.comments()),
+                )
+            });
 
             log::trace!("JsLoader.load: loaded config");
 
@@ -152,20 +322,64 @@ impl Load for SwcLoader {
 
             log::trace!("JsLoader.load: applied transforms");
 
-            program
+            (program, cache)
         };
 
-        match program {
-            Program::Module(module) => Ok(ModuleData {
+        let data = match program {
+            Program::Module(module) => ModuleData {
                 fm,
                 module,
                 helpers,
-            }),
+            },
             _ => unreachable!(),
+        };
+
+        if let Some((cache, key)) = cache {
+            if let Err(err) = self.store(&cache, &key, &data) {
+                log::warn!("Failed to store {} in the cache: {:?}", name, err);
+            }
+        }
+
+        Ok(data)
+    }
+}
+
+/// Renames identifiers referencing helpers, so that [HelperRestorer] can find
+/// them.
+struct HelperRenamer<'a> {
+    helpers: &'a Helpers,
+}
+
+impl VisitMut for HelperRenamer<'_> {
+    noop_visit_mut_type!();
+
+    fn visit_mut_ident(&mut self, i: &mut Ident) {
+        if i.span.ctxt.outer() == self.helpers.mark() {
+            i.sym = format!("{}{}", HELPER_PREFIX, i.sym).into();
+        }
+    }
+}
+
+/// Applies the mark of `helpers` to identifiers renamed by [HelperRenamer].
+struct HelperRestorer<'a> {
+    helpers: &'a Helpers,
+}
+
+impl VisitMut for HelperRestorer<'_> {
+    noop_visit_mut_type!();
+
+    fn visit_mut_ident(&mut self, i: &mut Ident) {
+        if i.sym.starts_with(HELPER_PREFIX) {
+            i.sym = i.sym[HELPER_PREFIX.len()..].into();
+            i.span = i.span.apply_mark(self.helpers.mark());
         }
     }
 }
 
+fn node_env() -> String {
+    env::var("NODE_ENV").unwrap_or_else(|_| "development".into())
+}
+
 fn env_map() -> HashMap<JsWord, Expr> {
     let mut m = HashMap::default();
 
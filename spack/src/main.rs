@@ -0,0 +1,281 @@
+//! Command line interface of spack.
+//!
+//! Options are read from `spack.config.json` and can be overridden by flags.
+//! Relative paths are resolved from the current directory.
+
+use anyhow::{bail, Context, Error};
+use clap::{App, Arg, ArgMatches};
+use serde_json::{json, Map, Value};
+use spack::{
+    config::{Config, Mode},
+    emitter::Emitter,
+    loaders::swc::SwcLoader,
+    resolvers::NodeResolver,
+};
+use std::{env, fs::read_to_string, path::PathBuf, process, sync::Arc};
+use swc_atoms::js_word;
+use swc_bundler::{Bundler, ModuleRecord};
+use swc_common::{
+    errors::{ColorConfig, Handler},
+    FilePathMapping, SourceMap, Span, GLOBALS,
+};
+use swc_ecma_ast::{Bool, Expr, Ident, KeyValueProp, Lit, PropName, Str};
+
+const DEFAULT_CONFIG: &str = "spack.config.json";
+
+/// Modules provided by node.js.
+const NODE_BUILTINS: &[&str] = &[
+    "assert",
+    "buffer",
+    "child_process",
+    "console",
+    "cluster",
+    "crypto",
+    "dgram",
+    "dns",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "string_decoder",
+    "timers",
+    "tls",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "wasi",
+    "worker",
+    "zlib",
+];
+
+fn main() {
+    let matches = App::new("spack")
+        .about("Bundles javascript and typescript files using swc")
+        .arg(
+            Arg::with_name("config")
+                .short("c")
+                .long("config")
+                .takes_value(true)
+                .help("Path to the config file. Defaults to spack.config.json, if it exists"),
+        )
+        .arg(
+            Arg::with_name("entry")
+                .long("entry")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .help("Entry file. Overrides `entry` of the config file"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .takes_value(true)
+                .help("Output directory"),
+        )
+        .arg(
+            Arg::with_name("output-name")
+                .long("output-name")
+                .takes_value(true)
+                .help("File name of bundles. `[name]` is replaced with the name of a bundle"),
+        )
+        .arg(
+            Arg::with_name("mode")
+                .long("mode")
+                .takes_value(true)
+                .possible_values(&["production", "debug", "none"]),
+        )
+        .arg(
+            Arg::with_name("working-dir")
+                .long("working-dir")
+                .takes_value(true)
+                .help("The output directory is relative to this directory"),
+        )
+        .get_matches();
+
+    if let Err(err) = run(&matches) {
+        eprintln!("error: {:?}", err);
+        process::exit(1);
+    }
+}
+
+fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let config = load_config(matches)?;
+    if config.output.is_none() {
+        bail!(
+            "output directory is not specified. Use `--output` or `output.path` of the config file"
+        );
+    }
+
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let handler = Arc::new(Handler::with_tty_emitter(
+        ColorConfig::Auto,
+        true,
+        false,
+        Some(cm.clone()),
+    ));
+    let compiler = Arc::new(swc::Compiler::new(cm.clone(), handler));
+
+    let summary = GLOBALS.set(compiler.globals(), || -> Result<_, Error> {
+        let loader = SwcLoader::new(compiler.clone(), config.options.clone().unwrap());
+        let resolver = match &config.resolve {
+            Some(resolve) => NodeResolver::from_config(resolve),
+            None => NodeResolver::new(),
+        };
+
+        let bundler = Bundler::new(
+            compiler.globals(),
+            cm.clone(),
+            &loader,
+            resolver,
+            swc_bundler::Config {
+                require: true,
+                external_modules: NODE_BUILTINS.iter().map(|&s| s.into()).collect(),
+                ..Default::default()
+            },
+            Box::new(Hook),
+        );
+
+        let bundles = bundler
+            .bundle(config.entry.clone().into())
+            .context("failed to bundle")?;
+
+        Emitter::new(&compiler, &config).emit(&config, bundles)
+    })?;
+
+    for file in &summary.files {
+        match file.map_size {
+            Some(map_size) => println!(
+                "{} ({} bytes, source map: {} bytes)",
+                file.path.display(),
+                file.size,
+                map_size
+            ),
+            None => println!("{} ({} bytes)", file.path.display(), file.size),
+        }
+    }
+
+    Ok(())
+}
+
+/// Flags are merged into the config file before deserializing it.
+fn load_config(matches: &ArgMatches) -> Result<Config, Error> {
+    let path = match matches.value_of("config") {
+        Some(path) => Some(PathBuf::from(path)),
+        None => Some(PathBuf::from(DEFAULT_CONFIG)).filter(|path| path.is_file()),
+    };
+
+    let mut config = match &path {
+        Some(path) => {
+            let s = read_to_string(path)
+                .with_context(|| format!("failed to read config file: {}", path.display()))?;
+            match serde_json::from_str(&s)
+                .with_context(|| format!("failed to parse config file: {}", path.display()))?
+            {
+                Value::Object(v) => v,
+                _ => bail!("config file should be an object: {}", path.display()),
+            }
+        }
+        None => Map::new(),
+    };
+
+    if let Some(entry) = matches.values_of("entry") {
+        config.insert("entry".into(), json!(entry.collect::<Vec<_>>()));
+    }
+    if !config.contains_key("entry") {
+        bail!("entry is not specified. Use `--entry` or `entry` of the config file");
+    }
+
+    if let Some(mode) = matches.value_of("mode") {
+        config.insert("mode".into(), json!(mode));
+    }
+    if let Some(dir) = matches.value_of("working-dir") {
+        config.insert("working_dir".into(), json!(dir));
+    }
+
+    if matches.is_present("output") || matches.is_present("output-name") {
+        let output = config
+            .entry("output")
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .context("`output` of the config file should be an object")?;
+        if let Some(path) = matches.value_of("output") {
+            output.insert("path".into(), json!(path));
+        }
+        if let Some(name) = matches.value_of("output-name") {
+            output.insert("name".into(), json!(name));
+        }
+        if !output.contains_key("path") {
+            bail!(
+                "output directory is not specified. Use `--output` or `output.path` of the config \
+                 file"
+            );
+        }
+    }
+
+    let mut config: Config =
+        serde_json::from_value(Value::Object(config)).context("invalid config")?;
+    if config.options.is_none() {
+        // Use defaults of serde, not `Default`.
+        config.options = Some(serde_json::from_value(json!({}))?);
+    }
+
+    if let Mode::Production = config.mode {
+        apply_production_mode(&mut config);
+    }
+
+    Ok(config)
+}
+
+/// Minifies bundles and sets `NODE_ENV` unless they are configured.
+fn apply_production_mode(config: &mut Config) {
+    if env::var_os("NODE_ENV").is_none() {
+        env::set_var("NODE_ENV", "production");
+    }
+
+    let options = config.options.as_mut().unwrap();
+    let c = options.config.get_or_insert_with(Default::default);
+    if c.minify.is_none() {
+        c.minify = Some(true);
+    }
+}
+
+struct Hook;
+
+impl swc_bundler::Hook for Hook {
+    fn get_import_meta_props(
+        &self,
+        span: Span,
+        module_record: &ModuleRecord,
+    ) -> Result<Vec<KeyValueProp>, Error> {
+        Ok(vec![
+            KeyValueProp {
+                key: PropName::Ident(Ident::new(js_word!("url"), span)),
+                value: Box::new(Expr::Lit(Lit::Str(Str {
+                    span,
+                    value: module_record.file_name.to_string().into(),
+                    has_escape: false,
+                }))),
+            },
+            KeyValueProp {
+                key: PropName::Ident(Ident::new("main".into(), span)),
+                value: Box::new(Expr::Lit(Lit::Bool(Bool {
+                    span,
+                    value: module_record.is_entry,
+                }))),
+            },
+        ])
+    }
+}
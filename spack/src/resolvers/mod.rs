@@ -2,66 +2,216 @@
 //!
 //! See: https://github.com/goto-bus-stop/node-resolve
 
+use self::package::{split_specifier, PackageJson, Target};
+pub use self::tsconfig::TsConfigResolver;
+use crate::config::ResolveConfig;
 use anyhow::{bail, Context, Error};
-use serde::Deserialize;
 use std::{
+    env,
+    ffi::OsString,
     fs::File,
     io::BufReader,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
 };
 use swc_bundler::Resolve;
 use swc_common::FileName;
 
-#[derive(Deserialize)]
-struct PackageJson {
-    #[serde(rename = "swc-main", default)]
-    swc_main: Option<String>,
-    #[serde(default)]
-    esnext: Option<String>,
-    #[serde(default)]
-    main: Option<String>,
-}
+mod package;
+mod tsconfig;
 
-pub struct NodeResolver;
+/// Resolves modules like node.js and webpack.
+///
+/// [NodeResolver::new] uses the default options, and
+/// [NodeResolver::from_config] uses `resolve` of the spack config.
+pub struct NodeResolver {
+    /// Without leading `.`.
+    extensions: Vec<String>,
+    main_fields: Vec<String>,
+    /// Sorted by length of the key, in descending order.
+    alias: Vec<(String, PathBuf)>,
+    alias_fields: Vec<String>,
+    description_files: Vec<String>,
+    /// Conditions used for `exports` and `imports` of `package.json`.
+    /// `default` always matches.
+    ///
+    /// `require` is not used by default, because requests are resolved
+    /// regardless of whether they are `import`s or `require()` calls, and
+    /// bundled modules are ES modules.
+    conditions: Vec<String>,
+    enforce_extension: bool,
+    symlinks: bool,
+}
 
 static EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "json", "node"];
 
+static MAIN_FIELDS: &[&str] = &["swc-main", "esnext", "module", "main"];
+
+impl Default for NodeResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl NodeResolver {
     pub fn new() -> Self {
-        Self
+        NodeResolver {
+            extensions: EXTENSIONS.iter().map(|s| s.to_string()).collect(),
+            main_fields: MAIN_FIELDS.iter().map(|s| s.to_string()).collect(),
+            alias: vec![],
+            alias_fields: vec![],
+            description_files: vec!["package.json".into()],
+            conditions: vec!["import".into(), "node".into()],
+            enforce_extension: false,
+            symlinks: true,
+        }
+    }
+
+    /// If `conditionNames` is empty, `import` and one of `browser` and `node`
+    /// are used. `browser` is used if it's in `aliasFields` or
+    /// `mainFields`.
+    pub fn from_config(config: &ResolveConfig) -> Self {
+        let mut resolver = Self::new();
+
+        let extensions: Vec<String> = config
+            .extensions
+            .iter()
+            .map(|ext| ext.trim_start_matches('.').to_string())
+            .filter(|ext| !ext.is_empty())
+            .collect();
+        if !extensions.is_empty() {
+            resolver.extensions = extensions;
+        }
+
+        if !config.main_fields.is_empty() {
+            resolver.main_fields = config.main_fields.iter().map(|s| s.to_string()).collect();
+        }
+
+        if let Some(alias) = &config.alias {
+            resolver.alias = alias
+                .map
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect();
+            resolver
+                .alias
+                .sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        }
+
+        resolver.alias_fields = config.alias_fields.iter().map(|s| s.to_string()).collect();
+
+        if !config.description_files.is_empty() {
+            resolver.description_files = config
+                .description_files
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+        }
+
+        if config.condition_names.is_empty() {
+            let browser = resolver
+                .alias_fields
+                .iter()
+                .chain(resolver.main_fields.iter())
+                .any(|f| f == "browser");
+
+            resolver.conditions = vec![
+                "import".into(),
+                if browser { "browser" } else { "node" }.into(),
+            ];
+        } else {
+            resolver.conditions = config
+                .condition_names
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+        }
+
+        resolver.enforce_extension = config.enforce_extension;
+        resolver.symlinks = config.symlinks;
+
+        resolver
     }
 
     fn wrap(&self, path: PathBuf) -> Result<FileName, Error> {
-        Ok(FileName::Real(
-            path.canonicalize().context("failaed to canonicalize")?,
-        ))
+        if self.symlinks {
+            return Ok(FileName::Real(
+                path.canonicalize().context("failed to canonicalize")?,
+            ));
+        }
+
+        let path = if path.is_absolute() {
+            path
+        } else {
+            env::current_dir()
+                .context("failed to get the current directory")?
+                .join(path)
+        };
+
+        let mut normalized = PathBuf::new();
+        for c in path.components() {
+            match c {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                _ => normalized.push(c),
+            }
+        }
+
+        Ok(FileName::Real(normalized))
+    }
+
+    /// Resolve a path as a file or a directory.
+    fn resolve_path(&self, path: &Path) -> Result<PathBuf, Error> {
+        self.resolve_as_file(path)
+            .or_else(|_| self.resolve_as_directory(path))
     }
 
     /// Resolve a path as a file. If `path` refers to a file, it is returned;
     /// otherwise the `path` + each extension is tried.
     fn resolve_as_file(&self, path: &Path) -> Result<PathBuf, Error> {
         // 1. If X is a file, load X as JavaScript text.
-        if path.is_file() {
+        if path.is_file() && (!self.enforce_extension || self.has_known_extension(path)) {
             return Ok(path.to_path_buf());
         }
 
-        for ext in EXTENSIONS {
-            let ext_path = path.with_extension(ext);
+        for ext in &self.extensions {
+            let mut ext_path = OsString::from(path.as_os_str());
+            ext_path.push(".");
+            ext_path.push(ext);
+            let ext_path = PathBuf::from(ext_path);
             if ext_path.is_file() {
                 return Ok(ext_path);
             }
         }
 
+        // `./foo.js` may refer to `./foo.ts`.
+        if path.extension().is_some() {
+            for ext in &self.extensions {
+                let ext_path = path.with_extension(ext);
+                if ext_path.is_file() {
+                    return Ok(ext_path);
+                }
+            }
+        }
+
         bail!("file not found: {}", path.display())
     }
 
-    /// Resolve a path as a directory, using the "main" key from a package.json
-    /// file if it exists, or resolving to the index.EXT file if it exists.
-    fn resolve_as_directory(&self, path: &PathBuf) -> Result<PathBuf, Error> {
+    fn has_known_extension(&self, path: &Path) -> bool {
+        match path.extension() {
+            Some(ext) => self.extensions.iter().any(|e| ext == &**e),
+            None => false,
+        }
+    }
+
+    /// Resolve a path as a directory, using the main fields from a
+    /// package.json file if it exists, or resolving to the index.EXT file if
+    /// it exists.
+    fn resolve_as_directory(&self, path: &Path) -> Result<PathBuf, Error> {
         // 1. If X/package.json is a file, use it.
-        let pkg_path = path.join("package.json");
-        if pkg_path.is_file() {
-            let main = self.resolve_package_main(&pkg_path);
+        if let Some(pkg) = self.load_package(path)? {
+            let main = self.resolve_package_main(path, &pkg);
             if main.is_ok() {
                 return main;
             }
@@ -71,34 +221,30 @@ impl NodeResolver {
         self.resolve_index(path)
     }
 
-    /// Resolve using the package.json "main" key.
-    fn resolve_package_main(&self, pkg_path: &PathBuf) -> Result<PathBuf, Error> {
-        // TODO how to not always initialise this here?
-        let root = PathBuf::from("/");
-        let pkg_dir = pkg_path.parent().unwrap_or(&root);
-        let file = File::open(pkg_path)?;
-        let reader = BufReader::new(file);
-        let pkg: PackageJson =
-            serde_json::from_reader(reader).context("failed to deserialize package.json")?;
-
-        for main in &[&pkg.swc_main, &pkg.esnext, &pkg.main] {
-            if let Some(target) = main {
+    /// Resolve using the main fields of package.json.
+    fn resolve_package_main(&self, pkg_dir: &Path, pkg: &PackageJson) -> Result<PathBuf, Error> {
+        for field in &self.main_fields {
+            // `browser` may be an object, which is handled as an alias field.
+            if let Some(target) = pkg.fields.get(field).and_then(|v| v.as_str()) {
                 let path = pkg_dir.join(target);
-                return self
-                    .resolve_as_file(&path)
-                    .or_else(|_| self.resolve_as_directory(&path));
+                if let Ok(path) = self.resolve_path(&path) {
+                    return Ok(path);
+                }
             }
         }
 
-        bail!("package.json does not contain a \"main\" string")
+        bail!(
+            "package.json in {} does not contain a valid main field",
+            pkg_dir.display()
+        )
     }
 
     /// Resolve a directory to its index.EXT.
-    fn resolve_index(&self, path: &PathBuf) -> Result<PathBuf, Error> {
+    fn resolve_index(&self, path: &Path) -> Result<PathBuf, Error> {
         // 1. If X/index.js is a file, load X/index.js as JavaScript text.
         // 2. If X/index.json is a file, parse X/index.json to a JavaScript object.
         // 3. If X/index.node is a file, load X/index.node as binary addon.
-        for ext in EXTENSIONS {
+        for ext in &self.extensions {
             let ext_path = path.join(format!("index.{}", ext));
             if ext_path.is_file() {
                 return Ok(ext_path);
@@ -110,21 +256,312 @@ impl NodeResolver {
 
     /// Resolve by walking up node_modules folders.
     fn resolve_node_modules(&self, base_dir: &Path, target: &str) -> Result<PathBuf, Error> {
-        let node_modules = base_dir.join("node_modules");
-        if node_modules.is_dir() {
-            let path = node_modules.join(target);
-            let result = self
-                .resolve_as_file(&path)
-                .or_else(|_| self.resolve_as_directory(&path));
-            if result.is_ok() {
-                return result;
+        let (name, subpath) = split_specifier(target);
+
+        for dir in base_dir.ancestors() {
+            let node_modules = dir.join("node_modules");
+            if !node_modules.is_dir() {
+                continue;
+            }
+
+            // `exports` replaces all other ways to resolve a package.
+            let pkg_dir = node_modules.join(name);
+            if pkg_dir.is_dir() {
+                if let Some(PackageJson {
+                    exports: Some(exports),
+                    ..
+                }) = self.load_package(&pkg_dir)?
+                {
+                    return self.resolve_exports(&pkg_dir, &exports, &subpath);
+                }
+            }
+
+            if let Ok(path) = self.resolve_path(&node_modules.join(target)) {
+                return Ok(path);
+            }
+        }
+
+        bail!(
+            "failed to find `{}` in node_modules of {}",
+            target,
+            base_dir.display()
+        )
+    }
+
+    fn resolve_exports(
+        &self,
+        pkg_dir: &Path,
+        exports: &Target,
+        subpath: &str,
+    ) -> Result<PathBuf, Error> {
+        let (target, pattern) = exports
+            .lookup(subpath)
+            .with_context(|| format!("`{}` is not exported from {}", subpath, pkg_dir.display()))?;
+
+        self.resolve_target(pkg_dir, target, pattern.as_deref(), false)?
+            .with_context(|| {
+                format!(
+                    "`{}` of {} is not exported for conditions {:?}",
+                    subpath,
+                    pkg_dir.display(),
+                    self.conditions
+                )
+            })
+    }
+
+    /// Resolve `#name` using `imports` of the closest package.json.
+    fn resolve_imports(&self, base_dir: &Path, target: &str) -> Result<PathBuf, Error> {
+        let (pkg_dir, pkg) = self
+            .find_package(base_dir)?
+            .with_context(|| format!("failed to find package.json for `{}`", target))?;
+        let imports = pkg
+            .imports
+            .as_ref()
+            .with_context(|| format!("{} does not have `imports`", pkg_dir.display()))?;
+
+        let (import, pattern) = imports.lookup(target).with_context(|| {
+            format!(
+                "`{}` is not defined by imports of {}",
+                target,
+                pkg_dir.display()
+            )
+        })?;
+
+        self.resolve_target(&pkg_dir, import, pattern.as_deref(), true)?
+            .with_context(|| {
+                format!(
+                    "`{}` of {} is not available for conditions {:?}",
+                    target,
+                    pkg_dir.display(),
+                    self.conditions
+                )
+            })
+    }
+
+    /// Returns `Ok(None)` if the target is excluded by `null` or no condition
+    /// matches.
+    ///
+    /// `allow_bare` is true for `imports`, which may map to other packages.
+    fn resolve_target(
+        &self,
+        pkg_dir: &Path,
+        target: &Target,
+        pattern: Option<&str>,
+        allow_bare: bool,
+    ) -> Result<Option<PathBuf>, Error> {
+        match target {
+            Target::Null => Ok(None),
+            Target::Path(s) => {
+                let s = match pattern {
+                    Some(pattern) if s.contains('*') => s.replace('*', pattern),
+                    Some(pattern) if s.ends_with('/') => format!("{}{}", s, pattern),
+                    _ => s.clone(),
+                };
+
+                if s.starts_with("./") {
+                    self.resolve_as_file(&pkg_dir.join(&s)).map(Some)
+                } else if allow_bare && !s.starts_with('.') && !s.starts_with('/') {
+                    self.resolve_node_modules(pkg_dir, &s).map(Some)
+                } else {
+                    bail!("invalid target `{}` in {}", s, pkg_dir.display())
+                }
+            }
+            Target::Array(targets) => {
+                let mut last_err = None;
+                for target in targets {
+                    match self.resolve_target(pkg_dir, target, pattern, allow_bare) {
+                        Ok(Some(path)) => return Ok(Some(path)),
+                        Ok(None) => {}
+                        Err(err) => last_err = Some(err),
+                    }
+                }
+
+                match last_err {
+                    Some(err) => Err(err),
+                    None => Ok(None),
+                }
+            }
+            Target::Map(conditions) => {
+                for (condition, target) in conditions {
+                    if condition == "default" || self.conditions.contains(condition) {
+                        if let Some(path) =
+                            self.resolve_target(pkg_dir, target, pattern, allow_bare)?
+                        {
+                            return Ok(Some(path));
+                        }
+                    }
+                }
+
+                Ok(None)
+            }
+        }
+    }
+
+    /// Loads the description file in `dir`.
+    fn load_package(&self, dir: &Path) -> Result<Option<PackageJson>, Error> {
+        for name in &self.description_files {
+            let path = dir.join(name);
+            if !path.is_file() {
+                continue;
+            }
+
+            let file = File::open(&path)?;
+            let reader = BufReader::new(file);
+            let pkg = serde_json::from_reader(reader)
+                .with_context(|| format!("failed to deserialize {}", path.display()))?;
+            return Ok(Some(pkg));
+        }
+
+        Ok(None)
+    }
+
+    /// Finds the closest description file.
+    fn find_package(&self, dir: &Path) -> Result<Option<(PathBuf, PackageJson)>, Error> {
+        for dir in dir.ancestors() {
+            if let Some(pkg) = self.load_package(dir)? {
+                return Ok(Some((dir.to_path_buf(), pkg)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Finds `key` from the alias fields (e.g. `browser`) of `pkg`.
+    ///
+    /// Returns `Ok(None)` if `key` is not replaced.
+    fn resolve_alias_field(
+        &self,
+        pkg_dir: &Path,
+        pkg: &PackageJson,
+        keys: &[&str],
+    ) -> Result<Option<PathBuf>, Error> {
+        for field in &self.alias_fields {
+            let map = match pkg.fields.get(field).and_then(|v| v.as_object()) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            for &key in keys {
+                let value = match map.get(key) {
+                    Some(v) => v,
+                    None => continue,
+                };
+
+                return match value {
+                    serde_json::Value::String(s) if s.starts_with('.') => {
+                        self.resolve_path(&pkg_dir.join(s)).map(Some)
+                    }
+                    serde_json::Value::String(s) => self.resolve_node_modules(pkg_dir, s).map(Some),
+                    serde_json::Value::Bool(false) => bail!(
+                        "`{}` is disabled by `{}` of {}",
+                        key,
+                        field,
+                        pkg_dir.display()
+                    ),
+                    _ => bail!(
+                        "invalid value for `{}` in `{}` of {}",
+                        key,
+                        field,
+                        pkg_dir.display()
+                    ),
+                };
             }
         }
 
-        match base_dir.parent() {
-            Some(parent) => self.resolve_node_modules(parent, target),
-            None => bail!("not found"),
+        Ok(None)
+    }
+
+    /// Applies the alias fields of the closest package.json to a resolved
+    /// file.
+    fn replace_file(&self, path: PathBuf) -> Result<PathBuf, Error> {
+        if self.alias_fields.is_empty() {
+            return Ok(path);
+        }
+
+        let dir = match path.parent() {
+            Some(v) => v,
+            None => return Ok(path),
+        };
+        let (pkg_dir, pkg) = match self.find_package(dir)? {
+            Some(v) => v,
+            None => return Ok(path),
+        };
+
+        let rel = match path.strip_prefix(&pkg_dir) {
+            Ok(v) => v,
+            Err(_) => return Ok(path),
+        };
+        let rel: Vec<_> = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect();
+        let rel = format!("./{}", rel.join("/"));
+        let without_ext = match path.extension() {
+            Some(ext) => rel[..rel.len() - ext.len() - 1].to_string(),
+            None => rel.clone(),
+        };
+
+        Ok(self
+            .resolve_alias_field(&pkg_dir, &pkg, &[&rel, &without_ext])?
+            .unwrap_or(path))
+    }
+
+    fn resolve_specifier(&self, base_dir: &Path, target: &str) -> Result<PathBuf, Error> {
+        for (key, value) in &self.alias {
+            let rest = if key.ends_with('$') {
+                if target != &key[..key.len() - 1] {
+                    continue;
+                }
+                ""
+            } else if target == key {
+                ""
+            } else if target.starts_with(&**key) && target[key.len()..].starts_with('/') {
+                &target[key.len() + 1..]
+            } else {
+                continue;
+            };
+
+            let aliased = if rest.is_empty() {
+                value.clone()
+            } else {
+                value.join(rest)
+            };
+
+            // Aliases are not applied recursively.
+            return if aliased.is_absolute() {
+                self.resolve_path(&aliased)
+            } else {
+                self.resolve_request(base_dir, &aliased.to_string_lossy())
+            };
+        }
+
+        self.resolve_request(base_dir, target)
+    }
+
+    fn resolve_request(&self, base_dir: &Path, target: &str) -> Result<PathBuf, Error> {
+        if target.starts_with('#') {
+            return self.resolve_imports(base_dir, target);
+        }
+
+        // Absolute path
+        if target.starts_with('/') {
+            return self.resolve_path(Path::new(target));
+        }
+
+        if target == "." || target == ".." || target.starts_with("./") || target.starts_with("../")
+        {
+            return self.resolve_path(&base_dir.join(target));
         }
+
+        if !self.alias_fields.is_empty() {
+            if let Some((pkg_dir, pkg)) = self.find_package(base_dir)? {
+                if let Some(path) = self.resolve_alias_field(&pkg_dir, &pkg, &[target])? {
+                    return Ok(path);
+                }
+            }
+        }
+
+        self.resolve_node_modules(base_dir, target)
     }
 }
 
@@ -135,29 +572,12 @@ impl Resolve for NodeResolver {
             _ => bail!("node-resolver supports only files"),
         };
 
-        // Absolute path
-        if target.starts_with("/") {
-            let base_dir = &Path::new("/");
-
-            let path = base_dir.join(target);
-            return self
-                .resolve_as_file(&path)
-                .or_else(|_| self.resolve_as_directory(&path))
-                .and_then(|p| self.wrap(p));
-        }
-
         let cwd = &Path::new(".");
         let base_dir = base.parent().unwrap_or(&cwd);
 
-        if target.starts_with("./") || target.starts_with("../") {
-            let path = base_dir.join(target);
-            return self
-                .resolve_as_file(&path)
-                .or_else(|_| self.resolve_as_directory(&path))
-                .and_then(|p| self.wrap(p));
-        }
+        let path = self.resolve_specifier(base_dir, target)?;
+        let path = self.replace_file(path)?;
 
-        self.resolve_node_modules(base_dir, target)
-            .and_then(|p| self.wrap(p))
+        self.wrap(path)
     }
 }
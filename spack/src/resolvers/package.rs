@@ -0,0 +1,165 @@
+//! `package.json` and its `exports` / `imports` maps.
+//!
+//! See: https://nodejs.org/api/packages.html
+
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use std::{collections::HashMap, fmt};
+
+#[derive(Debug, Deserialize)]
+pub(super) struct PackageJson {
+    #[serde(default)]
+    pub exports: Option<Target>,
+    #[serde(default)]
+    pub imports: Option<Target>,
+    /// Main fields and alias fields. Names of them are configurable.
+    #[serde(flatten)]
+    pub fields: HashMap<String, serde_json::Value>,
+}
+
+/// A value of `exports` or `imports`.
+///
+/// Order of conditions matters, so we don't use [serde_json::Value].
+#[derive(Debug)]
+pub(super) enum Target {
+    /// `null` or `false`, which means that the path is not exported.
+    Null,
+    Path(String),
+    Array(Vec<Target>),
+    Map(Vec<(String, Target)>),
+}
+
+impl<'de> Deserialize<'de> for Target {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TargetVisitor;
+
+        impl<'de> Visitor<'de> for TargetVisitor {
+            type Value = Target;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string, an array, an object or null")
+            }
+
+            fn visit_unit<E>(self) -> Result<Target, E> {
+                Ok(Target::Null)
+            }
+
+            fn visit_none<E>(self) -> Result<Target, E> {
+                Ok(Target::Null)
+            }
+
+            fn visit_bool<E>(self, _: bool) -> Result<Target, E> {
+                Ok(Target::Null)
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Target, E> {
+                Ok(Target::Path(v.to_string()))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Target, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut items = vec![];
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                Ok(Target::Array(items))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Target, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut items = vec![];
+                while let Some(entry) = map.next_entry()? {
+                    items.push(entry);
+                }
+                Ok(Target::Map(items))
+            }
+        }
+
+        deserializer.deserialize_any(TargetVisitor)
+    }
+}
+
+impl Target {
+    /// Finds the target of `request` from a subpath map.
+    ///
+    /// Returns the target and the string matched by `*`, if any.
+    ///
+    /// `exports` may be a target for `.` itself, but `imports` is always a
+    /// map.
+    pub fn lookup<'a>(&'a self, request: &str) -> Option<(&'a Target, Option<String>)> {
+        let map = match self {
+            Target::Map(map)
+                if map
+                    .iter()
+                    .all(|(k, _)| k.starts_with('.') || k.starts_with('#')) =>
+            {
+                map
+            }
+            _ => {
+                if request == "." {
+                    return Some((self, None));
+                }
+                return None;
+            }
+        };
+
+        if let Some((_, target)) = map.iter().find(|(k, _)| k == request && !k.contains('*')) {
+            return Some((target, None));
+        }
+
+        // The longest prefix wins.
+        let mut best: Option<(&str, &Target, String)> = None;
+        for (key, target) in map {
+            let matched = match key.find('*') {
+                Some(idx) => {
+                    let (prefix, suffix) = (&key[..idx], &key[idx + 1..]);
+                    if request.len() >= key.len() - 1
+                        && request.starts_with(prefix)
+                        && request.ends_with(suffix)
+                    {
+                        Some((prefix, &request[prefix.len()..request.len() - suffix.len()]))
+                    } else {
+                        None
+                    }
+                }
+                // Deprecated folder mappings like `"./dir/": "./lib/"`.
+                None if key.ends_with('/') && request.starts_with(&**key) => {
+                    Some((&**key, &request[key.len()..]))
+                }
+                None => None,
+            };
+
+            if let Some((prefix, rest)) = matched {
+                if best.as_ref().map_or(true, |b| b.0.len() < prefix.len()) {
+                    best = Some((prefix, target, rest.to_string()));
+                }
+            }
+        }
+
+        best.map(|(_, target, rest)| (target, Some(rest)))
+    }
+}
+
+/// Splits a bare specifier into the package name and the subpath.
+///
+/// `@scope/pkg/a/b` => (`@scope/pkg`, `./a/b`)
+pub(super) fn split_specifier(target: &str) -> (&str, String) {
+    let mut idx = target.find('/');
+    if target.starts_with('@') {
+        idx = idx.and_then(|first| target[first + 1..].find('/').map(|v| v + first + 1));
+    }
+
+    match idx {
+        Some(idx) => (&target[..idx], format!(".{}", &target[idx..])),
+        None => (target, ".".into()),
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::{Context, Error};
+use std::path::{Path, PathBuf};
+use swc::config::TsConfigFile;
+use swc_bundler::Resolve;
+use swc_common::FileName;
+
+/// Resolves modules using `baseUrl` and `paths` of a `tsconfig.json` file,
+/// and delegates to `inner` for everything else.
+///
+/// Candidates from `paths` and `baseUrl` are passed to `inner` as absolute
+/// paths, so extensions and `index` files are handled by `inner`.
+pub struct TsConfigResolver<R> {
+    inner: R,
+    base_url: Option<PathBuf>,
+    /// Sorted by length of the prefix, in descending order.
+    paths: Vec<(String, Vec<String>)>,
+    paths_base: PathBuf,
+}
+
+impl<R> TsConfigResolver<R>
+where
+    R: Resolve,
+{
+    /// `extends` of the tsconfig file is applied.
+    pub fn new(inner: R, tsconfig: &Path) -> Result<Self, Error> {
+        let file = TsConfigFile::load(tsconfig)?;
+        Ok(Self::from_file(inner, file))
+    }
+
+    pub fn from_file(inner: R, file: TsConfigFile) -> Self {
+        let opts = file.compiler_options;
+
+        let mut paths: Vec<_> = opts.paths.unwrap_or_default().into_iter().collect();
+        paths.sort_by(|a, b| {
+            let prefix = |s: &str| s.find('*').unwrap_or_else(|| s.len());
+            prefix(&b.0).cmp(&prefix(&a.0)).then_with(|| a.0.cmp(&b.0))
+        });
+
+        TsConfigResolver {
+            inner,
+            base_url: opts.base_url,
+            paths,
+            paths_base: opts.paths_base.unwrap_or_default(),
+        }
+    }
+
+    /// Returns the substitutions from `paths` matching `target`.
+    fn candidates(&self, target: &str) -> Vec<PathBuf> {
+        // Exact matches win.
+        if let Some((_, subs)) = self.paths.iter().find(|(k, _)| k == target) {
+            return subs.iter().map(|s| self.paths_base.join(s)).collect();
+        }
+
+        for (pattern, subs) in &self.paths {
+            let idx = match pattern.find('*') {
+                Some(v) => v,
+                None => continue,
+            };
+            let (prefix, suffix) = (&pattern[..idx], &pattern[idx + 1..]);
+
+            if target.len() >= prefix.len() + suffix.len()
+                && target.starts_with(prefix)
+                && target.ends_with(suffix)
+            {
+                let matched = &target[prefix.len()..target.len() - suffix.len()];
+                return subs
+                    .iter()
+                    .map(|s| self.paths_base.join(s.replace('*', matched)))
+                    .collect();
+            }
+        }
+
+        vec![]
+    }
+}
+
+impl<R> Resolve for TsConfigResolver<R>
+where
+    R: Resolve,
+{
+    fn resolve(&self, base: &FileName, target: &str) -> Result<FileName, Error> {
+        let is_relative = target.starts_with("./")
+            || target.starts_with("../")
+            || target == "."
+            || target == "..";
+
+        if !is_relative && !Path::new(target).is_absolute() {
+            for path in self.candidates(target) {
+                if let Ok(v) = self.inner.resolve(base, &path.to_string_lossy()) {
+                    return Ok(v);
+                }
+            }
+
+            if let Some(base_url) = &self.base_url {
+                if let Ok(v) = self
+                    .inner
+                    .resolve(base, &base_url.join(target).to_string_lossy())
+                {
+                    return Ok(v);
+                }
+            }
+        }
+
+        self.inner
+            .resolve(base, target)
+            .with_context(|| format!("failed to resolve `{}` with tsconfig", target))
+    }
+}
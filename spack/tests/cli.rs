@@ -0,0 +1,59 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    process::Command,
+};
+
+fn spack() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_spack"))
+}
+
+#[test]
+fn config_and_flags() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    create_dir_all(root.join("src")).unwrap();
+    write(
+        root.join("src/a.js"),
+        "import { b } from './b';\nconsole.log(b, process.env.NODE_ENV);",
+    )
+    .unwrap();
+    write(root.join("src/b.js"), "export const b = 'b';").unwrap();
+    write(
+        root.join("spack.config.json"),
+        r#"{
+            "entry": { "main": "src/a.js" },
+            "output": { "path": "dist", "name": "[name].bundle.js" }
+        }"#,
+    )
+    .unwrap();
+
+    let status = spack().current_dir(root).status().unwrap();
+    assert!(status.success());
+    let code = read_to_string(root.join("dist/main.bundle.js")).unwrap();
+    assert!(code.contains("'b'"), "{}", code);
+    assert!(!code.contains("import"), "{}", code);
+
+    // Flags override the config file.
+    let status = spack()
+        .current_dir(root)
+        .args(&["--entry", "src/a.js", "-o", "out", "--mode", "production"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let code = read_to_string(root.join("out/a.bundle.js")).unwrap();
+    assert!(code.contains("\"production\""), "{}", code);
+    assert!(!code.contains('\n'), "{}", code);
+}
+
+#[test]
+fn missing_entry() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = spack()
+        .current_dir(dir.path())
+        .args(&["-o", "dist"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("entry is not specified"));
+}
@@ -0,0 +1,136 @@
+use anyhow::Error;
+use spack::{config::Config, emitter::Emitter, loaders::swc::SwcLoader, resolvers::NodeResolver};
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    sync::Arc,
+};
+use swc_bundler::{Bundler, ModuleRecord};
+use swc_common::{Span, GLOBALS};
+use swc_ecma_ast::KeyValueProp;
+
+struct Hook;
+
+impl swc_bundler::Hook for Hook {
+    fn get_import_meta_props(&self, _: Span, _: &ModuleRecord) -> Result<Vec<KeyValueProp>, Error> {
+        Ok(vec![])
+    }
+}
+
+fn bundle(config: serde_json::Value) -> (Config, spack::emitter::EmitSummary) {
+    let config: Config = serde_json::from_value(config).unwrap();
+
+    testing::run_test2(false, |cm, handler| {
+        let compiler = Arc::new(swc::Compiler::new(cm.clone(), Arc::new(handler)));
+
+        GLOBALS.set(compiler.globals(), || {
+            let loader = SwcLoader::new(
+                compiler.clone(),
+                swc::config::Options {
+                    swcrc: false,
+                    ..Default::default()
+                },
+            );
+            let bundler = Bundler::new(
+                compiler.globals(),
+                cm.clone(),
+                &loader,
+                NodeResolver::new(),
+                swc_bundler::Config {
+                    require: true,
+                    disable_inliner: true,
+                    ..Default::default()
+                },
+                Box::new(Hook),
+            );
+
+            let bundles = bundler.bundle(config.entry.clone().into()).unwrap();
+            let summary = Emitter::new(&compiler, &config)
+                .emit(&config, bundles)
+                .unwrap();
+
+            Ok(summary)
+        })
+    })
+    .map(|summary| (config, summary))
+    .unwrap()
+}
+
+#[test]
+fn emit() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    create_dir_all(root.join("src")).unwrap();
+    write(
+        root.join("src/a.js"),
+        "import { c } from './c';\nconsole.log('a', c);",
+    )
+    .unwrap();
+    write(root.join("src/b.js"), "console.log('b');").unwrap();
+    write(root.join("src/c.js"), "export const c = 'c';").unwrap();
+
+    let (_, summary) = bundle(serde_json::json!({
+        "working_dir": root,
+        "entry": {
+            "a": root.join("src/a.js"),
+            "b": root.join("src/b.js"),
+        },
+        "output": {
+            "path": "dist",
+            "name": "[name].bundle.js",
+        },
+        "options": {
+            "sourceMaps": true,
+        },
+    }));
+
+    assert_eq!(summary.files.len(), 2);
+    let a = read_to_string(root.join("dist/a.bundle.js")).unwrap();
+    assert!(a.ends_with("//# sourceMappingURL=a.bundle.js.map"));
+    assert!(root.join("dist/a.bundle.js.map").exists());
+    assert!(root.join("dist/b.bundle.js").exists());
+
+    let file = summary
+        .files
+        .iter()
+        .find(|f| f.path.ends_with("a.bundle.js"))
+        .unwrap();
+    assert_eq!(file.size, a.len());
+    assert!(file.map_size.is_some());
+    assert!(summary.total_size() > a.len());
+}
+
+#[test]
+fn emit_dynamic_import() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    create_dir_all(root.join("src/pages")).unwrap();
+    write(
+        root.join("src/a.js"),
+        "import('./pages/d').then(({ d }) => console.log('a', d));",
+    )
+    .unwrap();
+    write(root.join("src/pages/d.js"), "export const d = 'd';").unwrap();
+
+    let (_, summary) = bundle(serde_json::json!({
+        "working_dir": root,
+        "entry": {
+            "a": root.join("src/a.js"),
+        },
+        "output": {
+            "path": "dist",
+            "name": "[name].bundle.js",
+        },
+    }));
+
+    assert_eq!(summary.files.len(), 2);
+    let d = summary
+        .files
+        .iter()
+        .map(|f| f.path.file_name().unwrap().to_string_lossy().into_owned())
+        .find(|name| name.starts_with("dynamic."))
+        .unwrap();
+
+    let a = read_to_string(root.join("dist/a.bundle.js")).unwrap();
+    assert!(a.contains(&format!("./{}", d)), "{}", a);
+    assert!(!a.contains("./pages/d"), "{}", a);
+}
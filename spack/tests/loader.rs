@@ -0,0 +1,73 @@
+use spack::loaders::swc::SwcLoader;
+use std::{fs::write, sync::Arc};
+use swc_bundler::Load;
+use swc_common::{FileName, GLOBALS};
+
+/// Loads `path` with a fresh compiler, like a new process does.
+fn load(
+    cache_dir: &std::path::Path,
+    path: &std::path::Path,
+    source_maps: bool,
+) -> (String, String, Vec<&'static str>) {
+    testing::run_test2(false, |cm, handler| {
+        let compiler = Arc::new(swc::Compiler::new(cm, Arc::new(handler)));
+
+        GLOBALS.set(compiler.globals(), || {
+            let loader = SwcLoader::new(
+                compiler.clone(),
+                swc::config::Options {
+                    swcrc: false,
+                    cache_dir: Some(cache_dir.to_path_buf()),
+                    source_maps: Some(swc::config::SourceMapsConfig::Bool(source_maps)),
+                    ..Default::default()
+                },
+            );
+            let data = loader.load(&FileName::Real(path.to_path_buf())).unwrap();
+            let code = compiler
+                .print(
+                    &data.module,
+                    swc::config::SourceMapsConfig::Bool(false),
+                    None,
+                    false,
+                )
+                .unwrap()
+                .code;
+
+            Ok((data.fm.src.to_string(), code, data.helpers.used()))
+        })
+    })
+    .unwrap()
+}
+
+#[test]
+fn cache_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache_dir = dir.path().join("cache");
+    let path = dir.path().join("a.js");
+    write(&path, "export class Foo {}").unwrap();
+
+    let (src, code, helpers) = load(&cache_dir, &path, false);
+    assert_eq!(src, "export class Foo {}");
+    assert!(helpers.contains(&"class_call_check"));
+
+    let (cached_src, cached_code, cached_helpers) = load(&cache_dir, &path, false);
+    assert_ne!(cached_src, src, "module should be loaded from the cache");
+    assert_eq!(cached_code, code);
+    assert_eq!(cached_helpers, helpers);
+
+    write(&path, "export class Bar {}").unwrap();
+    let (src, _, _) = load(&cache_dir, &path, false);
+    assert_eq!(src, "export class Bar {}");
+}
+
+#[test]
+fn no_cache_with_source_maps() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache_dir = dir.path().join("cache");
+    let path = dir.path().join("a.js");
+    write(&path, "export class Foo {}").unwrap();
+
+    load(&cache_dir, &path, true);
+    let (src, _, _) = load(&cache_dir, &path, true);
+    assert_eq!(src, "export class Foo {}", "module should not be cached");
+}
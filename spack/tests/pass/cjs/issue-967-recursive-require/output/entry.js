@@ -14,34 +14,31 @@ function __spack_require__(mod) {
     };
 }
 var load = __spack_require__.bind(void 0, function(module, exports) {
-    console.log('c');
-});
-var load1 = __spack_require__.bind(void 0, function(module, exports) {
     console.log('b');
     module.exports = 'b';
 });
-var load2 = __spack_require__.bind(void 0, function(module, exports) {
-    var load3 = __spack_require__.bind(void 0, function(module1, exports1) {
+var load1 = __spack_require__.bind(void 0, function(module, exports) {
+    var load2 = __spack_require__.bind(void 0, function(module1, exports1) {
         console.log('a-b');
         exports1.default = 'ab';
     });
-    var load4 = __spack_require__.bind(void 0, function(module1, exports1) {
-        var load5 = __spack_require__.bind(void 0, function(module2, exports2) {
+    var load3 = __spack_require__.bind(void 0, function(module1, exports1) {
+        var load4 = __spack_require__.bind(void 0, function(module2, exports2) {
             module2.exports = {
                 default: 'a-a-a'
             };
         });
-        module1.exports = load5();
+        module1.exports = load4();
     });
-    var aa = load4();
-    var bb = load3();
-    load1();
+    var aa = load3();
+    var bb = load2();
+    load();
     module.exports = {
         aa: aa,
         bb: bb
     };
 });
-load2();
-var b = load1();
-load();
+load1();
+var b = load();
+console.log('c');
 console.log(b);
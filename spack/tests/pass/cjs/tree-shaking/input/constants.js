@@ -0,0 +1,3 @@
+exports.PI = 3.14;
+exports.E = 2.71;
+exports.log = console.log('constants');
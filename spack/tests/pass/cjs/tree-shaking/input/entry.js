@@ -0,0 +1,4 @@
+import { add } from './math';
+const { PI: pi } = require('./constants');
+
+console.log(add(1, 2), pi);
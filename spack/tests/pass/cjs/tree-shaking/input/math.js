@@ -0,0 +1,10 @@
+function add(a, b) {
+    return a + b;
+}
+
+function sub(a, b) {
+    return a - b;
+}
+
+module.exports = { add, sub };
+module.exports.mul = (a, b) => a * b;
@@ -0,0 +1,9 @@
+function add(a, b) {
+    return a + b;
+}
+var add1 = add;
+const add2 = add1;
+var PI = 3.14;
+console.log('constants');
+const pi = PI;
+console.log(add2(1, 2), pi);
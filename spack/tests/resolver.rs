@@ -0,0 +1,277 @@
+use serde_json::json;
+use spack::{
+    config::ResolveConfig,
+    resolvers::{NodeResolver, TsConfigResolver},
+};
+use std::{
+    fs::{create_dir_all, write},
+    path::Path,
+};
+use swc_bundler::Resolve;
+use swc_common::FileName;
+use tempfile::TempDir;
+
+fn setup(files: &[(&str, &str)]) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for (path, content) in files {
+        let path = dir.path().join(path);
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(path, content).unwrap();
+    }
+    dir
+}
+
+fn config(v: serde_json::Value) -> NodeResolver {
+    let config: ResolveConfig = serde_json::from_value(v).unwrap();
+    NodeResolver::from_config(&config)
+}
+
+fn resolve(r: &dyn Resolve, root: &Path, target: &str) -> String {
+    let base = FileName::Real(root.join("src").join("entry.js"));
+    match r.resolve(&base, target).unwrap() {
+        FileName::Real(path) => path
+            .strip_prefix(root.canonicalize().unwrap())
+            .unwrap()
+            .to_string_lossy()
+            .replace('\\', "/"),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn exports_conditions() {
+    let dir = setup(&[
+        ("src/entry.js", ""),
+        (
+            "node_modules/pkg/package.json",
+            r#"{
+                "main": "./main.js",
+                "exports": {
+                    ".": {
+                        "browser": "./browser.js",
+                        "import": "./esm.mjs",
+                        "default": "./main.js"
+                    },
+                    "./feature": { "node": "./feature-node.js", "default": "./feature.js" },
+                    "./dual": { "require": "./dual.cjs", "import": "./dual.mjs" },
+                    "./utils/*": "./lib/utils/*.js",
+                    "./internal/*": null
+                }
+            }"#,
+        ),
+        ("node_modules/pkg/main.js", ""),
+        ("node_modules/pkg/esm.mjs", ""),
+        ("node_modules/pkg/browser.js", ""),
+        ("node_modules/pkg/feature.js", ""),
+        ("node_modules/pkg/feature-node.js", ""),
+        ("node_modules/pkg/dual.cjs", ""),
+        ("node_modules/pkg/dual.mjs", ""),
+        ("node_modules/pkg/lib/utils/a.js", ""),
+        ("node_modules/pkg/internal/b.js", ""),
+    ]);
+    let root = dir.path();
+
+    let r = NodeResolver::new();
+    assert_eq!(resolve(&r, root, "pkg"), "node_modules/pkg/esm.mjs");
+    assert_eq!(
+        resolve(&r, root, "pkg/feature"),
+        "node_modules/pkg/feature-node.js"
+    );
+    assert_eq!(resolve(&r, root, "pkg/dual"), "node_modules/pkg/dual.mjs");
+    assert_eq!(
+        resolve(&r, root, "pkg/utils/a"),
+        "node_modules/pkg/lib/utils/a.js"
+    );
+    // `exports` hides other files.
+    assert!(r
+        .resolve(&FileName::Real(root.join("src/entry.js")), "pkg/main.js")
+        .is_err());
+    assert!(r
+        .resolve(&FileName::Real(root.join("src/entry.js")), "pkg/internal/b")
+        .is_err());
+
+    let r = config(json!({ "aliasFields": ["browser"] }));
+    assert_eq!(resolve(&r, root, "pkg"), "node_modules/pkg/browser.js");
+    assert_eq!(
+        resolve(&r, root, "pkg/feature"),
+        "node_modules/pkg/feature.js"
+    );
+
+    let r = config(json!({ "conditionNames": ["require"] }));
+    assert_eq!(resolve(&r, root, "pkg"), "node_modules/pkg/main.js");
+    assert_eq!(resolve(&r, root, "pkg/dual"), "node_modules/pkg/dual.cjs");
+}
+
+#[test]
+fn imports() {
+    let dir = setup(&[
+        (
+            "package.json",
+            r##"{
+                "imports": {
+                    "#dep": { "node": "dep", "default": "./src/dep-polyfill.js" },
+                    "#internal/*": "./src/internal/*.js"
+                }
+            }"##,
+        ),
+        ("src/entry.js", ""),
+        ("src/dep-polyfill.js", ""),
+        ("src/internal/a.js", ""),
+        ("node_modules/dep/index.js", ""),
+    ]);
+    let root = dir.path();
+
+    let r = NodeResolver::new();
+    assert_eq!(resolve(&r, root, "#dep"), "node_modules/dep/index.js");
+    assert_eq!(resolve(&r, root, "#internal/a"), "src/internal/a.js");
+
+    let r = config(json!({ "conditionNames": ["browser"] }));
+    assert_eq!(resolve(&r, root, "#dep"), "src/dep-polyfill.js");
+}
+
+#[test]
+fn main_fields() {
+    let dir = setup(&[
+        ("src/entry.js", ""),
+        (
+            "node_modules/pkg/package.json",
+            r#"{ "main": "./main.js", "module": "./module.js", "browser": "./browser.js" }"#,
+        ),
+        ("node_modules/pkg/main.js", ""),
+        ("node_modules/pkg/module.js", ""),
+        ("node_modules/pkg/browser.js", ""),
+    ]);
+    let root = dir.path();
+
+    let r = NodeResolver::new();
+    assert_eq!(resolve(&r, root, "pkg"), "node_modules/pkg/module.js");
+
+    let r = config(json!({ "mainFields": ["browser", "module", "main"] }));
+    assert_eq!(resolve(&r, root, "pkg"), "node_modules/pkg/browser.js");
+
+    let r = config(json!({ "mainFields": ["main"] }));
+    assert_eq!(resolve(&r, root, "pkg"), "node_modules/pkg/main.js");
+}
+
+#[test]
+fn browser_field() {
+    let dir = setup(&[
+        ("src/entry.js", ""),
+        (
+            "node_modules/pkg/package.json",
+            r#"{
+                "main": "./lib/index.js",
+                "browser": {
+                    "./lib/node.js": "./lib/browser.js",
+                    "stream": "stream-browserify"
+                }
+            }"#,
+        ),
+        ("node_modules/pkg/lib/index.js", ""),
+        ("node_modules/pkg/lib/node.js", ""),
+        ("node_modules/pkg/lib/browser.js", ""),
+        ("node_modules/stream-browserify/index.js", ""),
+    ]);
+    let root = dir.path();
+    let base = FileName::Real(root.join("node_modules/pkg/lib/index.js"));
+
+    let r = NodeResolver::new();
+    let path = |f: FileName| match f {
+        FileName::Real(path) => path
+            .strip_prefix(root.canonicalize().unwrap())
+            .unwrap()
+            .to_string_lossy()
+            .replace('\\', "/"),
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        path(r.resolve(&base, "./node").unwrap()),
+        "node_modules/pkg/lib/node.js"
+    );
+
+    let r = config(json!({ "aliasFields": ["browser"] }));
+    assert_eq!(
+        path(r.resolve(&base, "./node").unwrap()),
+        "node_modules/pkg/lib/browser.js"
+    );
+    assert_eq!(
+        path(r.resolve(&base, "stream").unwrap()),
+        "node_modules/stream-browserify/index.js"
+    );
+}
+
+#[test]
+fn alias_and_extensions() {
+    let dir = setup(&[
+        ("src/entry.js", ""),
+        ("src/utils/a.ts", ""),
+        ("src/utils/index.mjs", ""),
+        ("src/raw", ""),
+        ("node_modules/preact/compat/index.mjs", ""),
+    ]);
+    let root = dir.path();
+
+    let r = config(json!({
+        "alias": {
+            "@utils": root.join("src/utils"),
+            "react$": "preact/compat",
+        },
+        "extensions": [".mjs", ".ts"],
+    }));
+    assert_eq!(resolve(&r, root, "@utils/a"), "src/utils/a.ts");
+    assert_eq!(resolve(&r, root, "@utils"), "src/utils/index.mjs");
+    assert_eq!(
+        resolve(&r, root, "react"),
+        "node_modules/preact/compat/index.mjs"
+    );
+    assert_eq!(resolve(&r, root, "./raw"), "src/raw");
+
+    let r = config(json!({
+        "enforceExtension": true,
+        "extensions": [".ts"],
+    }));
+    assert!(r
+        .resolve(&FileName::Real(root.join("src/entry.js")), "./raw")
+        .is_err());
+}
+
+#[test]
+fn tsconfig_paths() {
+    let dir = setup(&[
+        (
+            "tsconfig.base.json",
+            r#"{
+                // Relative to this file
+                "compilerOptions": {
+                    "baseUrl": "./src",
+                    "paths": {
+                        "@app/*": ["app/*", "fallback/*"],
+                        "@app/special": ["special.ts"],
+                        "shared": ["../packages/shared/index.ts"],
+                    },
+                },
+            }"#,
+        ),
+        (
+            "tsconfig.json",
+            r#"{ "extends": "./tsconfig.base.json", "compilerOptions": {} }"#,
+        ),
+        ("src/entry.js", ""),
+        ("src/app/a.ts", ""),
+        ("src/fallback/b.ts", ""),
+        ("src/special.ts", ""),
+        ("src/utils/index.ts", ""),
+        ("packages/shared/index.ts", ""),
+        ("node_modules/pkg/index.js", ""),
+    ]);
+    let root = dir.path();
+
+    let r = TsConfigResolver::new(NodeResolver::new(), &root.join("tsconfig.json")).unwrap();
+    assert_eq!(resolve(&r, root, "@app/a"), "src/app/a.ts");
+    assert_eq!(resolve(&r, root, "@app/b"), "src/fallback/b.ts");
+    assert_eq!(resolve(&r, root, "@app/special"), "src/special.ts");
+    assert_eq!(resolve(&r, root, "shared"), "packages/shared/index.ts");
+    // `baseUrl`
+    assert_eq!(resolve(&r, root, "utils"), "src/utils/index.ts");
+    assert_eq!(resolve(&r, root, "pkg"), "node_modules/pkg/index.js");
+}
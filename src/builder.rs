@@ -1,12 +1,15 @@
-use crate::config::{GlobalPassOption, JscTarget, ModuleConfig};
+use crate::config::{GlobalPassOption, JscTarget, Merge, ModuleConfig};
 use either::Either;
 use std::{collections::HashMap, sync::Arc};
 use swc_atoms::JsWord;
 use swc_common::{chain, comments::Comments, errors::Handler, Mark, SourceMap};
 use swc_ecma_parser::Syntax;
 use swc_ecma_transforms::{
-    compat, const_modules, fixer, helpers, hygiene, modules, pass::Optional,
-    proposals::import_assertions, typescript,
+    compat::{self, Assumptions},
+    const_modules, fixer, helpers, hygiene, modules,
+    pass::Optional,
+    proposals::import_assertions,
+    typescript,
 };
 
 /// Builder is used to create a high performance `Compiler`.
@@ -18,6 +21,7 @@ pub struct PassBuilder<'a, 'b, P: swc_ecma_visit::Fold> {
     global_mark: Mark,
     target: JscTarget,
     loose: bool,
+    assumptions: Assumptions,
     hygiene: bool,
     fixer: bool,
     inject_helpers: bool,
@@ -38,6 +42,7 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
             target: JscTarget::Es5,
             global_mark,
             loose,
+            assumptions: Default::default(),
             hygiene: true,
             env: None,
             fixer: true,
@@ -56,6 +61,7 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
             pass,
             target: self.target,
             loose: self.loose,
+            assumptions: self.assumptions,
             hygiene: self.hygiene,
             env: self.env,
             global_mark: self.global_mark,
@@ -97,6 +103,12 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
         self.then(pass)
     }
 
+    /// Note: `loose` implies all assumptions.
+    pub fn assumptions(mut self, assumptions: Assumptions) -> Self {
+        self.assumptions = assumptions;
+        self
+    }
+
     pub fn target(mut self, target: JscTarget) -> Self {
         self.target = target;
         self
@@ -135,8 +147,16 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
             Some(ModuleConfig::Es6) | None => false,
         };
 
+        let assumptions = if self.loose {
+            Assumptions::all()
+        } else {
+            self.assumptions
+        };
+
         // compat
-        let compat_pass = if let Some(env) = self.env {
+        let compat_pass = if let Some(mut env) = self.env {
+            env.assumptions.merge(&assumptions);
+
             Either::Left(chain!(
                 import_assertions(),
                 Optional::new(typescript::strip(), syntax.typescript()),
@@ -145,21 +165,65 @@ impl<'a, 'b, P: swc_ecma_visit::Fold> PassBuilder<'a, 'b, P> {
         } else {
             Either::Right(chain!(
                 import_assertions(),
-                Optional::new(compat::es2020::es2020(), self.target < JscTarget::Es2020),
+                Optional::new(
+                    compat::es2022::es2022_with_config(compat::es2022::Config {
+                        class_properties: compat::es2020::class_properties::Config {
+                            set_public_class_fields: assumptions.set_public_class_fields,
+                        },
+                    }),
+                    self.target < JscTarget::Es2022
+                ),
+                Optional::new(compat::es2021::es2021(), self.target < JscTarget::Es2021),
+                Optional::new(
+                    compat::es2020::es2020_with_config(compat::es2020::Config {
+                        nullish_coalescing: compat::es2020::nullish_coalescing::Config {
+                            no_document_all: assumptions.no_document_all,
+                        },
+                        optional_chaining: compat::es2020::opt_chaining::Config {
+                            no_document_all: assumptions.no_document_all,
+                        },
+                        class_properties: compat::es2020::class_properties::Config {
+                            set_public_class_fields: assumptions.set_public_class_fields,
+                        },
+                    }),
+                    self.target < JscTarget::Es2020
+                ),
                 Optional::new(typescript::strip(), syntax.typescript()),
-                Optional::new(compat::es2018(), self.target <= JscTarget::Es2018),
+                Optional::new(
+                    compat::es2018::es2018_with_config(compat::es2018::Config {
+                        object_rest_spread: compat::es2018::object_rest_spread::Config {
+                            object_rest_no_symbols: assumptions.object_rest_no_symbols,
+                            set_spread_properties: assumptions.set_spread_properties,
+                        },
+                    }),
+                    self.target <= JscTarget::Es2018
+                ),
                 Optional::new(compat::es2017(), self.target <= JscTarget::Es2017),
                 Optional::new(compat::es2016(), self.target <= JscTarget::Es2016),
                 Optional::new(
                     compat::es2015(
                         self.global_mark,
                         compat::es2015::Config {
+                            classes: compat::es2015::classes::Config {
+                                no_class_calls: assumptions.no_class_calls,
+                                constant_super: assumptions.constant_super,
+                                set_class_methods: assumptions.set_class_methods,
+                            },
+                            template_literal: compat::es2015::template_literal::Config {
+                                ignore_to_primitive_hint: assumptions.ignore_to_primitive_hint,
+                                mutable_template_object: assumptions.mutable_template_object,
+                            },
+                            computed_props: compat::es2015::computed_props::Config {
+                                set_computed_properties: assumptions.set_computed_properties,
+                            },
                             for_of: compat::es2015::for_of::Config {
-                                assume_array: self.loose
+                                assume_array: assumptions.iterable_is_array
+                            },
+                            spread: compat::es2015::spread::Config {
+                                loose: assumptions.iterable_is_array
                             },
-                            spread: compat::es2015::spread::Config { loose: self.loose },
                             destructuring: compat::es2015::destructuring::Config {
-                                loose: self.loose
+                                loose: assumptions.iterable_is_array
                             },
                         }
                     ),
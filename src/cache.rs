@@ -0,0 +1,120 @@
+//! Persistent cache for outputs of previous runs.
+
+use anyhow::{Context, Error};
+use serde::{de::DeserializeOwned, Serialize};
+use sha1::{Digest, Sha1};
+use std::{
+    fs::{self, File},
+    io::BufReader,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Bump this if the format of a cached item changes.
+const FORMAT_VERSION: u32 = 1;
+
+/// Makes names of temporary files unique in a process.
+static TMP_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A directory storing outputs of previous runs.
+///
+/// Items are stored as json files named by [CacheKey]. Broken or missing
+/// items are treated as cache misses.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+/// Hash of everything affecting a cached item.
+///
+/// Version of swc is always included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey(String);
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// `kind` is used to distinguish items stored by different users of a
+    /// cache directory.
+    pub fn key(&self, kind: &str, parts: &[&[u8]]) -> CacheKey {
+        let mut hasher = Sha1::new();
+        hasher.update(env!("CARGO_PKG_VERSION"));
+        hasher.update(FORMAT_VERSION.to_le_bytes());
+        hasher.update(kind);
+        for part in parts {
+            // Prevents `["ab", "c"]` and `["a", "bc"]` from having same key.
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part);
+        }
+
+        CacheKey(format!("{:x}", hasher.finalize()))
+    }
+
+    fn path(&self, key: &CacheKey) -> PathBuf {
+        self.dir
+            .join(&key.0[..2])
+            .join(format!("{}.json", &key.0[2..]))
+    }
+
+    pub fn get<T>(&self, key: &CacheKey) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        let file = File::open(self.path(key)).ok()?;
+
+        match serde_json::from_reader(BufReader::new(file)) {
+            Ok(v) => Some(v),
+            Err(err) => {
+                log::debug!("Ignoring broken cache item {:?}: {}", key, err);
+                None
+            }
+        }
+    }
+
+    /// Writes are atomic, so concurrent processes sharing the directory don't
+    /// see partially written items.
+    pub fn put<T>(&self, key: &CacheKey, value: &T) -> Result<(), Error>
+    where
+        T: Serialize,
+    {
+        let path = self.path(key);
+        let dir = path.parent().unwrap();
+        fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create cache directory: {}", dir.display()))?;
+
+        let tmp = dir.join(format!(
+            "{}.{}-{}.tmp",
+            &key.0[2..],
+            process::id(),
+            TMP_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let content = serde_json::to_vec(value).context("failed to serialize cache item")?;
+        fs::write(&tmp, content)
+            .with_context(|| format!("failed to write cache item: {}", tmp.display()))?;
+        fs::rename(&tmp, &path)
+            .with_context(|| format!("failed to write cache item: {}", path.display()))?;
+
+        Ok(())
+    }
+}
+
+/// Serializes `value` as a part of a [CacheKey].
+///
+/// Unlike `Debug`, the output does not depend on the iteration order of hash
+/// maps, which differs between processes.
+pub fn key_part<T>(value: &T) -> Result<String, Error>
+where
+    T: Serialize,
+{
+    // Objects of `serde_json::Value` are sorted by keys.
+    let value = serde_json::to_value(value).context("failed to serialize a part of cache key")?;
+
+    Ok(value.to_string())
+}
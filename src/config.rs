@@ -6,7 +6,7 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap},
     env,
     path::{Path, PathBuf},
     sync::Arc,
@@ -20,17 +20,20 @@ use swc_ecma_ext_transforms::jest;
 pub use swc_ecma_parser::JscTarget;
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
 use swc_ecma_transforms::{
-    compat::es2020::typescript_class_properties,
+    compat::{es2020::typescript_class_properties, Assumptions},
     const_modules, modules,
     optimization::{inline_globals, json_parse, simplifier},
     pass::{noop, Optional},
     proposals::{decorators, export_default_from},
-    react, resolver_with_mark, typescript,
+    react, resolver_with_mark, typescript, util,
 };
 use swc_ecma_visit::Fold;
 
+pub use self::tsconfig::{CompilerOptions, TsConfigFile};
+
 #[cfg(test)]
 mod tests;
+mod tsconfig;
 
 #[derive(Default, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -97,6 +100,16 @@ pub struct Options {
     #[serde(default = "default_swcrc")]
     pub swcrc: bool,
 
+    /// Path to a `tsconfig.json` file. If specified, options like `target` and
+    /// `experimentalDecorators` in it override the ones in `.swcrc`.
+    #[serde(default)]
+    pub tsconfig: Option<PathBuf>,
+
+    /// Directory to store transformed files. Files are transformed again only
+    /// if the content, the resolved config or the version of swc changes.
+    #[serde(default)]
+    pub cache_dir: Option<PathBuf>,
+
     #[cfg(not(target_arch = "wasm32"))]
     #[serde(default)]
     pub swcrc_roots: Option<PathBuf>,
@@ -183,14 +196,25 @@ impl Options {
             external_helpers,
             target,
             loose,
+            assumptions,
         } = config.jsc;
 
         let syntax = syntax.unwrap_or_default();
+        let external_helpers = external_helpers.unwrap_or(false);
+        let target = target.unwrap_or_default();
+        let loose = loose.unwrap_or(false);
         let mut transform = transform.unwrap_or_default();
 
         if syntax.typescript() {
-            transform.legacy_decorator = true;
+            transform.legacy_decorator = Some(true);
         }
+        let strip_config = {
+            let mut c = typescript::strip::Config::default();
+            if let Some(v) = transform.import_not_used_as_values {
+                c.import_not_used_as_values = v;
+            }
+            c
+        };
         let optimizer = transform.optimizer;
         let enable_optimizer = optimizer.is_some();
 
@@ -228,19 +252,27 @@ impl Options {
         let pass = chain!(
             // handle jsx
             Optional::new(
-                react::react(cm.clone(), comments, transform.react),
+                // Invalid jsx options are reported to `handler`.
+                util::HANDLER.set(handler, || react::react(
+                    cm.clone(),
+                    comments,
+                    transform.react.into()
+                )),
                 syntax.jsx()
             ),
             // Decorators may use type information
             Optional::new(
                 decorators(decorators::Config {
-                    legacy: transform.legacy_decorator,
-                    emit_metadata: transform.decorator_metadata,
+                    legacy: transform.legacy_decorator.unwrap_or(false),
+                    emit_metadata: transform.decorator_metadata.unwrap_or(false),
                 }),
                 syntax.decorators()
             ),
             Optional::new(typescript_class_properties(), syntax.typescript()),
-            Optional::new(typescript::strip(), syntax.typescript()),
+            Optional::new(
+                typescript::strip::strip_with_config(strip_config),
+                syntax.typescript()
+            ),
             resolver_with_mark(root_mark),
             const_modules,
             optimization,
@@ -250,6 +282,7 @@ impl Options {
         );
 
         let pass = PassBuilder::new(&cm, &handler, loose, root_mark, pass)
+            .assumptions(assumptions.into())
             .target(target)
             .skip_helper_injection(self.skip_helper_injection)
             .hygiene(!self.disable_hygiene)
@@ -336,12 +369,16 @@ impl Default for Rc {
                 jsc: JscConfig {
                     syntax: Some(Default::default()),
                     transform: None,
-                    external_helpers: false,
-                    target: Default::default(),
-                    loose: false,
+                    external_helpers: None,
+                    target: None,
+                    loose: None,
+                    assumptions: Default::default(),
                 },
                 module: None,
                 minify: None,
+                extends: None,
+                envs: Default::default(),
+                overrides: Default::default(),
             },
             Config {
                 env: None,
@@ -353,12 +390,16 @@ impl Default for Rc {
                         ..Default::default()
                     })),
                     transform: None,
-                    external_helpers: false,
-                    target: Default::default(),
-                    loose: false,
+                    external_helpers: None,
+                    target: None,
+                    loose: None,
+                    assumptions: Default::default(),
                 },
                 module: None,
                 minify: None,
+                extends: None,
+                envs: Default::default(),
+                overrides: Default::default(),
             },
             Config {
                 env: None,
@@ -370,12 +411,16 @@ impl Default for Rc {
                         ..Default::default()
                     })),
                     transform: None,
-                    external_helpers: false,
-                    target: Default::default(),
-                    loose: false,
+                    external_helpers: None,
+                    target: None,
+                    loose: None,
+                    assumptions: Default::default(),
                 },
                 module: None,
                 minify: None,
+                extends: None,
+                envs: Default::default(),
+                overrides: Default::default(),
             },
         ])
     }
@@ -415,7 +460,7 @@ impl Rc {
 }
 
 /// A single object in the `.swcrc` file
-#[derive(Debug, Default, Clone, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Config {
     #[serde(default)]
@@ -435,6 +480,21 @@ pub struct Config {
 
     #[serde(default)]
     pub minify: Option<bool>,
+
+    /// Path to a `.swcrc` file to extend. Relative paths are resolved from
+    /// the directory of the file declaring it.
+    #[serde(default)]
+    pub extends: Option<PathBuf>,
+
+    /// Configs merged if the key is equal to `envName`.
+    ///
+    /// This is `env` of babel. `env` is used for preset-env.
+    #[serde(default)]
+    pub envs: HashMap<String, Config>,
+
+    /// Configs merged if `test` and `exclude` of them match the file.
+    #[serde(default)]
+    pub overrides: Vec<Config>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -442,6 +502,14 @@ pub struct Config {
 pub enum FileMatcher {
     Regex(String),
     Multi(Vec<FileMatcher>),
+    /// `{ "glob": "src/**/*.ts" }`
+    ///
+    /// `*` and `?` do not match `/`, and `**/` matches any number of
+    /// directories. Patterns not starting with `/` may match any trailing
+    /// part of the path.
+    Glob {
+        glob: String,
+    },
 }
 
 impl Default for FileMatcher {
@@ -469,6 +537,9 @@ impl FileMatcher {
 
                 Ok(re.is_match(&filename.to_string_lossy()))
             }
+            FileMatcher::Glob { ref glob } => {
+                FileMatcher::Regex(glob_to_regex(glob)).matches(filename)
+            }
             FileMatcher::Multi(ref v) => {
                 //
                 for m in v {
@@ -483,6 +554,47 @@ impl FileMatcher {
     }
 }
 
+/// Converts a glob pattern to a regex matching the whole path.
+fn glob_to_regex(glob: &str) -> String {
+    let mut re = String::from(if glob.starts_with('/') {
+        "^"
+    } else {
+        "(?:^|/)"
+    });
+    let glob = glob.trim_start_matches('/');
+    let mut chars = glob.chars().peekable();
+    let mut in_group = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            '{' => {
+                in_group = true;
+                re.push_str("(?:");
+            }
+            '}' if in_group => {
+                in_group = false;
+                re.push(')');
+            }
+            ',' if in_group => re.push('|'),
+            _ => re.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+
+    re.push('$');
+    re
+}
+
 impl Config {
     pub fn matches(&self, filename: &Path) -> Result<bool, Error> {
         if let Some(ref exclude) = self.exclude {
@@ -524,13 +636,76 @@ pub struct JscConfig {
     pub transform: Option<TransformConfig>,
 
     #[serde(default)]
-    pub external_helpers: bool,
+    pub external_helpers: Option<bool>,
 
     #[serde(default)]
-    pub target: JscTarget,
+    pub target: Option<JscTarget>,
 
     #[serde(default)]
-    pub loose: bool,
+    pub loose: Option<bool>,
+
+    /// Ignored if `loose` is true, as it implies all assumptions.
+    #[serde(default)]
+    pub assumptions: AssumptionsConfig,
+}
+
+/// Assumptions of the compat passes.
+///
+/// Unlike [Assumptions], unset fields are `None` so that a config can turn off
+/// an assumption made by the config it extends.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct AssumptionsConfig {
+    #[serde(default)]
+    pub no_class_calls: Option<bool>,
+
+    #[serde(default)]
+    pub constant_super: Option<bool>,
+
+    #[serde(default)]
+    pub set_class_methods: Option<bool>,
+
+    #[serde(default)]
+    pub set_public_class_fields: Option<bool>,
+
+    #[serde(default)]
+    pub set_computed_properties: Option<bool>,
+
+    #[serde(default)]
+    pub ignore_to_primitive_hint: Option<bool>,
+
+    #[serde(default)]
+    pub mutable_template_object: Option<bool>,
+
+    #[serde(default)]
+    pub set_spread_properties: Option<bool>,
+
+    #[serde(default)]
+    pub object_rest_no_symbols: Option<bool>,
+
+    #[serde(default)]
+    pub no_document_all: Option<bool>,
+
+    #[serde(default)]
+    pub iterable_is_array: Option<bool>,
+}
+
+impl From<AssumptionsConfig> for Assumptions {
+    fn from(c: AssumptionsConfig) -> Self {
+        Assumptions {
+            no_class_calls: c.no_class_calls.unwrap_or(false),
+            constant_super: c.constant_super.unwrap_or(false),
+            set_class_methods: c.set_class_methods.unwrap_or(false),
+            set_public_class_fields: c.set_public_class_fields.unwrap_or(false),
+            set_computed_properties: c.set_computed_properties.unwrap_or(false),
+            ignore_to_primitive_hint: c.ignore_to_primitive_hint.unwrap_or(false),
+            mutable_template_object: c.mutable_template_object.unwrap_or(false),
+            set_spread_properties: c.set_spread_properties.unwrap_or(false),
+            object_rest_no_symbols: c.object_rest_no_symbols.unwrap_or(false),
+            no_document_all: c.no_document_all.unwrap_or(false),
+            iterable_is_array: c.iterable_is_array.unwrap_or(false),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -568,7 +743,7 @@ impl ModuleConfig {
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct TransformConfig {
     #[serde(default)]
-    pub react: react::Options,
+    pub react: ReactConfig,
 
     #[serde(default)]
     pub const_modules: Option<ConstModulesConfig>,
@@ -577,15 +752,55 @@ pub struct TransformConfig {
     pub optimizer: Option<OptimizerConfig>,
 
     #[serde(default)]
-    pub legacy_decorator: bool,
+    pub legacy_decorator: Option<bool>,
+
+    #[serde(default)]
+    pub decorator_metadata: Option<bool>,
 
     #[serde(default)]
-    pub decorator_metadata: bool,
+    pub import_not_used_as_values: Option<typescript::strip::ImportNotUsedAsValues>,
 
     #[serde(default)]
     pub hidden: HiddenTransformConfig,
 }
 
+/// Options of the jsx transform.
+///
+/// Unlike [react::Options], unset fields are `None` so that a config can
+/// override some options of the config it extends.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReactConfig {
+    #[serde(default)]
+    pub pragma: Option<String>,
+
+    #[serde(default)]
+    pub pragma_frag: Option<String>,
+
+    #[serde(default)]
+    pub throw_if_namespace: Option<bool>,
+
+    #[serde(default)]
+    pub development: Option<bool>,
+
+    #[serde(default)]
+    pub use_builtins: Option<bool>,
+}
+
+impl From<ReactConfig> for react::Options {
+    fn from(c: ReactConfig) -> Self {
+        let default = react::Options::default();
+
+        react::Options {
+            pragma: c.pragma.unwrap_or(default.pragma),
+            pragma_frag: c.pragma_frag.unwrap_or(default.pragma_frag),
+            throw_if_namespace: c.throw_if_namespace.unwrap_or(default.throw_if_namespace),
+            development: c.development.unwrap_or(default.development),
+            use_builtins: c.use_builtins.unwrap_or(default.use_builtins),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct HiddenTransformConfig {
@@ -627,11 +842,11 @@ pub struct GlobalPassOption {
     #[serde(default)]
     pub vars: HashMap<String, String>,
     #[serde(default = "default_envs")]
-    pub envs: HashSet<String>,
+    pub envs: BTreeSet<String>,
 }
 
-fn default_envs() -> HashSet<String> {
-    let mut v = HashSet::default();
+fn default_envs() -> BTreeSet<String> {
+    let mut v = BTreeSet::default();
     v.insert(String::from("NODE_ENV"));
     v.insert(String::from("SWC_ENV"));
     v
@@ -760,14 +975,53 @@ impl Merge for JscConfig {
         self.transform.merge(&from.transform);
         self.target.merge(&from.target);
         self.external_helpers.merge(&from.external_helpers);
+        self.loose.merge(&from.loose);
+        self.assumptions.merge(&from.assumptions);
+    }
+}
+
+impl Merge for AssumptionsConfig {
+    fn merge(&mut self, from: &Self) {
+        self.no_class_calls.merge(&from.no_class_calls);
+        self.constant_super.merge(&from.constant_super);
+        self.set_class_methods.merge(&from.set_class_methods);
+        self.set_public_class_fields
+            .merge(&from.set_public_class_fields);
+        self.set_computed_properties
+            .merge(&from.set_computed_properties);
+        self.ignore_to_primitive_hint
+            .merge(&from.ignore_to_primitive_hint);
+        self.mutable_template_object
+            .merge(&from.mutable_template_object);
+        self.set_spread_properties
+            .merge(&from.set_spread_properties);
+        self.object_rest_no_symbols
+            .merge(&from.object_rest_no_symbols);
+        self.no_document_all.merge(&from.no_document_all);
+        self.iterable_is_array.merge(&from.iterable_is_array);
+    }
+}
+
+/// Used to add the assumptions of `jsc` to the ones of preset-env.
+impl Merge for Assumptions {
+    fn merge(&mut self, from: &Self) {
+        self.no_class_calls |= from.no_class_calls;
+        self.constant_super |= from.constant_super;
+        self.set_class_methods |= from.set_class_methods;
+        self.set_public_class_fields |= from.set_public_class_fields;
+        self.set_computed_properties |= from.set_computed_properties;
+        self.ignore_to_primitive_hint |= from.ignore_to_primitive_hint;
+        self.mutable_template_object |= from.mutable_template_object;
+        self.set_spread_properties |= from.set_spread_properties;
+        self.object_rest_no_symbols |= from.object_rest_no_symbols;
+        self.no_document_all |= from.no_document_all;
+        self.iterable_is_array |= from.iterable_is_array;
     }
 }
 
 impl Merge for JscTarget {
     fn merge(&mut self, from: &Self) {
-        if *self < *from {
-            *self = *from
-        }
+        *self = *from;
     }
 }
 
@@ -782,7 +1036,13 @@ impl Merge for Option<ModuleConfig> {
 
 impl Merge for bool {
     fn merge(&mut self, from: &Self) {
-        *self |= *from
+        *self = *from;
+    }
+}
+
+impl Merge for String {
+    fn merge(&mut self, from: &Self) {
+        *self = from.clone();
     }
 }
 
@@ -794,25 +1054,46 @@ impl Merge for Syntax {
 
 impl Merge for TransformConfig {
     fn merge(&mut self, from: &Self) {
-        self.optimizer.merge(&from.optimizer);
-        self.const_modules.merge(&from.const_modules);
         self.react.merge(&from.react);
+        self.const_modules.merge(&from.const_modules);
+        self.optimizer.merge(&from.optimizer);
+        self.legacy_decorator.merge(&from.legacy_decorator);
+        self.decorator_metadata.merge(&from.decorator_metadata);
+        self.import_not_used_as_values
+            .merge(&from.import_not_used_as_values);
+        self.hidden.merge(&from.hidden);
     }
 }
 
-impl Merge for OptimizerConfig {
+impl Merge for ReactConfig {
     fn merge(&mut self, from: &Self) {
-        self.globals.merge(&from.globals)
+        self.pragma.merge(&from.pragma);
+        self.pragma_frag.merge(&from.pragma_frag);
+        self.throw_if_namespace.merge(&from.throw_if_namespace);
+        self.development.merge(&from.development);
+        self.use_builtins.merge(&from.use_builtins);
     }
 }
 
-impl Merge for GlobalPassOption {
+impl Merge for typescript::strip::ImportNotUsedAsValues {
     fn merge(&mut self, from: &Self) {
-        *self = from.clone();
+        *self = *from;
+    }
+}
+
+impl Merge for HiddenTransformConfig {
+    fn merge(&mut self, from: &Self) {
+        self.jest |= from.jest;
     }
 }
 
-impl Merge for react::Options {
+impl Merge for OptimizerConfig {
+    fn merge(&mut self, from: &Self) {
+        self.globals.merge(&from.globals)
+    }
+}
+
+impl Merge for GlobalPassOption {
     fn merge(&mut self, from: &Self) {
         *self = from.clone();
     }
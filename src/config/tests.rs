@@ -10,3 +10,123 @@ fn object() {
 fn array() {
     let _: Rc = serde_json::from_str(include_str!("array.json")).expect("failed to parse");
 }
+
+#[test]
+fn glob() {
+    use super::FileMatcher;
+    use std::path::Path;
+
+    let m = |glob: &str, path: &str| {
+        FileMatcher::Glob { glob: glob.into() }
+            .matches(Path::new(path))
+            .unwrap()
+    };
+
+    assert!(m("src/**/*.ts", "/project/src/a.ts"));
+    assert!(m("src/**/*.ts", "/project/src/a/b/c.ts"));
+    assert!(!m("src/**/*.ts", "/project/src/a.tsx"));
+    assert!(!m("src/*.ts", "/project/src/a/b.ts"));
+    assert!(m("*.{ts,tsx}", "/project/src/a.tsx"));
+    assert!(!m("/src/*.ts", "/project/src/a.ts"));
+}
+
+#[test]
+fn merge_overrides_fields_set_by_child() {
+    use super::{Config, Merge};
+    use swc_ecma_parser::JscTarget;
+    use swc_ecma_transforms::typescript::strip::ImportNotUsedAsValues;
+
+    let mut base: Config = serde_json::from_str(
+        r#"{
+            "jsc": {
+                "target": "es2019",
+                "loose": true,
+                "externalHelpers": true,
+                "transform": {
+                    "legacyDecorator": true,
+                    "decoratorMetadata": true,
+                    "importNotUsedAsValues": "preserve",
+                    "react": {
+                        "pragma": "h",
+                        "pragmaFrag": "Fragment",
+                        "development": true
+                    }
+                }
+            },
+            "minify": true
+        }"#,
+    )
+    .unwrap();
+    let child: Config = serde_json::from_str(
+        r#"{
+            "jsc": {
+                "target": "es5",
+                "loose": false,
+                "transform": {
+                    "legacyDecorator": false,
+                    "importNotUsedAsValues": "remove",
+                    "react": {
+                        "pragma": "jsx",
+                        "development": false
+                    }
+                }
+            },
+            "minify": false
+        }"#,
+    )
+    .unwrap();
+
+    base.merge(&child);
+
+    assert_eq!(base.jsc.target, Some(JscTarget::Es5));
+    assert_eq!(base.jsc.loose, Some(false));
+    assert_eq!(base.jsc.external_helpers, Some(true));
+    assert_eq!(base.minify, Some(false));
+
+    let transform = base.jsc.transform.unwrap();
+    assert_eq!(transform.legacy_decorator, Some(false));
+    assert_eq!(transform.decorator_metadata, Some(true));
+    assert_eq!(
+        transform.import_not_used_as_values,
+        Some(ImportNotUsedAsValues::Remove)
+    );
+    assert_eq!(transform.react.pragma.as_deref(), Some("jsx"));
+    assert_eq!(transform.react.pragma_frag.as_deref(), Some("Fragment"));
+    assert_eq!(transform.react.development, Some(false));
+}
+
+#[test]
+fn merge_keeps_fields_unset_by_child() {
+    use super::{Config, Merge};
+    use swc_ecma_parser::JscTarget;
+
+    let mut base: Config =
+        serde_json::from_str(r#"{ "jsc": { "target": "es2018", "loose": true } }"#).unwrap();
+    let child: Config = serde_json::from_str(r#"{ "jsc": { "externalHelpers": true } }"#).unwrap();
+
+    base.merge(&child);
+
+    assert_eq!(base.jsc.target, Some(JscTarget::Es2018));
+    assert_eq!(base.jsc.loose, Some(true));
+    assert_eq!(base.jsc.external_helpers, Some(true));
+}
+
+#[test]
+fn merge_turns_off_assumptions() {
+    use super::{Config, Merge};
+    use swc_ecma_transforms::compat::Assumptions;
+
+    let mut base: Config = serde_json::from_str(
+        r#"{ "jsc": { "assumptions": { "setClassMethods": true, "noDocumentAll": true } } }"#,
+    )
+    .unwrap();
+    let child: Config =
+        serde_json::from_str(r#"{ "jsc": { "assumptions": { "setClassMethods": false } } }"#)
+            .unwrap();
+
+    base.merge(&child);
+
+    let assumptions: Assumptions = base.jsc.assumptions.into();
+    assert!(!assumptions.set_class_methods);
+    assert!(assumptions.no_document_all);
+}
@@ -0,0 +1,370 @@
+//! `tsconfig.json`
+
+use super::{Config, TransformConfig};
+use anyhow::{bail, Context, Error};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+use swc_ecma_parser::{JscTarget, Syntax, TsConfig};
+use swc_ecma_transforms::typescript::strip::ImportNotUsedAsValues;
+
+/// A `tsconfig.json` file, with `extends` applied.
+#[derive(Debug, Default, Clone)]
+pub struct TsConfigFile {
+    pub compiler_options: CompilerOptions,
+}
+
+/// `compilerOptions` used by swc.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompilerOptions {
+    /// Absolute path.
+    #[serde(default)]
+    pub base_url: Option<PathBuf>,
+
+    #[serde(default)]
+    pub paths: Option<HashMap<String, Vec<String>>>,
+
+    /// The directory `paths` is relative to.
+    ///
+    /// This is `baseUrl` if it exists, and the directory of the file
+    /// declaring `paths` otherwise.
+    #[serde(skip)]
+    pub paths_base: Option<PathBuf>,
+
+    #[serde(default)]
+    pub target: Option<String>,
+
+    #[serde(default)]
+    pub jsx: Option<String>,
+
+    #[serde(default)]
+    pub jsx_factory: Option<String>,
+
+    #[serde(default)]
+    pub jsx_fragment_factory: Option<String>,
+
+    #[serde(default)]
+    pub experimental_decorators: Option<bool>,
+
+    #[serde(default)]
+    pub emit_decorator_metadata: Option<bool>,
+
+    #[serde(default)]
+    pub imports_not_used_as_values: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTsConfig {
+    #[serde(default)]
+    extends: Option<Extends>,
+    #[serde(default)]
+    compiler_options: CompilerOptions,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Extends {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl TsConfigFile {
+    /// Loads `path` and the files it extends.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let compiler_options = load(path, &mut vec![])?;
+        Ok(TsConfigFile { compiler_options })
+    }
+
+    /// Overrides `config` with options in the tsconfig file.
+    pub fn apply(&self, config: &mut Config) -> Result<(), Error> {
+        let opts = &self.compiler_options;
+
+        if let Some(target) = &opts.target {
+            config.jsc.target = Some(parse_target(target)?);
+        }
+
+        // Files configured by a tsconfig are typescript. `tsx` of an existing
+        // typescript syntax (e.g. from the file extension) is kept.
+        let mut ts = match config.jsc.syntax {
+            Some(Syntax::Typescript(ts)) => ts,
+            _ => TsConfig {
+                tsx: opts.jsx.is_some(),
+                ..Default::default()
+            },
+        };
+        if let Some(decorators) = opts.experimental_decorators {
+            ts.decorators = decorators;
+        }
+        config.jsc.syntax = Some(Syntax::Typescript(ts));
+
+        let transform = config
+            .jsc
+            .transform
+            .get_or_insert_with(TransformConfig::default);
+        if let Some(v) = opts.experimental_decorators {
+            transform.legacy_decorator = Some(v);
+        }
+        if let Some(v) = opts.emit_decorator_metadata {
+            transform.decorator_metadata = Some(v);
+        }
+        if let Some(v) = &opts.jsx_factory {
+            transform.react.pragma = Some(v.clone());
+        }
+        if let Some(v) = &opts.jsx_fragment_factory {
+            transform.react.pragma_frag = Some(v.clone());
+        }
+        if let Some(v) = &opts.imports_not_used_as_values {
+            transform.import_not_used_as_values = Some(match &*v.to_ascii_lowercase() {
+                "remove" => ImportNotUsedAsValues::Remove,
+                // `error` preserves imports, too.
+                "preserve" | "error" => ImportNotUsedAsValues::Preserve,
+                _ => bail!("invalid value for `importsNotUsedAsValues`: {}", v),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Targets newer than the ones supported by swc are mapped to the newest one.
+fn parse_target(s: &str) -> Result<JscTarget, Error> {
+    let target = s.to_ascii_lowercase();
+
+    Ok(match &*target {
+        "es3" => JscTarget::Es3,
+        "es5" => JscTarget::Es5,
+        "es6" | "es2015" => JscTarget::Es2015,
+        "es2016" => JscTarget::Es2016,
+        "es2017" => JscTarget::Es2017,
+        "es2018" => JscTarget::Es2018,
+        "es2019" => JscTarget::Es2019,
+        "es2020" => JscTarget::Es2020,
+        "es2021" => JscTarget::Es2021,
+        "es2022" | "esnext" => JscTarget::Es2022,
+        _ => match target
+            .strip_prefix("es")
+            .and_then(|year| year.parse::<u32>().ok())
+        {
+            Some(year) if year > 2022 => JscTarget::Es2022,
+            _ => bail!("unsupported target: {}", s),
+        },
+    })
+}
+
+fn load(path: &Path, stack: &mut Vec<PathBuf>) -> Result<CompilerOptions, Error> {
+    if stack.iter().any(|p| p == path) {
+        bail!("circular `extends` in {}", path.display())
+    }
+
+    let content = read_to_string(path)
+        .with_context(|| format!("failed to read tsconfig file: {}", path.display()))?;
+    let raw: RawTsConfig = serde_json::from_str(&strip_jsonc(&content))
+        .with_context(|| format!("failed to deserialize tsconfig file: {}", path.display()))?;
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut opts = raw.compiler_options;
+    opts.base_url = opts.base_url.map(|base_url| dir.join(base_url));
+    if opts.paths.is_some() {
+        opts.paths_base = Some(dir.to_path_buf());
+    }
+
+    let extends = match raw.extends {
+        Some(Extends::One(v)) => vec![v],
+        Some(Extends::Many(v)) => v,
+        None => vec![],
+    };
+
+    stack.push(path.to_path_buf());
+    // Later files take precedence.
+    let mut base = CompilerOptions::default();
+    for extends in extends {
+        let parent = resolve_extends(dir, &extends)?;
+        base = merge(base, load(&parent, stack)?);
+    }
+    stack.pop();
+
+    let mut opts = merge(base, opts);
+    if let Some(base_url) = &opts.base_url {
+        if opts.paths.is_some() {
+            opts.paths_base = Some(base_url.clone());
+        }
+    }
+
+    Ok(opts)
+}
+
+/// Fields of `child` take precedence.
+fn merge(base: CompilerOptions, child: CompilerOptions) -> CompilerOptions {
+    let (paths, paths_base) = match child.paths {
+        Some(paths) => (Some(paths), child.paths_base),
+        None => (base.paths, base.paths_base),
+    };
+
+    CompilerOptions {
+        base_url: child.base_url.or(base.base_url),
+        paths,
+        paths_base,
+        target: child.target.or(base.target),
+        jsx_factory: child.jsx_factory.or(base.jsx_factory),
+        jsx_fragment_factory: child.jsx_fragment_factory.or(base.jsx_fragment_factory),
+        experimental_decorators: child
+            .experimental_decorators
+            .or(base.experimental_decorators),
+        emit_decorator_metadata: child
+            .emit_decorator_metadata
+            .or(base.emit_decorator_metadata),
+        imports_not_used_as_values: child
+            .imports_not_used_as_values
+            .or(base.imports_not_used_as_values),
+    }
+}
+
+/// Resolves `extends`, which may be a path or a package in `node_modules`.
+fn resolve_extends(dir: &Path, extends: &str) -> Result<PathBuf, Error> {
+    let with_json = |path: PathBuf| {
+        if path.is_file() {
+            return Some(path);
+        }
+        let mut s = path.into_os_string();
+        s.push(".json");
+        let path = PathBuf::from(s);
+        if path.is_file() {
+            Some(path)
+        } else {
+            None
+        }
+    };
+
+    if extends.starts_with('.') || Path::new(extends).is_absolute() {
+        if let Some(path) = with_json(dir.join(extends)) {
+            return Ok(path);
+        }
+    } else {
+        for dir in dir.ancestors() {
+            let path = dir.join("node_modules").join(extends);
+            if path.is_dir() {
+                let path = path.join("tsconfig.json");
+                if path.is_file() {
+                    return Ok(path);
+                }
+            } else if let Some(path) = with_json(path) {
+                return Ok(path);
+            }
+        }
+    }
+
+    bail!(
+        "failed to resolve `extends` of tsconfig: `{}` from {}",
+        extends,
+        dir.display()
+    )
+}
+
+/// Removes comments and trailing commas, which are allowed in tsconfig files.
+fn strip_jsonc(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                buf.push(c);
+                while let Some(c) = chars.next() {
+                    buf.push(c);
+                    match c {
+                        '\\' => buf.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while let Some(&c) = chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in &mut chars {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                buf.push(' ');
+            }
+            ']' | '}' => {
+                let trimmed = buf.trim_end().len();
+                if buf[..trimmed].ends_with(',') {
+                    buf.truncate(trimmed - 1);
+                }
+                buf.push(c);
+            }
+            _ => buf.push(c),
+        }
+    }
+
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_target, strip_jsonc, CompilerOptions, TsConfigFile};
+    use crate::config::Config;
+    use swc_ecma_parser::{JscTarget, Syntax, TsConfig};
+
+    #[test]
+    fn jsonc() {
+        let s = strip_jsonc(
+            r#"{
+                // comment
+                "a": "// not a comment", /* comment */
+                "b": ["\"/*", 1,],
+            }"#,
+        );
+        let v: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(v["a"], "// not a comment");
+        assert_eq!(v["b"][0], "\"/*");
+    }
+
+    #[test]
+    fn target() {
+        assert_eq!(parse_target("ES2017").unwrap(), JscTarget::Es2017);
+        assert_eq!(parse_target("ES2021").unwrap(), JscTarget::Es2021);
+        assert_eq!(parse_target("ESNext").unwrap(), JscTarget::Es2022);
+        assert_eq!(parse_target("ES2023").unwrap(), JscTarget::Es2022);
+        assert!(parse_target("ES1").is_err());
+        assert!(parse_target("foo").is_err());
+    }
+
+    #[test]
+    fn syntax_without_default() {
+        let tsconfig = TsConfigFile {
+            compiler_options: CompilerOptions {
+                jsx: Some("react".into()),
+                experimental_decorators: Some(true),
+                ..Default::default()
+            },
+        };
+        let mut config = Config::default();
+        tsconfig.apply(&mut config).unwrap();
+
+        assert!(matches!(
+            config.jsc.syntax,
+            Some(Syntax::Typescript(TsConfig {
+                tsx: true,
+                decorators: true,
+                ..
+            }))
+        ));
+    }
+}
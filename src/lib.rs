@@ -3,23 +3,31 @@
 pub use sourcemap;
 
 pub use crate::builder::PassBuilder;
-use crate::config::{
-    BuiltConfig, Config, ConfigFile, InputSourceMap, JscTarget, Merge, Options, Rc, RootMode,
-    SourceMapsConfig,
+use crate::{
+    cache::{Cache, CacheKey},
+    config::{
+        BuiltConfig, Config, ConfigFile, InputSourceMap, JscTarget, Merge, Options, Rc, RootMode,
+        SourceMapsConfig, TsConfigFile,
+    },
 };
 use anyhow::{bail, Context, Error};
 use dashmap::DashMap;
-use serde::Serialize;
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use serde_json::error::Category;
 use std::{
+    collections::BTreeMap,
+    fmt,
     fs::{read_to_string, File},
+    mem::take,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Mutex, RwLock},
 };
 use swc_common::{
     chain,
     comments::{Comment, Comments},
-    errors::Handler,
+    errors::{self, Diagnostic, DiagnosticBuilder, Handler, HandlerFlags},
     input::StringInput,
     BytePos, FileName, Globals, SourceFile, SourceMap, Spanned, GLOBALS,
 };
@@ -34,6 +42,7 @@ use swc_ecma_transforms::{
 use swc_ecma_visit::FoldWith;
 
 mod builder;
+pub mod cache;
 pub mod config;
 
 pub struct Compiler {
@@ -44,13 +53,57 @@ pub struct Compiler {
     /// CodeMap
     pub cm: Arc<SourceMap>,
     pub handler: Arc<Handler>,
-    comments: SwcComments,
+    comments: FileComments,
 }
 
-#[derive(Debug, Serialize)]
+/// Diagnostics emitted while processing a single input.
+///
+/// Errors returned by [Compiler] contain this if any diagnostic was emitted,
+/// so callers can report them without inspecting [Compiler::handler], which
+/// is shared by all inputs.
+#[derive(Clone)]
+pub struct Diagnostics {
+    pub cm: Arc<SourceMap>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl fmt::Debug for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.diagnostics.iter().map(|d| d.message()))
+            .finish()
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, d) in self.diagnostics.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}: {}", d.level, d.message())?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects diagnostics of a call and forwards them to [Compiler::handler].
+struct CallEmitter {
+    handler: Arc<Handler>,
+    diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+impl errors::Emitter for CallEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        self.diagnostics.lock().unwrap().push((**db).clone());
+        DiagnosticBuilder::new_diagnostic(&self.handler, (**db).clone()).emit();
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TransformOutput {
     pub code: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub map: Option<String>,
 }
 
@@ -60,7 +113,7 @@ impl Compiler {
         &self.globals
     }
 
-    pub fn comments(&self) -> &SwcComments {
+    pub fn comments(&self) -> &FileComments {
         &self.comments
     }
 
@@ -74,6 +127,42 @@ impl Compiler {
         GLOBALS.set(&self.globals, || op())
     }
 
+    /// Runs `op` with a handler which collects diagnostics of this call.
+    ///
+    /// Diagnostics are forwarded to [Compiler::handler] as well. If an error
+    /// is reported, the result is an error containing [Diagnostics].
+    pub fn with_call_handler<T, F>(&self, op: F) -> Result<T, Error>
+    where
+        F: FnOnce(&Handler) -> Result<T, Error>,
+    {
+        let diagnostics = Arc::new(Mutex::new(vec![]));
+        let handler = Handler::with_emitter_and_flags(
+            Box::new(CallEmitter {
+                handler: self.handler.clone(),
+                diagnostics: diagnostics.clone(),
+            }),
+            HandlerFlags {
+                can_emit_warnings: self.handler.flags.can_emit_warnings,
+                treat_err_as_bug: self.handler.flags.treat_err_as_bug,
+                ..Default::default()
+            },
+        );
+
+        let res = op(&handler);
+        let has_errors = handler.has_errors();
+        let diagnostics = Diagnostics {
+            cm: self.cm.clone(),
+            diagnostics: take(&mut *diagnostics.lock().unwrap()),
+        };
+
+        match res {
+            Ok(..) if has_errors => Err(Error::msg(diagnostics)),
+            Ok(v) => Ok(v),
+            Err(err) if diagnostics.diagnostics.is_empty() => Err(err),
+            Err(err) => Err(err.context(diagnostics)),
+        }
+    }
+
     fn get_orig_src_map(
         &self,
         fm: &SourceFile,
@@ -135,7 +224,29 @@ impl Compiler {
         })
     }
 
+    /// Parses a javascript / typescript file and returns its comments.
+    ///
+    /// Unlike [Compiler::parse_js], the comments are not stored in the
+    /// compiler.
+    pub fn parse_js_with_comments(
+        &self,
+        fm: Arc<SourceFile>,
+        target: JscTarget,
+        syntax: Syntax,
+        is_module: bool,
+    ) -> Result<(Program, SwcComments), Error> {
+        let program = self.parse_js(fm.clone(), target, syntax, is_module, true)?;
+        let comments = self.comments.for_file(&fm);
+        self.comments.release(&fm);
+
+        Ok((program, comments))
+    }
+
     /// This method parses a javascript / typescript file
+    ///
+    /// If `parse_comments` is true, the comments are stored until the file is
+    /// emitted by [Compiler::process_js] or released using
+    /// [FileComments::release].
     pub fn parse_js(
         &self,
         fm: Arc<SourceFile>,
@@ -143,28 +254,57 @@ impl Compiler {
         syntax: Syntax,
         is_module: bool,
         parse_comments: bool,
+    ) -> Result<Program, Error> {
+        let res = self.with_call_handler(|handler| {
+            self.parse_js_with_handler(
+                handler,
+                fm.clone(),
+                target,
+                syntax,
+                is_module,
+                parse_comments,
+            )
+        });
+        if res.is_err() {
+            self.comments.release(&fm);
+        }
+
+        res
+    }
+
+    fn parse_js_with_handler(
+        &self,
+        handler: &Handler,
+        fm: Arc<SourceFile>,
+        target: JscTarget,
+        syntax: Syntax,
+        is_module: bool,
+        parse_comments: bool,
     ) -> Result<Program, Error> {
         self.run(|| {
+            // Comments of previous parsing are dropped.
+            self.comments.release(&fm);
+            let comments = if parse_comments {
+                Some(self.comments.for_file(&fm))
+            } else {
+                None
+            };
             let lexer = Lexer::new(
                 syntax,
                 target,
                 StringInput::from(&*fm),
-                if parse_comments {
-                    Some(&self.comments)
-                } else {
-                    None
-                },
+                comments.as_ref().map(|c| c as &dyn Comments),
             );
             let mut parser = Parser::new_from(lexer);
             let program = if is_module {
                 let m = parser.parse_module();
 
                 for e in parser.take_errors() {
-                    e.into_diagnostic(&self.handler).emit();
+                    e.into_diagnostic(handler).emit();
                 }
 
                 m.map_err(|e| {
-                    e.into_diagnostic(&self.handler).emit();
+                    e.into_diagnostic(handler).emit();
                     Error::msg("failed to parse module")
                 })
                 .map(Program::Module)?
@@ -172,11 +312,11 @@ impl Compiler {
                 let s = parser.parse_script();
 
                 for e in parser.take_errors() {
-                    e.into_diagnostic(&self.handler).emit();
+                    e.into_diagnostic(handler).emit();
                 }
 
                 s.map_err(|e| {
-                    e.into_diagnostic(&self.handler).emit();
+                    e.into_diagnostic(handler).emit();
                     Error::msg("failed to parse module")
                 })
                 .map(Program::Script)?
@@ -193,6 +333,22 @@ impl Compiler {
         orig: Option<&sourcemap::SourceMap>,
         minify: bool,
     ) -> Result<TransformOutput, Error>
+    where
+        T: Node,
+    {
+        self.print_with_comments(node, source_map, orig, minify, &self.comments)
+    }
+
+    /// Same as [Compiler::print], but emits `comments` instead of the ones of
+    /// the compiler.
+    fn print_with_comments<T>(
+        &self,
+        node: &T,
+        source_map: SourceMapsConfig,
+        orig: Option<&sourcemap::SourceMap>,
+        minify: bool,
+        comments: &FileComments,
+    ) -> Result<TransformOutput, Error>
     where
         T: Node,
     {
@@ -204,7 +360,7 @@ impl Compiler {
                 {
                     let mut emitter = Emitter {
                         cfg: swc_ecma_codegen::Config { minify },
-                        comments: if minify { None } else { Some(&self.comments) },
+                        comments: if minify { None } else { Some(comments) },
                         cm: self.cm.clone(),
                         wr: Box::new(swc_ecma_codegen::text_writer::JsWriter::new(
                             self.cm.clone(),
@@ -277,14 +433,51 @@ impl Compiler {
     }
 
     pub fn read_config(&self, opts: &Options, name: &FileName) -> Result<Config, Error> {
+        self.read_config_with_cache(opts, name, None)
+    }
+
+    fn read_config_with_cache(
+        &self,
+        opts: &Options,
+        name: &FileName,
+        cache: Option<&SwcrcCache>,
+    ) -> Result<Config, Error> {
+        let mut config = self.read_swcrc(opts, name, cache)?;
+
+        if let Some(path) = &opts.tsconfig {
+            #[cfg(not(target_arch = "wasm32"))]
+            let path = &opts.cwd.join(path);
+
+            match cache {
+                Some(cache) => cache.load_tsconfig(path),
+                None => TsConfigFile::load(path),
+            }
+            .and_then(|tsconfig| tsconfig.apply(&mut config))
+            .with_context(|| format!("failed to apply tsconfig file ('{:?}')", path))?;
+        }
+
+        Ok(config)
+    }
+
+    fn read_swcrc(
+        &self,
+        opts: &Options,
+        name: &FileName,
+        cache: Option<&SwcrcCache>,
+    ) -> Result<Config, Error> {
         self.run(|| -> Result<_, Error> {
             let Options {
                 ref root,
                 root_mode,
                 swcrc,
                 config_file,
+                ref env_name,
                 ..
             } = opts;
+            let resolve = |config: Config, filename: Option<&Path>| {
+                resolve_config(config, filename, env_name, cache, &mut vec![])
+            };
+            let load = |path: &Path| load_swcrc_with_cache(path, cache);
 
             let root = root.clone().unwrap_or_else(|| {
                 if cfg!(target_arch = "wasm32") {
@@ -295,50 +488,48 @@ impl Compiler {
             });
 
             let config_file = match config_file {
-                Some(ConfigFile::Str(ref s)) => Some(load_swcrc(Path::new(&s))?),
+                Some(ConfigFile::Str(ref s)) => Some(load(Path::new(&s))?),
                 _ => None,
             };
 
             match name {
                 FileName::Real(ref path) => {
-                    if *swcrc {
-                        let mut parent = path.parent();
-                        while let Some(dir) = parent {
-                            let swcrc = dir.join(".swcrc");
-
-                            if swcrc.exists() {
-                                let config = load_swcrc(&swcrc)?;
-
-                                let mut config = config
-                                    .into_config(Some(path))
-                                    .context("failed to process config file")?;
+                    let swcrc = match path.parent() {
+                        Some(dir) if *swcrc => match cache {
+                            Some(cache) => cache.find(dir, &root, root_mode),
+                            None => find_swcrc(dir, &root, root_mode),
+                        },
+                        _ => None,
+                    };
 
-                                if let Some(config_file) = config_file {
-                                    config.merge(&config_file.into_config(Some(path))?)
-                                }
+                    if let Some(swcrc) = swcrc {
+                        let config = load(&swcrc)?;
 
-                                return Ok(config);
-                            }
+                        let config = config
+                            .into_config(Some(path))
+                            .context("failed to process config file")?;
+                        let mut config = resolve(config, Some(path))?;
 
-                            if dir == root && *root_mode == RootMode::Root {
-                                break;
-                            }
-                            parent = dir.parent();
+                        if let Some(config_file) = config_file {
+                            config
+                                .merge(&resolve(config_file.into_config(Some(path))?, Some(path))?)
                         }
+
+                        return Ok(config);
                     }
 
                     let config_file = config_file.unwrap_or_else(|| Rc::default());
                     let config = config_file.into_config(Some(path))?;
 
-                    return Ok(config);
+                    return resolve(config, Some(path));
                 }
                 _ => {}
             }
 
-            Ok(match config_file {
-                Some(config_file) => config_file.into_config(None)?,
-                None => Rc::default().into_config(None)?,
-            })
+            match config_file {
+                Some(config_file) => resolve(config_file.into_config(None)?, None),
+                None => Rc::default().into_config(None),
+            }
         })
         .with_context(|| format!("failed to read swcrc file ('{:?}')", name))
     }
@@ -399,7 +590,91 @@ impl Compiler {
         P: swc_ecma_visit::Fold,
     {
         self.run(|| -> Result<_, Error> {
-            let config = self.run(|| self.config_for_file(opts, &fm.name))?;
+            let config = self
+                .read_config(opts, &fm.name)
+                .with_context(|| format!("failed to load config for file '{:?}'", fm.name))?;
+            let orig = self.get_orig_src_map(&fm, &opts.input_source_map)?;
+
+            self.process_js_with_config(fm, opts, config, orig.as_ref(), custom_after_pass)
+        })
+        .context("failed to process js file")
+    }
+
+    pub fn process_js_file(
+        &self,
+        fm: Arc<SourceFile>,
+        opts: &Options,
+    ) -> Result<TransformOutput, Error> {
+        self.run(|| -> Result<_, Error> {
+            let config = self
+                .read_config(opts, &fm.name)
+                .with_context(|| format!("failed to load config for file '{:?}'", fm.name))?;
+
+            self.process_js_file_with_config(fm, opts, config)
+        })
+        .context("failed to process js file")
+    }
+
+    /// If `opts.cache_dir` is set, the output is reused for the same input and
+    /// `config`.
+    fn process_js_file_with_config(
+        &self,
+        fm: Arc<SourceFile>,
+        opts: &Options,
+        config: Config,
+    ) -> Result<TransformOutput, Error> {
+        self.run(|| {
+            let orig = self.get_orig_src_map(&fm, &opts.input_source_map)?;
+
+            let cache = match &opts.cache_dir {
+                Some(dir) => {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let dir = opts.cwd.join(dir);
+
+                    let cache = Cache::new(dir);
+                    let key = transform_cache_key(&cache, &fm, opts, &config, orig.as_ref())?;
+                    if let Some(output) = cache.get(&key) {
+                        return Ok(output);
+                    }
+                    Some((cache, key))
+                }
+                None => None,
+            };
+
+            let name = fm.name.clone();
+            let output = self.process_js_with_config(fm, opts, config, orig.as_ref(), noop())?;
+
+            if let Some((cache, key)) = cache {
+                if let Err(err) = cache.put(&key, &output) {
+                    log::warn!("Failed to store {} in the cache: {:?}", name, err);
+                }
+            }
+
+            Ok(output)
+        })
+    }
+
+    /// Parses, transforms and prints `fm` using the resolved `config`.
+    fn process_js_with_config<P>(
+        &self,
+        fm: Arc<SourceFile>,
+        opts: &Options,
+        config: Config,
+        orig: Option<&sourcemap::SourceMap>,
+        custom_after_pass: P,
+    ) -> Result<TransformOutput, Error>
+    where
+        P: swc_ecma_visit::Fold,
+    {
+        let comments = self.comments.for_call();
+        let res = self.with_call_handler(|handler| {
+            let config = opts.build(
+                &self.cm,
+                handler,
+                opts.is_module,
+                Some(config),
+                Some(&comments),
+            );
             let config = BuiltConfig {
                 pass: chain!(config.pass, custom_after_pass),
                 syntax: config.syntax,
@@ -410,8 +685,8 @@ impl Compiler {
                 input_source_map: config.input_source_map,
                 is_module: config.is_module,
             };
-            let orig = self.get_orig_src_map(&fm, &opts.input_source_map)?;
-            let program = self.parse_js(
+            let program = self.parse_js_with_handler(
+                handler,
                 fm.clone(),
                 config.target,
                 config.syntax,
@@ -419,17 +694,14 @@ impl Compiler {
                 true,
             )?;
 
-            self.process_js_inner(program, orig.as_ref(), config)
-        })
-        .context("failed to process js file")
-    }
+            self.process_js_inner(handler, &fm, program, orig, config, &comments)
+        });
+        // Comments are not emitted if parsing or transforming fails.
+        if res.is_err() {
+            self.comments.release(&fm);
+        }
 
-    pub fn process_js_file(
-        &self,
-        fm: Arc<SourceFile>,
-        opts: &Options,
-    ) -> Result<TransformOutput, Error> {
-        self.process_js_with_custom_pass(fm, opts, noop())
+        res
     }
 
     /// You can use custom pass with this method.
@@ -439,39 +711,132 @@ impl Compiler {
         self.run(|| -> Result<_, Error> {
             let loc = self.cm.lookup_char_pos(program.span().lo());
             let fm = loc.file;
-            let orig = self.get_orig_src_map(&fm, &opts.input_source_map)?;
 
-            let config = self.run(|| self.config_for_file(opts, &fm.name))?;
+            let res = self
+                .read_config(opts, &fm.name)
+                .with_context(|| format!("failed to load config for file '{:?}'", fm.name))
+                .and_then(|config| {
+                    let orig = self.get_orig_src_map(&fm, &opts.input_source_map)?;
+
+                    let comments = self.comments.for_call();
+                    self.with_call_handler(|handler| {
+                        let config = opts.build(
+                            &self.cm,
+                            handler,
+                            opts.is_module,
+                            Some(config),
+                            Some(&comments),
+                        );
+
+                        self.process_js_inner(
+                            handler,
+                            &fm,
+                            program,
+                            orig.as_ref(),
+                            config,
+                            &comments,
+                        )
+                    })
+                });
+            // Comments stored by `parse_js` are not emitted if it fails.
+            if res.is_err() {
+                self.comments.release(&fm);
+            }
 
-            self.process_js_inner(program, orig.as_ref(), config)
+            res
         })
         .context("failed to process js module")
     }
 
+    /// Parses, transforms and prints `files` using a thread pool.
+    ///
+    /// `.swcrc` files are looked up and loaded once per directory, instead of
+    /// once per file, and `opts.tsconfig` is loaded once. Results are in the
+    /// same order as `files`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn transform_many(
+        &self,
+        files: &[PathBuf],
+        opts: &Options,
+    ) -> Vec<Result<TransformOutput, Error>> {
+        self.transform_many_with(files, opts, |_, res| res)
+    }
+
+    /// Same as [Compiler::transform_many], but `op` is applied to the result
+    /// of each file on the thread which processed it.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn transform_many_with<F, Ret>(&self, files: &[PathBuf], opts: &Options, op: F) -> Vec<Ret>
+    where
+        F: Fn(&Path, Result<TransformOutput, Error>) -> Ret + Sync,
+        Ret: Send,
+    {
+        let swcrc_cache = SwcrcCache::default();
+
+        files
+            .par_iter()
+            .map(|path| {
+                let res = self
+                    .run(|| -> Result<_, Error> {
+                        let fm = self.cm.load_file(path).context("failed to load file")?;
+                        let config =
+                            self.read_config_with_cache(opts, &fm.name, Some(&swcrc_cache))?;
+
+                        self.process_js_file_with_config(fm, opts, config)
+                    })
+                    .with_context(|| format!("failed to transform '{}'", path.display()));
+
+                op(path, res)
+            })
+            .collect()
+    }
+
+    /// Comments of `fm` are released after emitting it.
+    ///
+    /// `handler` should be the handler of the current call, as passes report
+    /// unsupported input to it. `comments` should be the storage the passes
+    /// of `config` were built with.
     fn process_js_inner(
         &self,
+        handler: &Handler,
+        fm: &SourceFile,
         program: Program,
         orig: Option<&sourcemap::SourceMap>,
         config: BuiltConfig<impl swc_ecma_visit::Fold>,
+        comments: &FileComments,
     ) -> Result<TransformOutput, Error> {
         self.run(|| {
             if config.minify {
-                let preserve_excl = |_: &BytePos, vc: &mut Vec<Comment>| -> bool {
-                    vc.retain(|c: &Comment| c.text.starts_with("!"));
-                    !vc.is_empty()
-                };
-                self.comments.leading.retain(preserve_excl);
-                self.comments.trailing.retain(preserve_excl);
+                if let Some(comments) = self.comments.get(fm) {
+                    let preserve_excl = |_: &BytePos, vc: &mut Vec<Comment>| -> bool {
+                        vc.retain(|c: &Comment| c.text.starts_with("!"));
+                        !vc.is_empty()
+                    };
+                    comments.leading.retain(preserve_excl);
+                    comments.trailing.retain(preserve_excl);
+                }
             }
             let mut pass = config.pass;
             let program = helpers::HELPERS.set(&Helpers::new(config.external_helpers), || {
-                util::HANDLER.set(&self.handler, || {
+                util::HANDLER.set(handler, || {
                     // Fold module
                     program.fold_with(&mut pass)
                 })
             });
+            // Passes report unsupported input to the handler instead of panicking.
+            if handler.has_errors() {
+                self.comments.release(fm);
+                bail!("failed to transform '{}'", fm.name);
+            }
 
-            self.print(&program, config.source_maps, orig, config.minify)
+            let output = self.print_with_comments(
+                &program,
+                config.source_maps,
+                orig,
+                config.minify,
+                comments,
+            );
+            self.comments.release(fm);
+            output
         })
     }
 }
@@ -495,14 +860,192 @@ fn load_swcrc(path: &Path) -> Result<Rc, Error> {
 
     let content = read_to_string(path).context("failed to read config (.swcrc) file")?;
 
-    match serde_json::from_str(&content) {
-        Ok(v) => return Ok(v),
-        Err(..) => {}
+    let mut rc = match serde_json::from_str(&content) {
+        Ok(v) => v,
+        Err(..) => serde_json::from_str::<Config>(&content)
+            .map(Rc::Single)
+            .map_err(convert_json_err)?,
+    };
+
+    // `extends` is relative to the file declaring it.
+    fn absolutize_extends(c: &mut Config, dir: &Path) {
+        if let Some(extends) = &mut c.extends {
+            *extends = dir.join(&*extends);
+        }
+        for c in c.envs.values_mut().chain(c.overrides.iter_mut()) {
+            absolutize_extends(c, dir)
+        }
+    }
+
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    match &mut rc {
+        Rc::Single(c) => absolutize_extends(c, dir),
+        Rc::Multi(cs) => {
+            for c in cs {
+                absolutize_extends(c, dir)
+            }
+        }
+    }
+
+    Ok(rc)
+}
+
+fn load_swcrc_with_cache(path: &Path, cache: Option<&SwcrcCache>) -> Result<Rc, Error> {
+    match cache {
+        Some(cache) => cache.load(path),
+        None => load_swcrc(path),
+    }
+}
+
+/// Everything affecting the output of [Compiler::process_js_file] is hashed.
+fn transform_cache_key(
+    cache: &Cache,
+    fm: &SourceFile,
+    opts: &Options,
+    config: &Config,
+    orig: Option<&sourcemap::SourceMap>,
+) -> Result<CacheKey, Error> {
+    let name = fm.name.to_string();
+    let config = cache::key_part(config)?;
+    let opts = cache::key_part(&(
+        &opts.config,
+        opts.is_module,
+        &opts.source_maps,
+        &opts.source_file_name,
+        &opts.source_root,
+        opts.skip_helper_injection,
+        opts.disable_hygiene,
+        opts.disable_fixer,
+    ))?;
+    let mut orig_buf = vec![];
+    if let Some(orig) = orig {
+        orig.to_writer(&mut orig_buf)
+            .context("failed to serialize input source map")?;
+    }
+
+    Ok(cache.key(
+        "transform",
+        &[
+            name.as_bytes(),
+            fm.src.as_bytes(),
+            config.as_bytes(),
+            opts.as_bytes(),
+            &orig_buf,
+        ],
+    ))
+}
+
+/// Finds the `.swcrc` file used for files in `dir`.
+fn find_swcrc(dir: &Path, root: &Path, root_mode: &RootMode) -> Option<PathBuf> {
+    let mut parent = Some(dir);
+    while let Some(dir) = parent {
+        let swcrc = dir.join(".swcrc");
+        if swcrc.exists() {
+            return Some(swcrc);
+        }
+
+        if dir == root && *root_mode == RootMode::Root {
+            break;
+        }
+        parent = dir.parent();
     }
 
-    serde_json::from_str::<Config>(&content)
-        .map(Rc::Single)
-        .map_err(convert_json_err)
+    None
+}
+
+/// Shares `.swcrc` files between files of a batch.
+///
+/// Options (`root`, `rootMode`, ...) should be the same for all files.
+///
+/// Only the files are shared. A [BuiltConfig] can't be, because passes are
+/// stateful and `test`, `exclude` and `overrides` are matched per file.
+#[derive(Default)]
+struct SwcrcCache {
+    /// Directory => `.swcrc` file used for files in it.
+    dirs: DashMap<PathBuf, Option<PathBuf>>,
+    /// `.swcrc` files, including the ones loaded by `extends`.
+    files: DashMap<PathBuf, Rc>,
+    tsconfigs: DashMap<PathBuf, TsConfigFile>,
+}
+
+impl SwcrcCache {
+    fn find(&self, dir: &Path, root: &Path, root_mode: &RootMode) -> Option<PathBuf> {
+        if let Some(v) = self.dirs.get(dir) {
+            return v.clone();
+        }
+
+        let swcrc = find_swcrc(dir, root, root_mode);
+        self.dirs.insert(dir.to_path_buf(), swcrc.clone());
+        swcrc
+    }
+
+    fn load(&self, path: &Path) -> Result<Rc, Error> {
+        if let Some(v) = self.files.get(path) {
+            return Ok(v.clone());
+        }
+
+        let rc = load_swcrc(path)?;
+        self.files.insert(path.to_path_buf(), rc.clone());
+        Ok(rc)
+    }
+
+    fn load_tsconfig(&self, path: &Path) -> Result<TsConfigFile, Error> {
+        if let Some(v) = self.tsconfigs.get(path) {
+            return Ok(v.clone());
+        }
+
+        let tsconfig = TsConfigFile::load(path)?;
+        self.tsconfigs.insert(path.to_path_buf(), tsconfig.clone());
+        Ok(tsconfig)
+    }
+}
+
+/// Applies `extends`, `envs` and `overrides` of `config`.
+fn resolve_config(
+    mut config: Config,
+    filename: Option<&Path>,
+    env_name: &str,
+    cache: Option<&SwcrcCache>,
+    stack: &mut Vec<PathBuf>,
+) -> Result<Config, Error> {
+    let envs = take(&mut config.envs);
+    let overrides = take(&mut config.overrides);
+
+    let mut config = match config.extends.take() {
+        Some(path) => {
+            if stack.contains(&path) {
+                bail!("circular `extends` of .swcrc: {}", path.display())
+            }
+
+            let base = load_swcrc_with_cache(&path, cache)
+                .and_then(|rc| rc.into_config(filename))
+                .with_context(|| format!("failed to extend {}", path.display()))?;
+
+            stack.push(path);
+            let mut base = resolve_config(base, filename, env_name, cache, stack)?;
+            stack.pop();
+
+            base.merge(&config);
+            base
+        }
+        None => config,
+    };
+
+    if let Some(env) = envs.get(env_name) {
+        let env = resolve_config(env.clone(), filename, env_name, cache, stack)?;
+        config.merge(&env);
+    }
+
+    if let Some(filename) = filename {
+        for o in overrides {
+            if o.matches(filename)? {
+                let o = resolve_config(o, Some(filename), env_name, cache, stack)?;
+                config.merge(&o);
+            }
+        }
+    }
+
+    Ok(config)
 }
 
 type CommentMap = Arc<DashMap<BytePos, Vec<Comment>>>;
@@ -563,3 +1106,116 @@ impl Comments for SwcComments {
         self.trailing.remove(&pos).map(|v| v.1)
     }
 }
+
+/// Comments of multiple files, stored per [SourceFile].
+///
+/// Each file has its own [SwcComments], so comments of a file can be released
+/// without touching other files. Positions which do not belong to a parsed
+/// file (e.g. `DUMMY_SP`) are stored separately, and are kept until the
+/// [FileComments] is dropped.
+#[derive(Clone, Default)]
+pub struct FileComments {
+    /// Keyed by [SourceFile::start_pos]. The value contains
+    /// [SourceFile::end_pos].
+    files: Arc<RwLock<BTreeMap<BytePos, (BytePos, SwcComments)>>>,
+    others: SwcComments,
+}
+
+impl FileComments {
+    /// Returns comments of `fm`, creating an empty storage if it does not
+    /// exist.
+    pub fn for_file(&self, fm: &SourceFile) -> SwcComments {
+        if let Some(c) = self.get(fm) {
+            return c;
+        }
+
+        self.files
+            .write()
+            .unwrap()
+            .entry(fm.start_pos)
+            .or_insert_with(|| (fm.end_pos, Default::default()))
+            .1
+            .clone()
+    }
+
+    pub fn get(&self, fm: &SourceFile) -> Option<SwcComments> {
+        self.files
+            .read()
+            .unwrap()
+            .get(&fm.start_pos)
+            .map(|v| v.1.clone())
+    }
+
+    /// Drops comments of `fm`.
+    pub fn release(&self, fm: &SourceFile) {
+        self.files.write().unwrap().remove(&fm.start_pos);
+    }
+
+    /// Returns a storage sharing the comments of files, but not the ones at
+    /// other positions.
+    ///
+    /// Comments at other positions (e.g. `/*#__PURE__*/` added by passes at
+    /// `DUMMY_SP`) are emitted at the same position for every file, so a call
+    /// uses its own storage for them and drops it with the output.
+    fn for_call(&self) -> FileComments {
+        FileComments {
+            files: self.files.clone(),
+            others: Default::default(),
+        }
+    }
+
+    /// Returns the storage containing `pos`.
+    fn route(&self, pos: BytePos) -> SwcComments {
+        let files = self.files.read().unwrap();
+        match files.range(..=pos).next_back() {
+            Some((_, (end, c))) if pos <= *end => c.clone(),
+            _ => self.others.clone(),
+        }
+    }
+}
+
+impl Comments for FileComments {
+    fn add_leading(&self, pos: BytePos, cmt: Comment) {
+        self.route(pos).add_leading(pos, cmt)
+    }
+
+    fn add_leading_comments(&self, pos: BytePos, comments: Vec<Comment>) {
+        self.route(pos).add_leading_comments(pos, comments)
+    }
+
+    fn has_leading(&self, pos: BytePos) -> bool {
+        self.route(pos).has_leading(pos)
+    }
+
+    fn move_leading(&self, from: BytePos, to: BytePos) {
+        if let Some(cmt) = self.take_leading(from) {
+            self.add_leading_comments(to, cmt)
+        }
+    }
+
+    fn take_leading(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        self.route(pos).take_leading(pos)
+    }
+
+    fn add_trailing(&self, pos: BytePos, cmt: Comment) {
+        self.route(pos).add_trailing(pos, cmt)
+    }
+
+    fn add_trailing_comments(&self, pos: BytePos, comments: Vec<Comment>) {
+        self.route(pos).add_trailing_comments(pos, comments)
+    }
+
+    fn has_trailing(&self, pos: BytePos) -> bool {
+        self.route(pos).has_trailing(pos)
+    }
+
+    fn move_trailing(&self, from: BytePos, to: BytePos) {
+        if let Some(cmt) = self.take_trailing(from) {
+            self.add_trailing_comments(to, cmt)
+        }
+    }
+
+    fn take_trailing(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        self.route(pos).take_trailing(pos)
+    }
+}
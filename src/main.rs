@@ -0,0 +1,346 @@
+//! Command line interface of swc.
+//!
+//! ```sh
+//! swc src -d lib
+//! swc src/index.ts -o lib/index.js --source-maps true
+//! ```
+//!
+//! `.swcrc` files are looked up for each input file, just like the node
+//! binding.
+
+use anyhow::{bail, Context, Error};
+use clap::{App, Arg, ArgMatches};
+use std::{
+    fs::{copy, create_dir_all, write},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+};
+use swc::{
+    config::{FileMatcher, Options, SourceMapsConfig},
+    Compiler, TransformOutput,
+};
+use swc_common::{
+    errors::{ColorConfig, Handler},
+    FilePathMapping, SourceMap,
+};
+use walkdir::WalkDir;
+
+const DEFAULT_EXTENSIONS: &str = ".js,.jsx,.es6,.es,.mjs,.ts,.tsx";
+
+fn main() {
+    let matches = App::new("swc")
+        .about("Compiles javascript and typescript files using swc")
+        .arg(
+            Arg::with_name("files")
+                .required(true)
+                .multiple(true)
+                .help("Files or directories to compile"),
+        )
+        .arg(
+            Arg::with_name("out-dir")
+                .short("d")
+                .long("out-dir")
+                .takes_value(true)
+                .conflicts_with("out-file")
+                .help("Compiles into this directory, preserving the directory structure"),
+        )
+        .arg(
+            Arg::with_name("out-file")
+                .short("o")
+                .long("out-file")
+                .takes_value(true)
+                .help("Compiles a single file into this file"),
+        )
+        .arg(
+            Arg::with_name("source-maps")
+                .short("s")
+                .long("source-maps")
+                .takes_value(true)
+                .possible_values(&["true", "false", "inline"]),
+        )
+        .arg(
+            Arg::with_name("copy-files")
+                .short("D")
+                .long("copy-files")
+                .requires("out-dir")
+                .help("Copies files which are not compiled to the output directory"),
+        )
+        .arg(
+            Arg::with_name("ignore")
+                .long("ignore")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .help("Glob of files to ignore. Ignored files are not copied"),
+        )
+        .arg(
+            Arg::with_name("only")
+                .long("only")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .help("Glob of files to compile"),
+        )
+        .arg(
+            Arg::with_name("extensions")
+                .long("extensions")
+                .takes_value(true)
+                .help("Comma-separated extensions of files to compile")
+                .default_value(DEFAULT_EXTENSIONS),
+        )
+        .get_matches();
+
+    match run(&matches) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(err) => {
+            eprintln!("error: {:?}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// A file to compile or copy.
+struct Input {
+    path: PathBuf,
+    /// Path relative to the output directory.
+    out: PathBuf,
+}
+
+struct Filter {
+    extensions: Vec<String>,
+    ignore: Vec<FileMatcher>,
+    only: Vec<FileMatcher>,
+}
+
+impl Filter {
+    fn new(matches: &ArgMatches) -> Self {
+        let globs = |name| {
+            matches
+                .values_of(name)
+                .into_iter()
+                .flatten()
+                .map(|glob| FileMatcher::Glob { glob: glob.into() })
+                .collect::<Vec<_>>()
+        };
+
+        Filter {
+            extensions: matches
+                .value_of("extensions")
+                .unwrap_or(DEFAULT_EXTENSIONS)
+                .split(',')
+                .map(|ext| ext.trim().trim_start_matches('.').to_string())
+                .filter(|ext| !ext.is_empty())
+                .collect(),
+            ignore: globs("ignore"),
+            only: globs("only"),
+        }
+    }
+
+    fn is_ignored(&self, path: &Path) -> Result<bool, Error> {
+        any_matches(&self.ignore, path)
+    }
+
+    fn should_compile(&self, path: &Path) -> Result<bool, Error> {
+        let ext = path.extension().map(|ext| ext.to_string_lossy());
+        match ext {
+            Some(ext) if self.extensions.iter().any(|e| *e == ext) => {}
+            _ => return Ok(false),
+        }
+        if path.to_string_lossy().ends_with(".d.ts") {
+            return Ok(false);
+        }
+
+        Ok(self.only.is_empty() || any_matches(&self.only, path)?)
+    }
+}
+
+fn any_matches(globs: &[FileMatcher], path: &Path) -> Result<bool, Error> {
+    for glob in globs {
+        if glob.matches(path)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// `.ts` and `.tsx` files are compiled to `.js` files.
+fn output_path(path: &Path) -> PathBuf {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("mjs") => path.to_path_buf(),
+        _ => path.with_extension("js"),
+    }
+}
+
+/// Returns files to compile and files to copy.
+fn collect_inputs(files: &[PathBuf], filter: &Filter) -> Result<(Vec<Input>, Vec<Input>), Error> {
+    let mut compile = vec![];
+    let mut copy = vec![];
+
+    for file in files {
+        if file.is_dir() {
+            for entry in WalkDir::new(file).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+                let entry = entry
+                    .with_context(|| format!("failed to read directory: {}", file.display()))?;
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let path = entry.path();
+                if filter.is_ignored(path)? {
+                    continue;
+                }
+
+                let rel = path.strip_prefix(file).unwrap().to_path_buf();
+                if filter.should_compile(path)? {
+                    compile.push(Input {
+                        path: path.to_path_buf(),
+                        out: output_path(&rel),
+                    });
+                } else {
+                    copy.push(Input {
+                        path: path.to_path_buf(),
+                        out: rel,
+                    });
+                }
+            }
+        } else if file.is_file() {
+            // Files are compiled even if they don't match `--only` or `--extensions`.
+            if filter.is_ignored(file)? {
+                continue;
+            }
+            let name = file.file_name().unwrap();
+            compile.push(Input {
+                path: file.clone(),
+                out: output_path(Path::new(name)),
+            });
+        } else {
+            bail!("{} does not exist", file.display());
+        }
+    }
+
+    Ok((compile, copy))
+}
+
+/// Returns `false` if a file failed to compile.
+fn run(matches: &ArgMatches) -> Result<bool, Error> {
+    let files = matches
+        .values_of("files")
+        .unwrap()
+        .map(PathBuf::from)
+        .collect::<Vec<_>>();
+    let filter = Filter::new(matches);
+    let (inputs, copies) = collect_inputs(&files, &filter)?;
+
+    let out_dir = matches.value_of("out-dir").map(PathBuf::from);
+    let out_file = matches.value_of("out-file").map(PathBuf::from);
+    if out_file.is_some() && inputs.len() != 1 {
+        bail!("`--out-file` requires exactly one input file. Use `--out-dir` instead");
+    }
+
+    let source_maps = match matches.value_of("source-maps") {
+        Some("true") if out_dir.is_none() && out_file.is_none() => {
+            // There's no file to store the source map.
+            Some(SourceMapsConfig::Str("inline".into()))
+        }
+        Some("true") => Some(SourceMapsConfig::Bool(true)),
+        Some("inline") => Some(SourceMapsConfig::Str("inline".into())),
+        Some(_) => Some(SourceMapsConfig::Bool(false)),
+        None => None,
+    };
+
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let handler = Arc::new(Handler::with_tty_emitter(
+        ColorConfig::Auto,
+        true,
+        false,
+        Some(cm.clone()),
+    ));
+    let compiler = Compiler::new(cm, handler);
+
+    let mut options: Options =
+        serde_json::from_value(serde_json::Value::Object(Default::default()))
+            .context("failed to create default options")?;
+    options.source_maps = source_maps;
+
+    let paths = inputs.iter().map(|i| i.path.clone()).collect::<Vec<_>>();
+    let outputs = compiler.transform_many(&paths, &options);
+
+    let mut ok = true;
+    let mut count = 0;
+    for (input, output) in inputs.iter().zip(outputs) {
+        let output = match output {
+            Ok(v) => v,
+            Err(err) => {
+                eprintln!("error: {:?}", err);
+                ok = false;
+                continue;
+            }
+        };
+
+        let dest = match (&out_dir, &out_file) {
+            (Some(dir), _) => dir.join(&input.out),
+            (_, Some(file)) => file.clone(),
+            _ => {
+                io::stdout()
+                    .write_all(output.code.as_bytes())
+                    .context("failed to write to stdout")?;
+                println!();
+                continue;
+            }
+        };
+        write_output(&dest, output)?;
+        count += 1;
+    }
+
+    if let Some(dir) = &out_dir {
+        if matches.is_present("copy-files") {
+            for input in &copies {
+                let dest = dir.join(&input.out);
+                create_parent(&dest)?;
+                copy(&input.path, &dest).with_context(|| {
+                    format!(
+                        "failed to copy {} to {}",
+                        input.path.display(),
+                        dest.display()
+                    )
+                })?;
+            }
+        }
+    }
+
+    if out_dir.is_some() || out_file.is_some() {
+        eprintln!("Successfully compiled {} files with swc.", count);
+    }
+
+    Ok(ok)
+}
+
+fn create_parent(path: &Path) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)
+            .with_context(|| format!("failed to create directory: {}", parent.display()))?;
+    }
+    Ok(())
+}
+
+/// Source maps are written to `.map` files next to `path`.
+fn write_output(path: &Path, output: TransformOutput) -> Result<(), Error> {
+    create_parent(path)?;
+
+    let mut code = output.code;
+    if let Some(map) = output.map {
+        let mut map_path = path.as_os_str().to_owned();
+        map_path.push(".map");
+        let map_path = PathBuf::from(map_path);
+
+        write(&map_path, map).with_context(|| format!("failed to write {}", map_path.display()))?;
+        code.push_str(&format!(
+            "\n//# sourceMappingURL={}",
+            map_path.file_name().unwrap().to_string_lossy()
+        ));
+    }
+
+    write(path, code).with_context(|| format!("failed to write {}", path.display()))
+}
@@ -0,0 +1,132 @@
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::Path,
+    process::Command,
+};
+
+fn swc() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_swc"))
+}
+
+fn setup(root: &Path, files: &[(&str, &str)]) {
+    for (path, content) in files {
+        let path = root.join(path);
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(path, content).unwrap();
+    }
+}
+
+#[test]
+fn out_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    setup(
+        root,
+        &[
+            ("src/a.ts", "const a: number = 1;\nexport default a;"),
+            ("src/nested/b.tsx", "export const b = <div />;"),
+            ("src/nested/c.test.js", "test();"),
+            ("src/data.json", "{}"),
+            (
+                "src/.swcrc",
+                r#"{ "jsc": { "parser": { "syntax": "typescript", "tsx": true }, "target": "es5" } }"#,
+            ),
+        ],
+    );
+
+    let status = swc()
+        .current_dir(root)
+        .args(&["src", "-d", "lib", "--copy-files", "--ignore", "*.test.js"])
+        .args(&["--source-maps", "true"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let a = read_to_string(root.join("lib/a.js")).unwrap();
+    assert!(a.contains("var a = 1"), "{}", a);
+    assert!(a.ends_with("//# sourceMappingURL=a.js.map"), "{}", a);
+    assert!(root.join("lib/a.js.map").exists());
+
+    let b = read_to_string(root.join("lib/nested/b.js")).unwrap();
+    assert!(b.contains("React.createElement"), "{}", b);
+
+    assert!(root.join("lib/data.json").exists());
+    assert!(!root.join("lib/nested/c.test.js").exists());
+}
+
+#[test]
+fn only_and_extensions() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    setup(
+        root,
+        &[
+            ("src/a.js", "export const a = () => 1;"),
+            ("src/b.mjs", "export const b = () => 2;"),
+            ("src/c.ts", "export const c = 3;"),
+        ],
+    );
+
+    let status = swc()
+        .current_dir(root)
+        .args(&["src", "-d", "lib", "--extensions", ".js,.mjs"])
+        .args(&["--only", "b.mjs"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    assert!(root.join("lib/b.mjs").exists());
+    assert!(!root.join("lib/a.js").exists());
+    assert!(!root.join("lib/c.js").exists());
+}
+
+#[test]
+fn out_file_and_stdout() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    setup(root, &[("a.ts", "export const a: number = 1;")]);
+
+    let status = swc()
+        .current_dir(root)
+        .args(&["a.ts", "-o", "out/index.js"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let code = read_to_string(root.join("out/index.js")).unwrap();
+    assert!(code.contains("export var a = 1"), "{}", code);
+
+    let output = swc()
+        .current_dir(root)
+        .args(&["a.ts", "--source-maps", "inline"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("//# sourceMappingURL=data:application/json;base64,"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    setup(
+        root,
+        &[
+            ("src/ok.js", "export const a = 1;"),
+            ("src/bad.js", "let = ;"),
+        ],
+    );
+
+    let status = swc()
+        .current_dir(root)
+        .args(&["src", "-d", "lib"])
+        .status()
+        .unwrap();
+    assert!(!status.success());
+    // Other files are still compiled.
+    assert!(root.join("lib/ok.js").exists());
+}
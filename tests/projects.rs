@@ -516,7 +516,7 @@ fn issue_879() {
                         ..Default::default()
                     })),
                     transform: Some(TransformConfig {
-                        legacy_decorator: true,
+                        legacy_decorator: Some(true),
                         ..Default::default()
                     }),
                     ..Default::default()
@@ -573,3 +573,94 @@ fn codegen_1() {
 
     assert_eq!(f.to_string(), "\"\\\"\";\n");
 }
+
+#[test]
+fn tsconfig() {
+    let f = file_with_opt(
+        "tests/projects/tsconfig/input.tsx",
+        Options {
+            swcrc: false,
+            tsconfig: Some("tests/projects/tsconfig/tsconfig.json".into()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    println!("{}", f);
+
+    assert!(f.contains("class Bar"), "target should be es2017");
+    assert!(f.contains("h(Fragment, null, h(\"div\", null))"));
+    assert!(f.contains("\"./foo\""), "import should be preserved");
+}
+
+#[test]
+fn swcrc_extends() {
+    let f = file("tests/projects/swcrc-extends/src/input.js").unwrap();
+    println!("{}", f);
+
+    assert!(
+        f.contains("exports.add"),
+        "module config should be extended"
+    );
+    assert!(!f.contains("=>"));
+
+    let f = file("tests/projects/swcrc-extends/src/modern/input.js").unwrap();
+    println!("{}", f);
+
+    assert!(f.contains("exports.add"));
+    assert!(f.contains("=>"), "overrides should be applied");
+}
+
+#[test]
+fn swcrc_envs() {
+    let f = file("tests/projects/swcrc-extends/src/input.js").unwrap();
+    assert!(f.contains('\n'));
+
+    let f = file_with_opt(
+        "tests/projects/swcrc-extends/src/input.js",
+        Options {
+            swcrc: true,
+            env_name: "production".into(),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    println!("{}", f);
+
+    assert!(!f.trim().contains('\n'), "output should be minified");
+}
+
+#[test]
+fn transform_many() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm, Arc::new(handler));
+
+            let files = vec![
+                "tests/projects/swcrc-extends/src/input.js".into(),
+                "tests/projects/swcrc-extends/src/modern/input.js".into(),
+                "tests/projects/swcrc-extends/src/not-found.js".into(),
+            ];
+            let res = c.transform_many(
+                &files,
+                &Options {
+                    swcrc: true,
+                    is_module: true,
+                    ..Default::default()
+                },
+            );
+            assert_eq!(res.len(), 3);
+
+            let first = res[0].as_ref().unwrap();
+            assert!(first.code.contains("exports.add"));
+            assert!(!first.code.contains("=>"));
+
+            let second = res[1].as_ref().unwrap();
+            assert!(second.code.contains("exports.add"));
+            assert!(second.code.contains("=>"), "overrides should be applied");
+
+            assert!(res[2].is_err());
+
+            Ok(())
+        })
+        .unwrap();
+}
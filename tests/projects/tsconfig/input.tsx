@@ -0,0 +1,8 @@
+import { h, Fragment } from "preact";
+import { Foo } from "./foo";
+
+class Bar {
+    foo?: Foo;
+}
+
+export const el = <><div /></>;
@@ -1,10 +1,13 @@
 use std::sync::Arc;
 use swc::{
     config::{Config, JscConfig, Options},
-    Compiler,
+    Compiler, Diagnostics,
 };
-use swc_common::FileName;
-use swc_ecma_parser::{EsConfig, Syntax};
+use swc_common::{
+    comments::{Comment, CommentKind, Comments},
+    BytePos, FileName, DUMMY_SP,
+};
+use swc_ecma_parser::{EsConfig, JscTarget, Syntax};
 use testing::Tester;
 
 fn compile(src: &str, options: Options) -> String {
@@ -94,3 +97,291 @@ fn issue_834_3() {
         },
     );
 }
+
+#[test]
+fn logical_assignments_by_target() {
+    let compile_with_target = |target| {
+        compile(
+            "let a = {}; a.b ??= 1; a.c ||= 2;",
+            Options {
+                config: Some(Config {
+                    jsc: JscConfig {
+                        target: Some(target),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+                swcrc: false,
+                ..Default::default()
+            },
+        )
+    };
+
+    let es2021 = compile_with_target(JscTarget::Es2021);
+    assert!(es2021.contains("??="), "{}", es2021);
+    assert!(es2021.contains("||="), "{}", es2021);
+
+    let es2019 = compile_with_target(JscTarget::Es2019);
+    assert!(!es2019.contains("??"), "{}", es2019);
+    assert!(!es2019.contains("||="), "{}", es2019);
+}
+
+#[test]
+fn static_blocks_by_target() {
+    let compile_with_target = |target| {
+        compile(
+            "class Foo { static #a = 1; static { this.b = #a in this; } }",
+            Options {
+                config: Some(Config {
+                    jsc: JscConfig {
+                        target: Some(target),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+                swcrc: false,
+                ..Default::default()
+            },
+        )
+    };
+
+    let es2022 = compile_with_target(JscTarget::Es2022);
+    assert!(es2022.contains("static {"), "{}", es2022);
+    assert!(es2022.contains("#a in this"), "{}", es2022);
+
+    let es2021 = compile_with_target(JscTarget::Es2021);
+    assert!(!es2021.contains("static {"), "{}", es2021);
+    assert!(!es2021.contains("#a"), "{}", es2021);
+}
+
+#[test]
+fn assumptions() {
+    let compile_with_jsc = |jsc: &str| {
+        compile(
+            "class Foo { foo() { return `a${this.b}`; } }",
+            Options {
+                config: Some(Config {
+                    jsc: serde_json::from_str(jsc).unwrap(),
+                    ..Default::default()
+                }),
+                swcrc: false,
+                ..Default::default()
+            },
+        )
+    };
+
+    let spec = compile_with_jsc("{}");
+    assert!(spec.contains("_createClass"), "{}", spec);
+    assert!(spec.contains(".concat("), "{}", spec);
+
+    let assumed = compile_with_jsc(
+        r#"{ "assumptions": { "setClassMethods": true, "ignoreToPrimitiveHint": true } }"#,
+    );
+    assert!(!assumed.contains("_createClass"), "{}", assumed);
+    assert!(assumed.contains("Foo.prototype.foo ="), "{}", assumed);
+    assert!(assumed.contains("_classCallCheck"), "{}", assumed);
+    assert!(!assumed.contains(".concat("), "{}", assumed);
+
+    let loose = compile_with_jsc(r#"{ "loose": true }"#);
+    assert!(loose.contains("Foo.prototype.foo ="), "{}", loose);
+    assert!(!loose.contains("_classCallCheck"), "{}", loose);
+}
+
+#[test]
+fn transform_error() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), Arc::new(handler));
+
+            let fm = cm.new_source_file(
+                FileName::Real("input.js".into()),
+                "class Foo { constructor() {} constructor() {} }".into(),
+            );
+            let opts = Options {
+                swcrc: false,
+                is_module: true,
+                ..Default::default()
+            };
+            let res = c.process_js_file(fm, &opts);
+
+            let err = res.unwrap_err();
+            let diagnostics = err
+                .downcast_ref::<Diagnostics>()
+                .expect("errors should contain diagnostics of the file");
+            assert_eq!(diagnostics.diagnostics.len(), 1);
+            assert!(c.handler.has_errors());
+
+            // Errors of a file should not affect other files.
+            let fm = cm.new_source_file(FileName::Real("valid.js".into()), "class Foo {}".into());
+            assert!(c.process_js_file(fm, &opts).is_ok());
+
+            Ok(())
+        })
+        .unwrap()
+}
+
+#[test]
+fn comments_per_file() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), Arc::new(handler));
+
+            let a = cm.new_source_file(FileName::Real("a.js".into()), "// a\nfoo();".into());
+            let b = cm.new_source_file(FileName::Real("b.js".into()), "// b\nbar();".into());
+
+            let (_, comments) = c
+                .parse_js_with_comments(a.clone(), Default::default(), Default::default(), true)
+                .unwrap();
+            assert!(comments.has_leading(a.start_pos + BytePos(5)));
+
+            // Minifying `b` should not affect comments of `a`.
+            let output = c
+                .process_js_file(
+                    b.clone(),
+                    &Options {
+                        swcrc: false,
+                        is_module: true,
+                        config: Some(Config {
+                            minify: Some(true),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            assert!(!output.code.contains("// b"));
+            assert!(
+                c.comments().get(&b).is_none(),
+                "comments should be released"
+            );
+            assert!(comments.has_leading(a.start_pos + BytePos(5)));
+
+            let output = c
+                .process_js(
+                    c.parse_js(
+                        a.clone(),
+                        Default::default(),
+                        Default::default(),
+                        true,
+                        true,
+                    )
+                    .unwrap(),
+                    &Options {
+                        swcrc: false,
+                        is_module: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            assert_eq!(output.code.matches("// a").count(), 1);
+            assert!(c.comments().get(&a).is_none());
+
+            Ok(())
+        })
+        .unwrap()
+}
+
+#[test]
+fn comments_released_on_error() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), Arc::new(handler));
+
+            let fm = cm.new_source_file(FileName::Real("a.js".into()), "// a\nfoo(;".into());
+            let res = c.process_js_file(
+                fm.clone(),
+                &Options {
+                    swcrc: false,
+                    is_module: true,
+                    ..Default::default()
+                },
+            );
+            assert!(res.is_err());
+            assert!(c.comments().get(&fm).is_none());
+
+            let res = c.parse_js(
+                fm.clone(),
+                Default::default(),
+                Default::default(),
+                true,
+                true,
+            );
+            assert!(res.is_err());
+            assert!(c.comments().get(&fm).is_none());
+
+            Ok(())
+        })
+        .unwrap()
+}
+
+#[test]
+fn comments_outside_files_per_call() {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), Arc::new(handler));
+
+            // Stored outside of any file, like comments added at `DUMMY_SP`.
+            c.comments().add_leading(
+                BytePos(0),
+                Comment {
+                    kind: CommentKind::Block,
+                    span: DUMMY_SP,
+                    text: "#__PURE__".into(),
+                },
+            );
+
+            let fm = cm.new_source_file(FileName::Real("a.js".into()), "foo();".into());
+            let output = c
+                .process_js_file(
+                    fm,
+                    &Options {
+                        swcrc: false,
+                        is_module: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            assert!(!output.code.contains("__PURE__"), "{}", output.code);
+
+            Ok(())
+        })
+        .unwrap()
+}
+
+#[test]
+fn cache_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = |minify| Options {
+        swcrc: false,
+        cache_dir: Some(dir.path().to_path_buf()),
+        config: Some(Config {
+            minify: Some(minify),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let items = || {
+        walkdir::WalkDir::new(dir.path())
+            .into_iter()
+            .map(|e| e.unwrap())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .collect::<Vec<_>>()
+    };
+
+    let first = compile("class Foo {}", options(false));
+    assert_eq!(items().len(), 1);
+    assert_eq!(compile("class Foo {}", options(false)), first);
+    assert_eq!(items().len(), 1);
+
+    compile("class Bar {}", options(false));
+    assert_eq!(items().len(), 2);
+    compile("class Foo {}", options(true));
+    assert_eq!(items().len(), 3);
+
+    // Cached outputs are used as-is.
+    for item in items() {
+        std::fs::write(item, r#"{ "code": "cached" }"#).unwrap();
+    }
+    assert_eq!(compile("class Foo {}", options(false)), "cached");
+}
@@ -9,7 +9,7 @@ use swc::{
     Compiler,
 };
 use swc_common::{
-    errors::{DiagnosticBuilder, Emitter, Handler, SourceMapperDyn},
+    errors::{json::JsonDiagnostic, Handler, JsonEmitter, SourceMapperDyn},
     FileName, FilePathMapping, SourceMap,
 };
 use swc_ecmascript::ast::Program;
@@ -28,7 +28,7 @@ pub fn parse_sync(s: &str, opts: JsValue) -> Result<JsValue, JsValue> {
     let fm = c.cm.new_source_file(FileName::Anon, s.into());
     let program = c
         .parse_js(fm, opts.target, opts.syntax, opts.is_module, opts.comments)
-        .map_err(|err| format!("failed to parse: {}\n{}", err, errors))?;
+        .map_err(|err| errors.into_js_value(format!("failed to parse: {}", err)))?;
 
     Ok(JsValue::from_serde(&program).map_err(|err| format!("failed to return value: {}", err))?)
 }
@@ -56,9 +56,9 @@ pub fn print_sync(s: JsValue, opts: JsValue) -> Result<JsValue, JsValue> {
             None,
             opts.config.unwrap_or_default().minify.unwrap_or_default(),
         )
-        .map_err(|err| format!("failed to print: {}\n{}", err, errors))?;
+        .map_err(|err| errors.into_js_value(format!("failed to print: {}", err)))?;
 
-    Ok(JsValue::from_serde(&s).map_err(|err| format!("failed to print: {}\n{}", err, errors))?)
+    Ok(JsValue::from_serde(&s).map_err(|err| format!("failed to print: {}", err))?)
 }
 
 #[wasm_bindgen(js_name = "transformSync")]
@@ -74,7 +74,7 @@ pub fn transform_sync(s: &str, opts: JsValue) -> Result<JsValue, JsValue> {
     let fm = c.cm.new_source_file(FileName::Anon, s.into());
     let out = c
         .process_js_file(fm, &opts)
-        .map_err(|err| format!("failed to process code: {}\n{}", err, errors))?;
+        .map_err(|err| errors.into_js_value(format!("failed to process code: {}", err)))?;
 
     Ok(JsValue::from_serde(&out).unwrap())
 }
@@ -97,27 +97,19 @@ fn codemap() -> Arc<SourceMap> {
     CM.clone()
 }
 
-/// Creates a new handler which emits to returned buffer.
-fn new_handler(_cm: Arc<SourceMapperDyn>) -> (Arc<Handler>, BufferedError) {
+/// Creates a new handler which emits json diagnostics to returned buffer.
+fn new_handler(cm: Arc<SourceMapperDyn>) -> (Arc<Handler>, BufferedError) {
     let e = BufferedError::default();
 
-    let handler = Handler::with_emitter(true, false, Box::new(MyEmiter::default()));
+    let handler = Handler::with_emitter(
+        true,
+        false,
+        Box::new(JsonEmitter::new(Box::new(e.clone()), Some(cm))),
+    );
 
     (Arc::new(handler), e)
 }
 
-#[derive(Clone, Default)]
-struct MyEmiter(BufferedError);
-
-impl Emitter for MyEmiter {
-    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
-        let z = &(self.0).0;
-        for msg in &db.message {
-            z.write().unwrap().push_str(&msg.0);
-        }
-    }
-}
-
 #[derive(Clone, Default)]
 pub(crate) struct BufferedError(Arc<RwLock<String>>);
 
@@ -135,6 +127,36 @@ impl Write for BufferedError {
     }
 }
 
+impl BufferedError {
+    /// Returns an array of all emitted diagnostics, followed by `msg`.
+    fn into_js_value(self, msg: String) -> JsValue {
+        let diagnostics = self
+            .0
+            .read()
+            .unwrap()
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<JsonDiagnostic>, _>>();
+        let mut diagnostics = match diagnostics {
+            Ok(v) => v,
+            Err(err) => return format!("{}\nfailed to read diagnostics: {}", msg, err).into(),
+        };
+
+        diagnostics.push(JsonDiagnostic {
+            message: msg,
+            code: None,
+            level: "error".into(),
+            spans: vec![],
+            children: vec![],
+        });
+
+        match JsValue::from_serde(&diagnostics) {
+            Ok(v) => v,
+            Err(err) => format!("failed to serialize diagnostics: {}", err).into(),
+        }
+    }
+}
+
 impl Display for BufferedError {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         Display::fmt(&self.0.read().unwrap(), f)